@@ -0,0 +1,232 @@
+use std::fs;
+use std::io::{
+    Read,
+    Seek,
+    Cursor,
+};
+
+use crate::axml_reader::{
+    AxmlEvent,
+    AxmlReader,
+};
+use crate::res_table::ResTable;
+use crate::error::{
+    AxmlError,
+    Result,
+};
+use crate::{
+    parse_res_table,
+    read_zip_entry,
+};
+
+/* Directory of the APKs built for devices which do not support splits, in
+ * the `.apks` archives of bundletool */
+const STANDALONES_DIR: &str = "standalones/";
+
+/* Prefix of the names of configuration splits (e.g., `config.xxhdpi`) */
+const CONFIG_SPLIT_PREFIX: &str = "config.";
+
+/**
+ * One of the APKs of an app distributed as split APKs: either the base APK,
+ * or a split (with a `split` attribute in its manifest).
+ *
+ * Splits are either feature splits (a dynamic feature module), or
+ * configuration splits, which only hold the resources for some
+ * configurations (densities, languages, ABIs) of the base APK or of a
+ * feature split.
+ */
+#[derive(Debug, Clone)]
+pub struct SplitApk {
+    /* Path of the APK in the bundle */
+    pub path: String,
+
+    /* Package name of the app, the same for every APK of a bundle */
+    pub package: String,
+
+    /* Name of the split, `None` for the base APK */
+    pub split: Option<String>,
+
+    /* Whether this is a feature split (`android:isFeatureSplit`) */
+    pub is_feature_split: bool,
+
+    /* Split this configuration split is for (`configForSplit`), `None` if
+     * the attribute is not set, which means the base APK */
+    pub config_for_split: Option<String>,
+
+    /* Raw binary XML manifest */
+    pub manifest: Vec<u8>,
+}
+
+impl SplitApk {
+    /// Read the manifest and the resource table of an APK held in memory.
+    /// A resource table which cannot be parsed is returned as an error,
+    /// without failing.
+    fn parse(path: &str, raw_apk: Vec<u8>) -> Result<(Self, Result<Option<ResTable>>)> {
+        let mut archive = zip::ZipArchive::new(Cursor::new(raw_apk))?;
+        let manifest = read_zip_entry(&mut archive, "AndroidManifest.xml")?
+                           .ok_or(AxmlError::MissingManifest)?;
+        let resources = read_zip_entry(&mut archive, "resources.arsc")?
+                            .map(|raw_table| parse_res_table(Cursor::new(raw_table)))
+                            .transpose();
+
+        let mut split_apk = SplitApk {
+            path: path.to_string(),
+            package: String::new(),
+            split: None,
+            is_feature_split: false,
+            config_for_split: None,
+            manifest: Vec::new(),
+        };
+
+        /* Everything we need is on the root element */
+        for event in AxmlReader::from_bytes(&manifest) {
+            if let AxmlEvent::StartElement { attrs, .. } = event? {
+                for attr in attrs {
+                    match attr.qualified_name().as_str() {
+                        "package" => split_apk.package = attr.value,
+                        "split" => split_apk.split = Some(attr.value),
+                        "android:isFeatureSplit" => split_apk.is_feature_split = attr.value == "true",
+                        "configForSplit" => split_apk.config_for_split = Some(attr.value),
+                        _ => { },
+                    }
+                }
+                break;
+            }
+        }
+
+        split_apk.manifest = manifest;
+        Ok((split_apk, resources))
+    }
+
+    /// Whether this is the base APK of the app
+    pub fn is_base(&self) -> bool {
+        self.split.is_none()
+    }
+
+    /// Whether this is a configuration split of the base APK, whose
+    /// resources complete the ones of the base APK
+    pub fn is_base_config_split(&self) -> bool {
+        let is_config_split = self.config_for_split.is_some()
+                              || self.split.as_deref().is_some_and(|split| split.starts_with(CONFIG_SPLIT_PREFIX));
+        let for_base = self.config_for_split.as_deref().is_none_or(str::is_empty);
+
+        !self.is_base() && !self.is_feature_split && is_config_split && for_base
+    }
+}
+
+/**
+ * An app distributed as split APKs, in one archive: `.apks` (bundletool),
+ * `.xapk`, `.apkm`, or any zip of APKs.
+ */
+#[derive(Debug)]
+pub struct ApkBundle {
+    /* Base APK */
+    pub base: SplitApk,
+
+    /* Feature and configuration splits, in the order of the archive */
+    pub splits: Vec<SplitApk>,
+
+    /* Resources of the base APK, merged with the ones of its
+     * configuration splits */
+    pub resources: Option<ResTable>,
+
+    /* Errors of the resource tables of the base APK and of its
+     * configuration splits which could not be parsed, and are thus left
+     * out of `resources`, with the path of their APK */
+    pub resources_errors: Vec<(String, AxmlError)>,
+
+    /* APKs of the archive which could not be read (not a zip, no manifest,
+     * or a manifest which cannot be decoded), with their path and error */
+    pub skipped: Vec<(String, AxmlError)>,
+}
+
+impl ApkBundle {
+    /// Open a bundle file
+    pub fn open(file_path: &str) -> Result<Self> {
+        ApkBundle::from_reader(fs::File::open(file_path)?)
+    }
+
+    /// Read a bundle from any seekable source. APKs which cannot be read are
+    /// skipped (see `skipped`). Fails with `AxmlError::MissingManifest` if
+    /// there is no base APK in the bundle.
+    pub fn from_reader<R: Read + Seek>(reader: R) -> Result<Self> {
+        ApkBundle::from_archive(&mut zip::ZipArchive::new(reader)?)
+    }
+
+    pub(crate) fn from_archive<R: Read + Seek>(archive: &mut zip::ZipArchive<R>) -> Result<Self> {
+        let mut apk_names = apk_names(archive);
+        if apk_names.iter().any(|name| !name.starts_with(STANDALONES_DIR)) {
+            apk_names.retain(|name| !name.starts_with(STANDALONES_DIR));
+        }
+
+        let mut base = None;
+        let mut splits = Vec::new();
+        let mut skipped = Vec::new();
+        for name in apk_names {
+            let raw_apk = match read_zip_entry(archive, &name)? {
+                Some(raw_apk) => raw_apk,
+                None => continue,
+            };
+
+            /* A broken split should not prevent reading the others */
+            let (split_apk, resources) = match SplitApk::parse(&name, raw_apk) {
+                Ok(parsed) => parsed,
+                Err(e) => {
+                    skipped.push((name, e));
+                    continue;
+                },
+            };
+            match split_apk.is_base() && base.is_none() {
+                true => base = Some((split_apk, resources)),
+                false => splits.push((split_apk, resources)),
+            }
+        }
+
+        let (base, base_resources) = base.ok_or(AxmlError::MissingManifest)?;
+        let mut resources_errors = Vec::new();
+        let mut resources = base_resources.unwrap_or_else(|e| {
+            resources_errors.push((base.path.clone(), e));
+            None
+        });
+
+        let mut split_apks = Vec::new();
+        for (split_apk, split_resources) in splits {
+            if split_apk.is_base_config_split() {
+                match split_resources {
+                    Ok(Some(split_resources)) => match resources.as_mut() {
+                        Some(resources) => resources.merge(split_resources),
+                        None => resources = Some(split_resources),
+                    },
+                    Ok(None) => { },
+                    Err(e) => resources_errors.push((split_apk.path.clone(), e)),
+                }
+            }
+            split_apks.push(split_apk);
+        }
+
+        Ok(ApkBundle {
+            base,
+            splits: split_apks,
+            resources,
+            resources_errors,
+            skipped,
+        })
+    }
+
+    /// The base APK, then every split
+    pub fn apks(&self) -> impl Iterator<Item = &SplitApk> {
+        std::iter::once(&self.base).chain(self.splits.iter())
+    }
+}
+
+/// Names of the APKs in an archive, in the order of the archive
+pub(crate) fn apk_names<R: Read + Seek>(archive: &mut zip::ZipArchive<R>) -> Vec<String> {
+    (0..archive.len()).filter_map(|index| archive.by_index_raw(index).ok().map(|file| file.name().to_string()))
+                      .filter(|name| name.ends_with(".apk"))
+                      .collect()
+}
+
+/// Whether an archive is a bundle of APKs rather than a single APK
+pub(crate) fn is_bundle<R: Read + Seek>(archive: &mut zip::ZipArchive<R>) -> bool {
+    archive.by_name("AndroidManifest.xml").is_err() && !apk_names(archive).is_empty()
+}
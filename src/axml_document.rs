@@ -0,0 +1,380 @@
+use std::io::Cursor;
+
+use crate::axml_reader::{
+    AxmlEvent,
+    AxmlReader,
+};
+use crate::chunk_walker::UnknownChunk;
+use crate::data_value_type::DataValueType;
+use crate::parser::Attribute;
+use crate::res_value::ResValue;
+use crate::error::{
+    AxmlError,
+    Result,
+};
+
+/// Whether an attribute has the given name, with a namespace given either
+/// as a prefix or as a URI (empty for attributes without namespace)
+fn has_name(attr: &Attribute, namespace: &str, name: &str) -> bool {
+    let ns_matches = match (&attr.prefix, &attr.namespace) {
+        (None, None) => namespace.is_empty(),
+        (prefix, uri) => prefix.as_deref() == Some(namespace)
+                         || uri.as_deref() == Some(namespace),
+    };
+
+    ns_matches && attr.name == name
+}
+
+/// A text node of a decoded binary XML document
+#[derive(Debug, Clone)]
+pub struct Text {
+    /// Text of the node
+    pub text: String,
+
+    /// Typed value of the text, as stored in the document (aapt2 writes
+    /// `TYPE_NULL`, but older tools wrote the string itself)
+    pub typed_value: ResValue,
+
+    /// Line number of the text in the original text XML
+    pub line: u32,
+}
+
+impl Text {
+    /// A new text node, without typed value
+    pub fn new(text: &str, line: u32) -> Self {
+        Text {
+            text: text.to_string(),
+            typed_value: ResValue::new(DataValueType::TypeNull, 0),
+            line,
+        }
+    }
+}
+
+/// A child of an element: either another element or a text node
+#[derive(Debug, Clone)]
+pub enum Node {
+    Element(Element),
+    Text(Text),
+}
+
+impl From<Element> for Node {
+    fn from(element: Element) -> Self {
+        Node::Element(element)
+    }
+}
+
+impl From<Text> for Node {
+    fn from(text: Text) -> Self {
+        Node::Text(text)
+    }
+}
+
+/// An element of a decoded binary XML document, with all its children
+#[derive(Debug, Clone, Default)]
+pub struct Element {
+    /// Local name of the element
+    pub name: String,
+
+    /// Namespace URI of the element, if any
+    pub namespace: Option<String>,
+
+    /// Prefix bound to the namespace URI, if any
+    pub prefix: Option<String>,
+
+    /// Namespace declarations scoped on this element, as `(prefix, uri)`
+    /// pairs. Prefixes made up for namespaces the document uses without
+    /// declaring them are not included, so that they are not written back.
+    pub namespaces: Vec<(String, String)>,
+
+    /// Attributes, in the order in which they appear in the document
+    pub attributes: Vec<Attribute>,
+
+    /// Child elements and text nodes, in document order
+    pub children: Vec<Node>,
+
+    /// Line number of the element in the original text XML
+    pub line: u32,
+}
+
+impl Element {
+    /// A new element without namespace, attributes nor children
+    pub fn new(name: &str) -> Self {
+        Element {
+            name: name.to_string(),
+            ..Element::default()
+        }
+    }
+
+    /// Get an attribute by namespace and name. The namespace can be given
+    /// either as a prefix (`android`) or as a URI; use an empty string for
+    /// attributes without namespace.
+    pub fn attr(&self, namespace: &str, name: &str) -> Option<&Attribute> {
+        self.attributes.iter().find(|attr| has_name(attr, namespace, name))
+    }
+
+    /// Get an attribute by namespace and name to change it, see `attr`
+    pub fn attr_mut(&mut self, namespace: &str, name: &str) -> Option<&mut Attribute> {
+        self.attributes.iter_mut().find(|attr| has_name(attr, namespace, name))
+    }
+
+    /// Add an attribute, or replace the attribute with the same resource ID
+    /// (or, for attributes without ID, the same namespace and name)
+    pub fn set_attr(&mut self, attr: Attribute) {
+        let existing = self.attributes.iter_mut().find(|existing| match (existing.resource_id, attr.resource_id) {
+            (Some(existing_id), Some(id)) => existing_id == id,
+            _ => existing.namespace == attr.namespace && existing.name == attr.name,
+        });
+
+        match existing {
+            Some(existing) => *existing = attr,
+            None => self.attributes.push(attr),
+        }
+    }
+
+    /// Remove an attribute by namespace and name (see `attr`), returning it
+    pub fn remove_attr(&mut self, namespace: &str, name: &str) -> Option<Attribute> {
+        let index = self.attributes.iter().position(|attr| has_name(attr, namespace, name))?;
+        Some(self.attributes.remove(index))
+    }
+
+    /// Get the decoded value of an attribute, see `attr`
+    pub fn attr_value(&self, namespace: &str, name: &str) -> Option<&str> {
+        self.attr(namespace, name).map(|attr| attr.value.as_str())
+    }
+
+    /// Child elements, without the text nodes
+    pub fn elements(&self) -> impl Iterator<Item = &Element> {
+        self.children.iter().filter_map(|child| match child {
+            Node::Element(element) => Some(element),
+            Node::Text(_) => None,
+        })
+    }
+
+    /// Child elements, to change them, see `elements`
+    pub fn elements_mut(&mut self) -> impl Iterator<Item = &mut Element> {
+        self.children.iter_mut().filter_map(|child| match child {
+            Node::Element(element) => Some(element),
+            Node::Text(_) => None,
+        })
+    }
+
+    /// Concatenation of the text nodes directly inside this element
+    pub fn text(&self) -> String {
+        self.children.iter()
+                     .filter_map(|child| match child {
+                         Node::Text(text) => Some(text.text.as_str()),
+                         Node::Element(_) => None,
+                     })
+                     .collect()
+    }
+
+    /// Direct children with the given name
+    pub fn children_named<'a>(&'a self, name: &'a str) -> impl Iterator<Item = &'a Element> + 'a {
+        self.elements().filter(move |child| child.name == name)
+    }
+
+    /// First element with the given name in this subtree (this element included),
+    /// in document order
+    pub fn find(&self, name: &str) -> Option<&Element> {
+        if self.name == name {
+            return Some(self);
+        }
+
+        self.elements().find_map(|child| child.find(name))
+    }
+
+    /// All elements with the given name in this subtree (this element included),
+    /// in document order
+    pub fn find_all(&self, name: &str) -> Vec<&Element> {
+        let mut found = Vec::new();
+        self.collect(name, &mut found);
+        found
+    }
+
+    /// First element with the given name in this subtree, to change it,
+    /// see `find`
+    pub fn find_mut(&mut self, name: &str) -> Option<&mut Element> {
+        if self.name == name {
+            return Some(self);
+        }
+
+        self.elements_mut().find_map(|child| child.find_mut(name))
+    }
+
+    /// Call `f` on every element with the given name in this subtree (this
+    /// element included), in document order, e.g., to set an attribute on
+    /// all the `<activity>` elements of a manifest
+    pub fn for_each_mut<F: FnMut(&mut Element)>(&mut self, name: &str, mut f: F) {
+        self.visit_mut(name, &mut f);
+    }
+
+    fn visit_mut(&mut self, name: &str, f: &mut dyn FnMut(&mut Element)) {
+        if self.name == name {
+            f(self);
+        }
+
+        for child in self.elements_mut() {
+            child.visit_mut(name, f);
+        }
+    }
+
+    /// Remove the direct children with the given name for which `predicate`
+    /// is true, returning how many were removed
+    pub fn remove_children<P: FnMut(&Element) -> bool>(&mut self, name: &str, mut predicate: P) -> usize {
+        let count = self.children.len();
+        self.children.retain(|child| match child {
+            Node::Element(element) => element.name != name || !predicate(element),
+            Node::Text(_) => true,
+        });
+        count - self.children.len()
+    }
+
+    fn collect<'a>(&'a self, name: &str, found: &mut Vec<&'a Element>) {
+        if self.name == name {
+            found.push(self);
+        }
+
+        for child in self.elements() {
+            child.collect(name, found);
+        }
+    }
+}
+
+/**
+ * In-memory tree of a decoded binary XML document.
+ *
+ * The tree is built from the events of an `AxmlReader`, and allows random
+ * access to the elements of the document (e.g., to find the `<application>`
+ * node of a manifest and walk its children).
+ *
+ * The tree can also be changed (attributes set or removed, elements added
+ * or removed) and serialised back to binary XML with `to_bytes`, e.g., to
+ * patch the manifest of an APK. New attributes with a typed value are
+ * built with `XmlEncoder::attribute`.
+ */
+#[derive(Debug, Clone, Default)]
+pub struct AxmlDocument {
+    /// Root element of the document
+    pub root: Element,
+
+    /// Namespace declarations, as `(prefix, uri)` pairs
+    pub namespaces: Vec<(String, String)>,
+
+    /// Resource IDs of the attribute names, indexed like the string pool
+    /// of the decoded document (`to_bytes` builds a new one)
+    pub resource_map: Vec<u32>,
+
+    /// Chunks that could not be decoded
+    pub unknown_chunks: Vec<UnknownChunk>,
+}
+
+impl AxmlDocument {
+    pub fn from_buff<T: AsRef<[u8]>>(axml_buff: Cursor<T>) -> Result<Self> {
+        let position = axml_buff.position();
+        let mut reader_buff = Cursor::new(axml_buff.get_ref().as_ref());
+        reader_buff.set_position(position);
+
+        AxmlDocument::from_reader(AxmlReader::new(reader_buff))
+    }
+
+    /// Build the tree of a document held in a byte slice
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self> {
+        AxmlDocument::from_reader(AxmlReader::from_bytes(bytes))
+    }
+
+    pub fn from_reader(reader: AxmlReader<'_>) -> Result<Self> {
+        AxmlDocument::from_events(reader)
+    }
+
+    /// Build the tree from a sequence of events, e.g., from a
+    /// `ProtoXmlReader` for the XML files of an Android App Bundle
+    pub fn from_events<I: IntoIterator<Item = Result<AxmlEvent>>>(events: I) -> Result<Self> {
+        let mut document = AxmlDocument::default();
+        let mut root = None;
+
+        /* Elements which are not closed yet, the innermost one is last */
+        let mut stack = Vec::<Element>::new();
+
+        /* Declarations read since the previous element */
+        let mut declared = Vec::<(String, String)>::new();
+
+        for event in events {
+            match event? {
+                AxmlEvent::StartElement { name, ns, prefix, namespaces, attrs, line } => {
+                    if root.is_some() && stack.is_empty() {
+                        return Err(AxmlError::MalformedDocument {
+                            line,
+                            reason: format!("second root element <{}>", name),
+                        });
+                    }
+
+                    let namespaces: Vec<(String, String)> = namespaces.into_iter()
+                                                                      .filter(|binding| declared.contains(binding))
+                                                                      .collect();
+                    declared.clear();
+
+                    document.namespaces.extend(namespaces.iter().cloned());
+                    stack.push(Element {
+                        name,
+                        namespace: ns,
+                        prefix,
+                        namespaces,
+                        attributes: attrs,
+                        line,
+                        ..Element::default()
+                    });
+                },
+                AxmlEvent::EndElement { name, line, .. } => {
+                    let element = stack.pop().ok_or_else(|| AxmlError::MalformedDocument {
+                        line,
+                        reason: format!("closing </{}> without opening element", name),
+                    })?;
+
+                    match stack.last_mut() {
+                        Some(parent) => parent.children.push(element.into()),
+                        None => root = Some(element),
+                    }
+                },
+                AxmlEvent::CData { text, typed_value, line } => {
+                    if let Some(current) = stack.last_mut() {
+                        current.children.push(Text { text, typed_value, line }.into());
+                    }
+                },
+                AxmlEvent::ResourceMap(ids) => document.resource_map = ids,
+                AxmlEvent::UnknownChunk(chunk) => document.unknown_chunks.push(chunk),
+                AxmlEvent::StartNamespace { prefix, uri, .. } => declared.push((prefix, uri)),
+                AxmlEvent::EndNamespace { .. } => { },
+            }
+        }
+
+        /* Be lenient with documents which are not properly closed */
+        while let Some(element) = stack.pop() {
+            match stack.last_mut() {
+                Some(parent) => parent.children.push(element.into()),
+                None => root = Some(element),
+            }
+        }
+
+        document.root = root.ok_or(AxmlError::MalformedDocument {
+            line: 0,
+            reason: "no root element".to_string(),
+        })?;
+
+        Ok(document)
+    }
+
+    /// First element with the given name in the document
+    pub fn find(&self, name: &str) -> Option<&Element> {
+        self.root.find(name)
+    }
+
+    /// All elements with the given name in the document, in document order
+    pub fn find_all(&self, name: &str) -> Vec<&Element> {
+        self.root.find_all(name)
+    }
+
+    /// First element with the given name in the document, to change it
+    pub fn find_mut(&mut self, name: &str) -> Option<&mut Element> {
+        self.root.find_mut(name)
+    }
+}
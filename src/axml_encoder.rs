@@ -0,0 +1,498 @@
+use std::cell::RefCell;
+
+use quick_xml::Reader;
+use quick_xml::events::{
+    BytesStart,
+    Event,
+};
+
+use crate::axml_document::{
+    AxmlDocument,
+    Element,
+    Node,
+    Text,
+};
+use crate::data_value_type::DataValueType;
+use crate::framework_res;
+use crate::namespaces;
+use crate::parser::{
+    Attribute,
+    ANDROID_NS_URI,
+    FRAMEWORK_PACKAGE_ID,
+};
+use crate::res_table::{
+    AttributeFormat,
+    ResTable,
+    FORMAT_BOOLEAN,
+    FORMAT_COLOR,
+    FORMAT_DIMENSION,
+    FORMAT_ENUM,
+    FORMAT_FLAGS,
+    FORMAT_FLOAT,
+    FORMAT_FRACTION,
+    FORMAT_INTEGER,
+    FORMAT_STRING,
+};
+use crate::res_value::ResValue;
+use crate::error::{
+    AxmlError,
+    Result,
+};
+
+/* Namespaces of the attributes defined by the app itself: either the
+ * automatic one, or one named after the package */
+const RES_AUTO_NS_URI: &str = "http://schemas.android.com/apk/res-auto";
+const APP_NS_URI_PREFIX: &str = "http://schemas.android.com/apk/res/";
+
+/* Namespace bound to the reserved `xml` prefix */
+const XML_NS_URI: &str = "http://www.w3.org/XML/1998/namespace";
+
+/* Package of the app's own resources */
+const APP_PACKAGE_ID: u32 = 0x7f;
+
+/// Value of an enum or flag attribute given by name, e.g., `portrait` or
+/// `orientation|screenSize`
+fn symbolic_value(format: &AttributeFormat, text: &str) -> Option<ResValue> {
+    if format.accepts(FORMAT_ENUM) {
+        if let Some(data) = format.value(text.trim()) {
+            return Some(ResValue::new(DataValueType::TypeIntDec, data));
+        }
+    }
+
+    if format.accepts(FORMAT_FLAGS) {
+        let mut data = 0;
+        for symbol in text.split('|') {
+            data |= format.value(symbol.trim())?;
+        }
+        return Some(ResValue::new(DataValueType::TypeIntHex, data));
+    }
+
+    None
+}
+
+/// Type of attribute format (`FORMAT_*`) a typed value belongs to
+fn value_format(data_type: DataValueType) -> u32 {
+    match data_type {
+        DataValueType::TypeIntBoolean => FORMAT_BOOLEAN,
+        DataValueType::TypeIntDec
+        | DataValueType::TypeIntHex => FORMAT_INTEGER,
+        DataValueType::TypeIntColorArgb8
+        | DataValueType::TypeIntColorRgb8
+        | DataValueType::TypeIntColorArgb4
+        | DataValueType::TypeIntColorRgb4 => FORMAT_COLOR,
+        DataValueType::TypeFloat => FORMAT_FLOAT,
+        DataValueType::TypeDimension => FORMAT_DIMENSION,
+        DataValueType::TypeFraction => FORMAT_FRACTION,
+        _ => 0,
+    }
+}
+
+/// Split a qualified name into its prefix, if any, and its local name
+fn split_name(qualified_name: &str) -> (Option<&str>, &str) {
+    match qualified_name.split_once(':') {
+        Some((prefix, name)) => (Some(prefix), name),
+        None => (None, qualified_name),
+    }
+}
+
+/// Add text to an element, in the same node as the text just before it,
+/// if any (e.g., text around a CDATA section)
+fn push_text(element: &mut Element, text: &str, line: u32) {
+    match element.children.last_mut() {
+        Some(Node::Text(last)) => last.text.push_str(text),
+        _ => element.children.push(Text::new(text, line).into()),
+    }
+}
+
+/**
+ * Compiler of text XML files (manifests, layouts, etc.) into binary XML.
+ *
+ * Attributes in the Android namespace are given the resource ID of the
+ * framework attribute with the same name, and their values are typed like
+ * aapt does, following the format of the attribute: booleans, integers,
+ * colours, dimensions, enums and flags become typed values, and references
+ * such as `?android:attr/colorAccent` or `@0x7f0c0001` are resolved to
+ * resource IDs. Values which do not match the format of their attribute are
+ * an error, unless it accepts strings. Attributes without a resource ID are
+ * kept as strings, and attributes whose format is not known accept any
+ * type of value.
+ *
+ * The framework's resources and attribute formats come from a partial
+ * built-in table, or from the framework's resource table when given with
+ * `with_framework` (e.g., the one of an SDK's `android.jar`). References to
+ * the app's own resources (`@string/app_name`) and the IDs and formats of
+ * attributes defined by the app can only be resolved with the app's
+ * resource table, see `with_resources`. IDs declared with `@+id/name` which
+ * are not in the app's resource table are given new IDs, in the order in
+ * which they are declared.
+ */
+#[derive(Debug, Clone, Default)]
+pub struct XmlEncoder<'a> {
+    resources: Option<&'a ResTable>,
+    framework: Option<&'a ResTable>,
+
+    /* Names of the IDs declared with `@+id/` which are not in the app's
+     * resource table, by order of declaration */
+    new_ids: RefCell<Vec<String>>,
+}
+
+impl<'a> XmlEncoder<'a> {
+    pub fn new() -> Self {
+        XmlEncoder::default()
+    }
+
+    /// Resolve the references to the app's resources with its resource table
+    pub fn with_resources(mut self, resources: &'a ResTable) -> Self {
+        self.resources = Some(resources);
+        self
+    }
+
+    /// Resolve the references to the framework's resources, and type the
+    /// values of its attributes, with its resource table instead of the
+    /// built-in one
+    pub fn with_framework(mut self, framework: &'a ResTable) -> Self {
+        self.framework = Some(framework);
+        self
+    }
+
+    /// Compile a text XML document into binary XML
+    pub fn encode(&self, text: &str) -> Result<Vec<u8>> {
+        Ok(self.parse(text)?.to_bytes())
+    }
+
+    /// Parse a text XML document into a tree with typed attribute values,
+    /// ready to be serialised with `AxmlDocument::to_bytes`
+    pub fn parse(&self, text: &str) -> Result<AxmlDocument> {
+        let mut reader = Reader::from_str(text);
+        let mut document = AxmlDocument::default();
+        let mut root = None;
+
+        /* Elements which are not closed yet, the innermost one is last */
+        let mut stack = Vec::<Element>::new();
+
+        /* Line of the current event, counted up to `counted` */
+        let mut line = 1;
+        let mut counted = 0;
+
+        loop {
+            let position = reader.buffer_position();
+            line += text.as_bytes()[counted..position].iter().filter(|&&byte| byte == b'\n').count() as u32;
+            counted = position;
+
+            let (start, is_empty) = match reader.read_event()? {
+                Event::Start(start) => (start, false),
+                Event::Empty(start) => (start, true),
+                Event::End(_) => {
+                    let element = stack.pop().ok_or_else(|| AxmlError::MalformedDocument {
+                        line,
+                        reason: "closing tag without opening element".to_string(),
+                    })?;
+                    match stack.last_mut() {
+                        Some(parent) => parent.children.push(element.into()),
+                        None => root = Some(element),
+                    }
+                    continue;
+                },
+                Event::Text(text) => {
+                    let text = text.unescape()?;
+                    if let (Some(current), false) = (stack.last_mut(), text.trim().is_empty()) {
+                        push_text(current, &text, line);
+                    }
+                    continue;
+                },
+                Event::CData(cdata) => {
+                    if let Some(current) = stack.last_mut() {
+                        push_text(current, &String::from_utf8_lossy(&cdata), line);
+                    }
+                    continue;
+                },
+                Event::Eof => break,
+                _ => continue,
+            };
+
+            if root.is_some() && stack.is_empty() {
+                return Err(AxmlError::MalformedDocument {
+                    line,
+                    reason: "second root element".to_string(),
+                });
+            }
+
+            let element = self.element(&start, &stack, line)?;
+            document.namespaces.extend(element.namespaces.iter().cloned());
+            match is_empty {
+                true => match stack.last_mut() {
+                    Some(parent) => parent.children.push(element.into()),
+                    None => root = Some(element),
+                },
+                false => stack.push(element),
+            }
+        }
+
+        if let Some(element) = stack.last() {
+            return Err(AxmlError::MalformedDocument {
+                line,
+                reason: format!("<{}> is not closed", element.name),
+            });
+        }
+
+        document.root = root.ok_or(AxmlError::MalformedDocument {
+            line: 0,
+            reason: "no root element".to_string(),
+        })?;
+
+        Ok(document)
+    }
+
+    /// Build an element from its start tag. `ancestors` are the elements it
+    /// is nested in, whose namespace declarations are in scope.
+    fn element(&self, start: &BytesStart, ancestors: &[Element], line: u32) -> Result<Element> {
+        let mut element = Element {
+            line,
+            ..Element::default()
+        };
+
+        /* Namespace declarations come first, they apply to the element itself */
+        let mut attributes = Vec::new();
+        for attr in start.attributes() {
+            let attr = attr.map_err(quick_xml::Error::from)?;
+            let name = String::from_utf8_lossy(attr.key.as_ref()).into_owned();
+            let value = attr.unescape_value()?.into_owned();
+
+            match name.strip_prefix("xmlns") {
+                Some("") => element.namespaces.push((String::new(), value)),
+                Some(prefix) if prefix.starts_with(':') => element.namespaces.push((prefix[1..].to_string(), value)),
+                _ => attributes.push((name, value)),
+            }
+        }
+
+        let resolve = |prefix: &str| -> Result<String> {
+            if prefix == "xml" {
+                return Ok(XML_NS_URI.to_string());
+            }
+
+            std::iter::once(&element).chain(ancestors.iter().rev())
+                .flat_map(|scope| scope.namespaces.iter().rev())
+                .find(|(bound, _)| bound == prefix)
+                .map(|(_, uri)| uri.clone())
+                .ok_or_else(|| AxmlError::MalformedDocument {
+                    line,
+                    reason: format!("undeclared namespace prefix \"{}\"", prefix),
+                })
+        };
+
+        /* Elements without prefix are in the default namespace, if any */
+        let qualified_name = String::from_utf8_lossy(start.name().as_ref()).into_owned();
+        let (prefix, name) = split_name(&qualified_name);
+        let namespace = match prefix {
+            Some(prefix) => Some(resolve(prefix)?),
+            None => resolve("").ok().filter(|uri| !uri.is_empty()),
+        };
+
+        let mut decoded_attrs = Vec::new();
+        for (qualified_name, value) in attributes.iter() {
+            let (attr_prefix, attr_name) = split_name(qualified_name);
+            let attr_namespace = attr_prefix.map(&resolve).transpose()?;
+            decoded_attrs.push(self.typed_attribute(attr_namespace, attr_prefix, attr_name, value, line)?);
+        }
+
+        element.name = name.to_string();
+        element.prefix = namespace.as_ref().map(|_| prefix.unwrap_or_default().to_string());
+        element.namespace = namespace;
+        element.attributes = decoded_attrs;
+        Ok(element)
+    }
+
+    /// Build an attribute from its namespace URI, name and text value, with
+    /// its resource ID and typed value, e.g., to add it to an element with
+    /// `Element::set_attr`
+    pub fn attribute(&self, namespace: Option<&str>, name: &str, value: &str) -> Result<Attribute> {
+        let prefix = namespace.and_then(namespaces::well_known_prefix);
+        self.typed_attribute(namespace.map(str::to_string), prefix, name, value, 0)
+    }
+
+    /// Build an attribute with its resource ID and typed value
+    fn typed_attribute(&self, namespace: Option<String>, prefix: Option<&str>, name: &str,
+                       value: &str, line: u32) -> Result<Attribute> {
+        /* Android ignores attributes of its namespace without an ID, so
+         * they cannot be written as plain strings */
+        let resource_id = match namespace.as_deref() {
+            Some(ANDROID_NS_URI) => match self.framework_id("attr", name) {
+                Some(resource_id) => Some(resource_id),
+                None => return Err(AxmlError::UnknownAttribute { line, name: format!("android:{}", name) }),
+            },
+            Some(uri) if uri == RES_AUTO_NS_URI || uri.starts_with(APP_NS_URI_PREFIX) =>
+                self.resources.and_then(|resources| resources.resource_id("attr", name)),
+            _ => None,
+        };
+
+        let typed_value = self.typed_value(name, resource_id, value, line)?;
+        let raw_value = match typed_value.data_type {
+            DataValueType::TypeString => Some(value.strip_prefix('\\').unwrap_or(value).to_string()),
+            _ => None,
+        };
+
+        Ok(Attribute {
+            namespace,
+            prefix: prefix.map(str::to_string),
+            name: name.to_string(),
+            resource_id,
+            pool_name: None,
+            value: raw_value.clone().unwrap_or_else(|| value.to_string()),
+            raw_value,
+            typed_value,
+        })
+    }
+
+    /// Type the value of an attribute, following its format. Attributes
+    /// without a resource ID are strings, like with aapt.
+    fn typed_value(&self, name: &str, resource_id: Option<u32>, value: &str, line: u32) -> Result<ResValue> {
+        let string = ResValue::new(DataValueType::TypeString, 0);
+        let resource_id = match resource_id {
+            Some(resource_id) => resource_id,
+            None => return Ok(string),
+        };
+
+        /* A leading backslash escapes the first character, e.g., `\@` */
+        if value.starts_with('\\') {
+            return Ok(string);
+        }
+
+        if value.starts_with('@') || value.starts_with('?') {
+            return self.reference(value, line);
+        }
+
+        let format = self.attribute_format(resource_id);
+        if let Some(typed_value) = symbolic_value(&format, value) {
+            return Ok(typed_value);
+        }
+
+        /* Like aapt, integers are taken as floats by attributes which only
+         * accept floating point numbers */
+        let typed_value = ResValue::parse(value).and_then(|typed_value| {
+            match value_format(typed_value.data_type) {
+                types if format.accepts(types) => Some(typed_value),
+                FORMAT_INTEGER if format.accepts(FORMAT_FLOAT) => {
+                    let float = value.trim().parse::<f32>().ok()?;
+                    Some(ResValue::new(DataValueType::TypeFloat, float.to_bits()))
+                },
+                _ => None,
+            }
+        });
+
+        match typed_value {
+            Some(typed_value) => Ok(typed_value),
+            None if format.accepts(FORMAT_STRING) => Ok(string),
+            None => Err(AxmlError::MalformedDocument {
+                line,
+                reason: format!("\"{}\" is not a valid value for attribute {}", value, name),
+            }),
+        }
+    }
+
+    /// Format of the attribute with the given ID, from the framework's or
+    /// the app's resource table. Attributes whose format is not known
+    /// accept any type of value.
+    fn attribute_format(&self, resource_id: u32) -> AttributeFormat {
+        let format = match resource_id >> 24 {
+            FRAMEWORK_PACKAGE_ID => match self.framework {
+                Some(framework) => framework.attribute_format(resource_id),
+                None => framework_res::framework_attribute(resource_id).map(|(types, values)| AttributeFormat {
+                    types,
+                    values: values.iter().map(|&(name, value)| (name.to_string(), value)).collect(),
+                }),
+            },
+            _ => self.resources.and_then(|resources| resources.attribute_format(resource_id)),
+        };
+
+        format.unwrap_or_else(AttributeFormat::any)
+    }
+
+    /// ID of a resource of the framework
+    fn framework_id(&self, type_name: &str, entry: &str) -> Option<u32> {
+        match self.framework {
+            Some(framework) => framework.resource_id(type_name, entry),
+            None => framework_res::framework_id(type_name, entry),
+        }
+    }
+
+    /// ID of an ID declared with `@+id/name` which is not in the app's
+    /// resource table. IDs are added after the last entry of the `id` type
+    /// of the app's package or, if it has none, in a new type after its
+    /// other types.
+    fn new_id(&self, name: &str, create: bool) -> Option<u32> {
+        let mut new_ids = self.new_ids.borrow_mut();
+        let index = match new_ids.iter().position(|new_id| new_id == name) {
+            Some(index) => index,
+            None if create => {
+                new_ids.push(name.to_string());
+                new_ids.len() - 1
+            },
+            None => return None,
+        };
+
+        let package = self.resources.and_then(|resources| {
+            resources.packages.iter().find(|package| package.id != FRAMEWORK_PACKAGE_ID)
+        });
+        let (package_id, type_id, first_entry) = match package {
+            Some(package) => match package.type_specs.iter().find(|spec| package.type_name(spec.id).as_deref() == Some("id")) {
+                Some(spec) => (package.id, spec.id as u32, spec.entry_count),
+                None => (package.id, package.type_specs.iter().map(|spec| spec.id as u32).max().unwrap_or(0) + 1, 0),
+            },
+            None => (APP_PACKAGE_ID, 1, 0),
+        };
+
+        Some(package_id << 24 | type_id << 16 | (first_entry + index as u32))
+    }
+
+    /// Resolve a reference to a resource (`@type/name`) or to a theme
+    /// attribute (`?name`), with or without package
+    fn reference(&self, text: &str, line: u32) -> Result<ResValue> {
+        match text {
+            "@null" => return Ok(ResValue::new(DataValueType::TypeReference, 0)),
+            "@empty" => return Ok(ResValue::new(DataValueType::TypeNull, 1)),
+            _ => { },
+        }
+
+        let (data_type, reference) = match text.split_at(1) {
+            ("?", reference) => (DataValueType::TypeAttribute, reference),
+            (_, reference) => (DataValueType::TypeReference, reference),
+        };
+
+        /* `@+id/name` declares an ID, `@*android:...` is a private resource */
+        let create = reference.starts_with('+');
+        let reference = reference.trim_start_matches(['+', '*']);
+        let unresolved = || AxmlError::UnresolvedReference {
+            line,
+            reference: text.to_string(),
+        };
+
+        if let Some(hex) = reference.strip_prefix("0x") {
+            let id = u32::from_str_radix(hex, 16).map_err(|_| unresolved())?;
+            return Ok(ResValue::new(data_type, id));
+        }
+
+        /* The type of theme attributes is implicit */
+        let (package, name) = split_name(reference);
+        let (type_name, entry) = match (name.split_once('/'), data_type) {
+            (Some(type_and_entry), _) => type_and_entry,
+            (None, DataValueType::TypeAttribute) => ("attr", name),
+            (None, _) => return Err(unresolved()),
+        };
+
+        let id = match package {
+            Some("android") => self.framework_id(type_name, entry),
+            _ => self.resources.and_then(|resources| resources.resource_id(type_name, entry))
+                     .or_else(|| match type_name {
+                         "id" => self.new_id(entry, create),
+                         _ => None,
+                     }),
+        };
+
+        id.map(|id| ResValue::new(data_type, id)).ok_or_else(unresolved)
+    }
+}
+
+/// Compile a text XML document into binary XML, see `XmlEncoder`
+pub fn encode_xml(text: &str) -> Result<Vec<u8>> {
+    XmlEncoder::new().encode(text)
+}
@@ -0,0 +1,221 @@
+use std::io::Cursor;
+
+use crate::parser::{
+    self,
+    Attribute,
+};
+use crate::chunk_header::ChunkHeader;
+use crate::chunk_walker::{
+    ChunkWalker,
+    UnknownChunk,
+};
+use crate::namespaces::NamespaceStack;
+use crate::resource_map::ResourceMap;
+use crate::res_value::ResValue;
+use crate::string_pool::StringPool;
+use crate::xml_types::XmlTypes;
+use crate::res_resolver::ResourceResolver;
+use crate::res_table::ResTable;
+use crate::error::Result;
+
+/// An event produced while decoding a binary XML document
+#[derive(Debug, Clone)]
+pub enum AxmlEvent {
+    /// A namespace prefix is bound to a URI
+    StartNamespace { prefix: String, uri: String, line: u32 },
+
+    /// The scope of a namespace binding ends
+    EndNamespace { prefix: String, uri: String, line: u32 },
+
+    /// Opening tag of an element. `ns` is the namespace URI of the element,
+    /// if any, and `prefix` the prefix it is bound to. `namespaces` are the
+    /// `(prefix, uri)` declarations scoped on this element.
+    StartElement {
+        name: String,
+        ns: Option<String>,
+        prefix: Option<String>,
+        namespaces: Vec<(String, String)>,
+        attrs: Vec<Attribute>,
+        line: u32,
+    },
+
+    /// Closing tag of an element
+    EndElement { name: String, ns: Option<String>, prefix: Option<String>, line: u32 },
+
+    /// Text content of an element. `typed_value` is usually null, but aapt
+    /// can store a typed version of the text (e.g., an integer)
+    CData { text: String, typed_value: ResValue, line: u32 },
+
+    /// Resource IDs of the attribute names, indexed like the string pool
+    ResourceMap(Vec<u32>),
+
+    /// A chunk that the parser does not know how to decode
+    UnknownChunk(UnknownChunk),
+}
+
+/**
+ * Pull parser for binary XML documents.
+ *
+ * The reader walks over the chunks of the document and yields one
+ * `AxmlEvent` per chunk of interest. String pools and the document header
+ * are consumed internally. Iteration stops after the first error.
+ *
+ * The document is borrowed from an in-memory buffer (a `Vec<u8>`, a slice
+ * of a larger buffer, a memory-mapped file, etc.), and so is the string
+ * pool: strings are only decoded when an event needs them. To decode a
+ * document from a `Read` source, see `parse_axml_reader`, which reads it
+ * in memory first.
+ */
+pub struct AxmlReader<'a> {
+    axml_buff: Cursor<&'a [u8]>,
+    walker: ChunkWalker,
+    strings: StringPool<'a>,
+    namespaces: NamespaceStack,
+    resource_map: Vec<u32>,
+    resolver: Option<&'a dyn ResourceResolver>,
+    resources: Option<&'a ResTable>,
+    done: bool,
+}
+
+impl<'a> AxmlReader<'a> {
+    /// Read a document from a byte slice, without copying it
+    pub fn from_bytes(bytes: &'a [u8]) -> Self {
+        AxmlReader::new(Cursor::new(bytes))
+    }
+
+    /// Read a document from the current position of `axml_buff`
+    pub fn new(axml_buff: Cursor<&'a [u8]>) -> Self {
+        let walker = ChunkWalker::new(&axml_buff);
+
+        AxmlReader {
+            axml_buff,
+            walker,
+            strings: StringPool::empty(),
+            namespaces: NamespaceStack::new(),
+            resource_map: Vec::new(),
+            resolver: None,
+            resources: None,
+            done: false,
+        }
+    }
+
+    /// Use `resolver` to give symbolic names to resource references
+    /// (e.g., `@string/app_name` instead of `@0x7f0a0012`)
+    pub fn with_resolver(mut self, resolver: &'a dyn ResourceResolver) -> Self {
+        self.resolver = Some(resolver);
+        self
+    }
+
+    /// Use the app's resource table to replace references to string, bool
+    /// and integer resources by their value (e.g., the app name instead of
+    /// `@string/app_name`), and to name the other references
+    pub fn with_resources(mut self, resources: &'a ResTable) -> Self {
+        self.resolver = Some(resources);
+        self.resources = Some(resources);
+        self
+    }
+
+    /// Strings of the document's string pool (empty until the pool is read)
+    pub fn strings(&self) -> &StringPool<'a> {
+        &self.strings
+    }
+
+    /// Resource IDs of the attribute names, indexed like the string pool
+    /// (empty until the resource map is read)
+    pub fn resource_map(&self) -> &[u32] {
+        &self.resource_map
+    }
+
+    /// Namespace declarations in scope
+    pub fn namespaces(&self) -> &NamespaceStack {
+        &self.namespaces
+    }
+
+    /// Decode chunks until one produces an event, returning `None` at the
+    /// end of the document
+    fn next_event(&mut self) -> Result<Option<AxmlEvent>> {
+        while let Some(chunk) = self.walker.next_chunk(&mut self.axml_buff)? {
+            let block_type = match chunk.chunk_type {
+                Some(block_type) => block_type,
+                None => {
+                    let unknown = UnknownChunk::from_buff(&self.axml_buff, &chunk)?;
+                    return Ok(Some(AxmlEvent::UnknownChunk(unknown)));
+                }
+            };
+
+            let event = match block_type {
+                XmlTypes::ResNullType => continue,
+                XmlTypes::ResStringPoolType => {
+                    let data: &'a [u8] = self.axml_buff.get_ref();
+                    self.strings = StringPool::parse(data, chunk.offset as usize)?;
+                    continue;
+                },
+                XmlTypes::ResXmlType => {
+                    /* Go back 2 bytes, to account from the block type */
+                    let offset = self.axml_buff.position();
+                    self.axml_buff.set_position(offset - 2);
+
+                    ChunkHeader::from_buff(&mut self.axml_buff, XmlTypes::ResXmlType)?;
+                    continue;
+                },
+                XmlTypes::ResXmlStartNamespaceType => {
+                    parser::parse_start_namespace(&mut self.axml_buff, &self.strings, &mut self.namespaces)?
+                },
+                XmlTypes::ResXmlEndNamespaceType => {
+                    parser::parse_end_namespace(&mut self.axml_buff, &self.strings, &mut self.namespaces)?
+                },
+                XmlTypes::ResXmlStartElementType => {
+                    let mut event = parser::parse_start_element(&mut self.axml_buff, &self.strings, &mut self.namespaces, &self.resource_map, self.resolver)?;
+                    if let (Some(resources), AxmlEvent::StartElement { attrs, .. }) = (self.resources, &mut event) {
+                        for attr in attrs.iter_mut() {
+                            if let Some(value) = resources.substitute(&attr.typed_value) {
+                                attr.value = value;
+                            }
+                        }
+                    }
+                    event
+                },
+                XmlTypes::ResXmlEndElementType => {
+                    parser::parse_end_element(&mut self.axml_buff, &self.strings, &mut self.namespaces)?
+                },
+                XmlTypes::ResXmlCDataType => {
+                    parser::parse_cdata(&mut self.axml_buff, &self.strings)?
+                },
+                XmlTypes::ResXmlResourceMapType => {
+                    let resource_map = ResourceMap::from_buff(&mut self.axml_buff)?;
+                    self.resource_map = resource_map.resources_id.clone();
+                    AxmlEvent::ResourceMap(resource_map.resources_id)
+                },
+
+                /* Everything else does not belong in a binary XML document */
+                _ => AxmlEvent::UnknownChunk(UnknownChunk::from_buff(&self.axml_buff, &chunk)?),
+            };
+
+            return Ok(Some(event));
+        }
+
+        Ok(None)
+    }
+}
+
+impl Iterator for AxmlReader<'_> {
+    type Item = Result<AxmlEvent>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.done {
+            return None;
+        }
+
+        match self.next_event() {
+            Ok(Some(event)) => Some(Ok(event)),
+            Ok(None) => {
+                self.done = true;
+                None
+            },
+            Err(e) => {
+                self.done = true;
+                Some(Err(e))
+            },
+        }
+    }
+}
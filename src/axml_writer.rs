@@ -0,0 +1,250 @@
+use std::collections::HashMap;
+
+use crate::axml_document::{
+    AxmlDocument,
+    Element,
+    Node,
+};
+use crate::data_value_type::DataValueType;
+use crate::parser::{
+    Attribute,
+    NO_ENTRY,
+};
+use crate::string_pool;
+use crate::write_ext::{
+    self,
+    WriteExt,
+};
+use crate::xml_types::XmlTypes;
+
+/* Size of the ResXMLTree_attrExt structure, after which the attributes start */
+const ATTRIBUTES_START: u16 = 20;
+
+/* Size of a ResXMLTree_attribute structure */
+const ATTRIBUTE_SIZE: u16 = 20;
+
+/**
+ * Strings of the document being written, in the order of the string pool.
+ *
+ * Names of attributes with a resource ID come first, so that the resource
+ * map (indexed like the pool) only covers them. They are never shared with
+ * other strings: a string that is also used as a value, or as the name of
+ * an attribute without ID, gets its own entry.
+ */
+#[derive(Debug, Default)]
+struct PoolBuilder {
+    strings: Vec<String>,
+    resource_map: Vec<u32>,
+
+    /* Indices of the attribute names with an ID, and of the other strings */
+    attribute_names: HashMap<(String, u32), u32>,
+    others: HashMap<String, u32>,
+}
+
+impl PoolBuilder {
+    /// Index of the name of an attribute with a resource ID
+    fn attribute_name(&mut self, name: &str, resource_id: u32) -> u32 {
+        let key = (name.to_string(), resource_id);
+        if let Some(&index) = self.attribute_names.get(&key) {
+            return index;
+        }
+
+        let index = self.strings.len() as u32;
+        self.strings.push(name.to_string());
+        self.resource_map.push(resource_id);
+        self.attribute_names.insert(key, index);
+        index
+    }
+
+    /// Index of any other string. Must only be called once all the
+    /// attribute names with an ID are in the pool.
+    fn string(&mut self, string: &str) -> u32 {
+        if let Some(&index) = self.others.get(string) {
+            return index;
+        }
+
+        let index = self.strings.len() as u32;
+        self.strings.push(string.to_string());
+        self.others.insert(string.to_string(), index);
+        index
+    }
+
+    fn optional_string(&mut self, string: Option<&str>) -> u32 {
+        string.map_or(NO_ENTRY, |string| self.string(string))
+    }
+
+    /// Add the names of the attributes with an ID of `element` and of its
+    /// children, in document order
+    fn add_attribute_names(&mut self, element: &Element) {
+        for attr in sorted_attributes(element) {
+            if let Some(id) = attr.resource_id {
+                self.attribute_name(pool_name(attr), id);
+            }
+        }
+
+        for child in element.elements() {
+            self.add_attribute_names(child);
+        }
+    }
+}
+
+/// Name of an attribute in the string pool, which is kept as it was if it
+/// differs from its canonical name
+fn pool_name(attr: &Attribute) -> &str {
+    attr.pool_name.as_deref().unwrap_or(&attr.name)
+}
+
+/// Attributes of an element, in the order Android expects them: attributes
+/// with a resource ID first, by increasing ID (the framework looks them up
+/// with a merge of two sorted lists), then the other ones as they are
+fn sorted_attributes(element: &Element) -> Vec<&Attribute> {
+    let mut attributes: Vec<&Attribute> = element.attributes.iter().collect();
+    attributes.sort_by_key(|attr| match attr.resource_id {
+        Some(id) => (0, id),
+        None => (1, 0),
+    });
+    attributes
+}
+
+/// 1-based position of an attribute without namespace among the attributes
+/// of an element, 0 if there is none. This is how elements point to their
+/// `id`, `class` and `style` attributes (`android:id` does not count).
+fn special_index(attributes: &[&Attribute], name: &str) -> u16 {
+    attributes.iter()
+              .position(|attr| attr.namespace.is_none() && attr.name == name)
+              .map_or(0, |index| index as u16 + 1)
+}
+
+/// Append a node chunk: the line number and comment of the node, then
+/// `data` (the ResXMLTree_*Ext structure of the node)
+fn write_node(out: &mut Vec<u8>, chunk_type: XmlTypes, line: u32, data: &[u8]) {
+    let mut header = Vec::new();
+    header.write_u32_le(line);
+    header.write_u32_le(NO_ENTRY);
+
+    write_ext::write_chunk(out, chunk_type as u16, &header, data);
+}
+
+/// Append the chunks of `element` and of its subtree
+fn write_element(out: &mut Vec<u8>, pool: &mut PoolBuilder, element: &Element) {
+    let namespaces: Vec<(u32, u32)> = element.namespaces.iter()
+                                             .map(|(prefix, uri)| (pool.string(prefix), pool.string(uri)))
+                                             .collect();
+    for &(prefix, uri) in namespaces.iter() {
+        let mut data = Vec::new();
+        data.write_u32_le(prefix);
+        data.write_u32_le(uri);
+        write_node(out, XmlTypes::ResXmlStartNamespaceType, element.line, &data);
+    }
+
+    let namespace = pool.optional_string(element.namespace.as_deref());
+    let name = pool.string(&element.name);
+    let attributes = sorted_attributes(element);
+
+    let id_index = special_index(&attributes, "id");
+    let class_index = special_index(&attributes, "class");
+    let style_index = special_index(&attributes, "style");
+
+    let mut data = Vec::new();
+    data.write_u32_le(namespace);
+    data.write_u32_le(name);
+    data.write_u16_le(ATTRIBUTES_START);
+    data.write_u16_le(ATTRIBUTE_SIZE);
+    data.write_u16_le(attributes.len() as u16);
+    data.write_u16_le(id_index);
+    data.write_u16_le(class_index);
+    data.write_u16_le(style_index);
+
+    for attr in attributes {
+        let attr_namespace = pool.optional_string(attr.namespace.as_deref());
+        let attr_name = match attr.resource_id {
+            Some(id) => pool.attribute_name(pool_name(attr), id),
+            None => pool.string(pool_name(attr)),
+        };
+
+        /* String values are indices into the pool, and are kept as the
+         * raw value as well */
+        let mut typed_value = attr.typed_value;
+        let raw_value = match typed_value.data_type {
+            DataValueType::TypeString => {
+                typed_value.data = pool.string(attr.raw_value.as_deref().unwrap_or(&attr.value));
+                typed_value.data
+            },
+            _ => pool.optional_string(attr.raw_value.as_deref()),
+        };
+
+        data.write_u32_le(attr_namespace);
+        data.write_u32_le(attr_name);
+        data.write_u32_le(raw_value);
+        typed_value.write(&mut data);
+    }
+    write_node(out, XmlTypes::ResXmlStartElementType, element.line, &data);
+
+    for child in element.children.iter() {
+        match child {
+            Node::Element(child) => write_element(out, pool, child),
+            Node::Text(text) => {
+                /* A string value is the index of the text in the pool */
+                let mut typed_value = text.typed_value;
+                let index = pool.string(&text.text);
+                if typed_value.data_type == DataValueType::TypeString {
+                    typed_value.data = index;
+                }
+
+                let mut data = Vec::new();
+                data.write_u32_le(index);
+                typed_value.write(&mut data);
+                write_node(out, XmlTypes::ResXmlCDataType, text.line, &data);
+            },
+        }
+    }
+
+    let mut data = Vec::new();
+    data.write_u32_le(namespace);
+    data.write_u32_le(name);
+    write_node(out, XmlTypes::ResXmlEndElementType, element.line, &data);
+
+    for &(prefix, uri) in namespaces.iter().rev() {
+        let mut data = Vec::new();
+        data.write_u32_le(prefix);
+        data.write_u32_le(uri);
+        write_node(out, XmlTypes::ResXmlEndNamespaceType, element.line, &data);
+    }
+}
+
+impl AxmlDocument {
+    /// Serialise the document as binary XML, the way aapt2 does for
+    /// manifests: a UTF-16 string pool, the resource map of the attribute
+    /// names, then the chunks of the elements.
+    ///
+    /// The string pool and the resource map are rebuilt from the tree, so
+    /// that elements and attributes can be freely added, removed or
+    /// changed before. The typed value of string attributes is ignored and
+    /// replaced by the index of their value in the new pool. Unknown chunks
+    /// are written as is after the resource map, since their position
+    /// among the elements is not kept.
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut pool = PoolBuilder::default();
+        pool.add_attribute_names(&self.root);
+
+        let mut nodes = Vec::new();
+        write_element(&mut nodes, &mut pool, &self.root);
+
+        let mut body = string_pool::encode_string_pool(&pool.strings, false);
+        if !pool.resource_map.is_empty() {
+            let mut ids = Vec::new();
+            for &id in pool.resource_map.iter() {
+                ids.write_u32_le(id);
+            }
+            write_ext::write_chunk(&mut body, XmlTypes::ResXmlResourceMapType as u16, &[], &ids);
+        }
+        for chunk in self.unknown_chunks.iter() {
+            body.extend_from_slice(&chunk.data);
+        }
+        body.extend_from_slice(&nodes);
+
+        let mut out = Vec::new();
+        write_ext::write_chunk(&mut out, XmlTypes::ResXmlType as u16, &[], &body);
+        out
+    }
+}
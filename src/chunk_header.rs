@@ -1,14 +1,12 @@
 #![allow(dead_code)]
 
-use std::io::{
-    Error,
-    Cursor,
-};
-use byteorder::{
-    LittleEndian,
-    ReadBytesExt,
-};
+use std::io::Cursor;
 use crate::xml_types::XmlTypes;
+use crate::read_ext::ReadExt;
+use crate::error::{
+    AxmlError,
+    Result,
+};
 
 /* Header that appears at the beginning of every chunk */
 #[derive(Debug)]
@@ -32,34 +30,37 @@ pub struct ChunkHeader {
 
 impl ChunkHeader {
 
-    pub fn from_buff(axml_buff: &mut Cursor<Vec<u8>>, expected_type: XmlTypes) -> Result<Self, Error> {
+    pub fn from_buff<T: AsRef<[u8]>>(axml_buff: &mut Cursor<T>, expected_type: XmlTypes) -> Result<Self> {
         /* Minimum size, for a chunk with no data */
         let minimum_size = 8;
+        let offset = axml_buff.position();
 
         /* Get chunk type */
-        let chunk_type = XmlTypes::parse_block_type(axml_buff)
-                        .expect("Error: cannot parse block type");
+        let chunk_type = XmlTypes::parse_block_type(axml_buff)?;
 
         /* Check if this is indeed of the expected type */
         if chunk_type != expected_type {
-            panic!("Error: unexpected XML chunk type");
+            return Err(AxmlError::UnexpectedChunkType {
+                offset,
+                expected: expected_type as u16,
+                found: chunk_type as u16,
+            });
         }
 
         /* Get chunk header size and total size */
-        let header_size = axml_buff.read_u16::<LittleEndian>().unwrap();
-        let size = axml_buff.read_u32::<LittleEndian>().unwrap();
+        let header_size = axml_buff.read_u16_le()?;
+        let size = axml_buff.read_u32_le()?;
 
         /* Exhaustive checks on the announced sizes */
-        if header_size < minimum_size {
-            panic!("Error: parsed header size is smaller than the minimum");
-        }
-
-        if size < minimum_size.into() {
-            panic!("Error: parsed total size is smaller than the minimum");
+        if header_size < minimum_size
+            || size < minimum_size.into()
+            || size < header_size.into() {
+            return Err(AxmlError::SizeMismatch { offset, header_size, size });
         }
 
-        if size < header_size.into() {
-            panic!("Error: parsed total size if smaller than parsed header size");
+        /* The chunk must fit in the remaining data */
+        if offset + size as u64 > axml_buff.get_ref().as_ref().len() as u64 {
+            return Err(AxmlError::Truncated { offset });
         }
 
         /* Build and return the object */
@@ -0,0 +1,156 @@
+use std::io::Cursor;
+
+use crate::xml_types::XmlTypes;
+use crate::read_ext::ReadExt;
+use crate::error::{
+    AxmlError,
+    Result,
+};
+
+/* Minimum size of a chunk, i.e., a chunk header with no data */
+const MINIMUM_CHUNK_SIZE: u32 = 8;
+
+/// Location and raw header of a chunk found by a `ChunkWalker`
+#[derive(Debug, Clone, Copy)]
+pub struct ChunkInfo {
+    /* Offset of the first byte of the chunk in the buffer */
+    pub offset: u64,
+
+    /* Raw chunk type, as read from the buffer */
+    pub type_id: u16,
+
+    /* Decoded chunk type, `None` if this type is not known to the parser */
+    pub chunk_type: Option<XmlTypes>,
+
+    /* Size of the chunk header (in bytes) */
+    pub header_size: u16,
+
+    /* Total size of the chunk (in bytes) */
+    pub size: u32,
+}
+
+/// A chunk that was skipped because the parser does not know (or does not
+/// support) its type. The raw bytes, header included, are kept verbatim.
+#[derive(Debug, Clone)]
+pub struct UnknownChunk {
+    /* Offset of the first byte of the chunk in the buffer */
+    pub offset: u64,
+
+    /* Raw chunk type */
+    pub chunk_type: u16,
+
+    /* Size of the chunk header (in bytes) */
+    pub header_size: u16,
+
+    /* The whole chunk, header included */
+    pub data: Vec<u8>,
+}
+
+impl UnknownChunk {
+    pub fn from_buff<T: AsRef<[u8]>>(axml_buff: &Cursor<T>, info: &ChunkInfo) -> Result<Self> {
+        let start = info.offset as usize;
+        let end = start + info.size as usize;
+        let data = axml_buff.get_ref().as_ref()
+                            .get(start..end)
+                            .ok_or(AxmlError::Truncated { offset: info.offset })?;
+
+        Ok(UnknownChunk {
+            offset: info.offset,
+            chunk_type: info.type_id,
+            header_size: info.header_size,
+            data: data.to_vec(),
+        })
+    }
+}
+
+/**
+ * Walk over a sequence of chunks using the sizes announced in their headers.
+ *
+ * This is how Android itself iterates over binary XML: a chunk is never
+ * interpreted past its header to find the next one, so chunks with an
+ * unknown type (or an extended header) can be skipped safely. The
+ * `ResXmlType` chunk is a container: the walker descends into it instead
+ * of jumping over it.
+ */
+#[derive(Debug)]
+pub struct ChunkWalker {
+    /* Offset of the next chunk to read */
+    next: u64,
+
+    /* Offset at which the walk stops */
+    end: u64,
+}
+
+impl ChunkWalker {
+    pub fn new<T: AsRef<[u8]>>(axml_buff: &Cursor<T>) -> Self {
+        ChunkWalker {
+            next: axml_buff.position(),
+            end: axml_buff.get_ref().as_ref().len() as u64,
+        }
+    }
+
+    /// Walk over the chunks located between `start` and `end`, e.g., the
+    /// children of a chunk
+    pub fn with_range(start: u64, end: u64) -> Self {
+        ChunkWalker {
+            next: start,
+            end,
+        }
+    }
+
+    /// Read the header of the next chunk. On success, the cursor is left
+    /// right after the chunk type, which is what the `from_buff`/`parse_*`
+    /// functions expect. Returns `None` once the end of the data is reached.
+    pub fn next_chunk<T: AsRef<[u8]>>(&mut self, axml_buff: &mut Cursor<T>) -> Result<Option<ChunkInfo>> {
+        loop {
+            /* Not enough data left for a chunk header, we are done */
+            if self.next + MINIMUM_CHUNK_SIZE as u64 > self.end {
+                return Ok(None);
+            }
+
+            let offset = self.next;
+            axml_buff.set_position(offset);
+            let type_id = axml_buff.read_u16_le()?;
+            let header_size = axml_buff.read_u16_le()?;
+            let size = axml_buff.read_u32_le()?;
+
+            let valid_sizes = header_size as u32 >= MINIMUM_CHUNK_SIZE
+                              && size >= header_size as u32;
+            let fits = offset + size as u64 <= self.end;
+
+            /* Some files have null padding between chunks, skip it */
+            if type_id == XmlTypes::ResNullType as u16 && !(valid_sizes && fits) {
+                self.next += 2;
+                continue;
+            }
+
+            if !valid_sizes {
+                return Err(AxmlError::SizeMismatch { offset, header_size, size });
+            }
+
+            if !fits {
+                return Err(AxmlError::Truncated { offset });
+            }
+
+            /* Like Android, ignore whatever follows the document chunk */
+            let chunk_type = XmlTypes::from_u16(type_id);
+            self.next = match chunk_type {
+                Some(XmlTypes::ResXmlType) => {
+                    self.end = offset + size as u64;
+                    offset + header_size as u64
+                },
+                _ => offset + size as u64,
+            };
+
+            axml_buff.set_position(offset + 2);
+
+            return Ok(Some(ChunkInfo {
+                offset,
+                type_id,
+                chunk_type,
+                header_size,
+                size,
+            }));
+        }
+    }
+}
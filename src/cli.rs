@@ -2,11 +2,16 @@ use std::path::PathBuf;
 use clap::Parser;
 
 /// Accepted file types
-#[derive(Debug, PartialEq)]
+#[derive(Debug, Clone, Copy, PartialEq)]
 pub enum ArgType {
     Apk,
+
+    /* Binary XML, or the protobuf XML of Android App Bundles */
     Axml,
-    Arsc
+    Arsc,
+
+    /* Text XML, which needs no decoding */
+    PlainXml,
 }
 
 /// Basic CLI for the binary
@@ -14,13 +19,44 @@ pub enum ArgType {
 #[command(author, version, about, long_about = None)]
 pub struct Args {
     /// Path to the file to parse. The file can be either an APK, an Android
-    /// binary-XML file, or a resource.arsc file.
+    /// binary-XML file, or a resource.arsc file. With `--file`, the type is
+    /// detected from the contents of the file.
     #[clap(flatten)]
     target: Target,
 
-    /// Path to the output file to write the decoded content
+    /// Path to the output file to write the decoded content (or to the
+    /// output directory, with `--all-xml`)
     #[arg(short, long)]
     pub output: Option<PathBuf>,
+
+    /// Decode every binary XML file of the APK (the manifest, layouts,
+    /// drawables, etc.) into the output directory, in the same tree as in
+    /// the APK
+    #[arg(long, requires = "output", conflicts_with_all = ["xml", "res"])]
+    pub all_xml: bool,
+
+    /// Compile the input text XML file (e.g., an AndroidManifest.xml) into
+    /// binary XML, written to the output file
+    #[arg(long, requires = "output", conflicts_with = "all_xml")]
+    pub encode: bool,
+
+    /// With `--encode`, take the framework's resources and the formats of
+    /// its attributes from this `android.jar` (or its resources.arsc)
+    /// instead of the built-in table, which is partial
+    #[arg(long, requires = "encode")]
+    pub framework: Option<String>,
+
+    /// Check that every binary XML file and resource table of the input is
+    /// written back byte for byte by the lossless representation, and that
+    /// binary XML files are encoded again to the same chunks once decoded,
+    /// and exit with an error if one is not
+    #[arg(long, conflicts_with_all = ["all_xml", "encode"])]
+    pub verify_roundtrip: bool,
+
+    /// Keep references to the app's resources as raw IDs (e.g., `@0x7f120034`)
+    /// instead of resolving them with the APK's resources.arsc
+    #[arg(long)]
+    pub raw_ids: bool,
 }
 
 /// Argument group to represent any file that can be parsed by AXMLParser
@@ -38,6 +74,10 @@ struct Target {
    /// Path to resources.arsc file
    #[arg(short, long)]
    res: Option<String>,
+
+   /// Path to a file of any of the above types, detected from its contents
+   #[arg(short, long)]
+   file: Option<String>,
 }
 
 pub fn parse_args() -> Args {
@@ -45,33 +85,39 @@ pub fn parse_args() -> Args {
 }
 
 impl Args {
-    pub fn get_arg_type(&self) -> ArgType {
+    /// Type of the file given on the command line, `None` if it must be
+    /// detected from its contents
+    pub fn get_arg_type(&self) -> Option<ArgType> {
         if self.target.apk.is_some() {
-            return ArgType::Apk;
+            return Some(ArgType::Apk);
         }
 
         if self.target.xml.is_some() {
-            return ArgType::Axml;
+            return Some(ArgType::Axml);
         }
 
         if self.target.res.is_some() {
-            return ArgType::Arsc;
+            return Some(ArgType::Arsc);
         }
 
-        panic!("Will never happen");
+        None
     }
 
     pub fn get_arg_path(&self) -> String {
-        if self.target.apk.is_some() {
-            return self.target.apk.as_ref().unwrap().clone();
+        if let Some(path) = &self.target.apk {
+            return path.clone();
         }
 
-        if self.target.xml.is_some() {
-            return self.target.xml.as_ref().unwrap().clone();
+        if let Some(path) = &self.target.xml {
+            return path.clone();
         }
 
-        if self.target.res.is_some() {
-            return self.target.res.as_ref().unwrap().clone();
+        if let Some(path) = &self.target.res {
+            return path.clone();
+        }
+
+        if let Some(path) = &self.target.file {
+            return path.clone();
         }
 
         panic!("Will never happen");
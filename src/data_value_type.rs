@@ -1,10 +1,10 @@
-use std::io::Error;
-
 /* Data value types
  *
  * Note: we ignore TypeFirstInt, TypeFirstColorInt, and TypeLastColorInt which hold the same values
  * as actual data types (respectively TypeIntDec, TypeIntColorArgb8, and TypeIntColorRgb4).
  */
+#[derive(PartialEq, Debug, Clone, Copy)]
+#[repr(u8)]
 pub enum DataValueType {
     /* The 'data' is either 0 or 1, specifying this resource is either undefined or empty,
      * respectively */
@@ -45,11 +45,16 @@ pub enum DataValueType {
     TypeIntColorArgb4		= 0x1e,
     /* The 'data' is a raw integer value of the form #rgb */
     TypeIntColorRgb4		= 0x1f,
+
+    /* Any other type, e.g., one added in a later version of Android. Like
+     * Android's ResXMLParser, we keep the raw type and data instead of
+     * rejecting the value. */
+    Unknown(u8),
 }
 
 impl DataValueType {
-    pub fn from_val(value: u8) -> Result<Self, Error> {
-        let data_value_type = match value {
+    pub fn from_val(value: u8) -> Self {
+        match value {
             0x00 => DataValueType::TypeNull,
             0x01 => DataValueType::TypeReference,
             0x02 => DataValueType::TypeAttribute,
@@ -66,9 +71,30 @@ impl DataValueType {
             0x1d => DataValueType::TypeIntColorRgb8,
             0x1e => DataValueType::TypeIntColorArgb4,
             0x1f => DataValueType::TypeIntColorRgb4,
-            _ => panic!("Error: unknown data value type {:02X}", value)
-        };
+            _ => DataValueType::Unknown(value),
+        }
+    }
 
-        Ok(data_value_type)
+    /// Raw type, as stored in a Res_value structure
+    pub fn to_val(self) -> u8 {
+        match self {
+            DataValueType::TypeNull => 0x00,
+            DataValueType::TypeReference => 0x01,
+            DataValueType::TypeAttribute => 0x02,
+            DataValueType::TypeString => 0x03,
+            DataValueType::TypeFloat => 0x04,
+            DataValueType::TypeDimension => 0x05,
+            DataValueType::TypeFraction => 0x06,
+            DataValueType::TypeDynamicReference => 0x07,
+            DataValueType::TypeDynamicAttribute => 0x08,
+            DataValueType::TypeIntDec => 0x10,
+            DataValueType::TypeIntHex => 0x11,
+            DataValueType::TypeIntBoolean => 0x12,
+            DataValueType::TypeIntColorArgb8 => 0x1c,
+            DataValueType::TypeIntColorRgb8 => 0x1d,
+            DataValueType::TypeIntColorArgb4 => 0x1e,
+            DataValueType::TypeIntColorRgb4 => 0x1f,
+            DataValueType::Unknown(value) => value,
+        }
     }
 }
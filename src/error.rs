@@ -0,0 +1,167 @@
+use std::fmt;
+use std::io;
+
+/// Errors raised while decoding binary XML, resource tables or APKs.
+///
+/// Whenever the error is tied to a specific location in the input, the
+/// offset (in bytes, from the start of the decoded buffer) is attached.
+#[derive(Debug)]
+pub enum AxmlError {
+    /* The input ended before the structure starting at `offset` could be read */
+    Truncated { offset: u64 },
+
+    /* A chunk with an unknown type was found */
+    UnknownChunkType { offset: u64, chunk_type: u16 },
+
+    /* A chunk of a given type was found where another one was expected */
+    UnexpectedChunkType { offset: u64, expected: u16, found: u16 },
+
+    /* The sizes announced in a chunk header are inconsistent */
+    SizeMismatch { offset: u64, header_size: u16, size: u32 },
+
+    /* A string index points outside of the string pool */
+    BadStringIndex { offset: u64, index: u32 },
+
+    /* An attribute uses a namespace that was never declared */
+    UnknownNamespace { offset: u64, uri: String },
+
+    /* A string from a string pool is not valid UTF-8 */
+    InvalidUtf8 { offset: u64 },
+
+    /* A string from a string pool is not valid UTF-16 */
+    InvalidUtf16 { offset: u64 },
+
+    /* Protobuf data (from an Android App Bundle) cannot be decoded */
+    MalformedProto { offset: u64 },
+
+    /* Writing back a document does not give the bytes it was read from */
+    RoundtripMismatch { offset: u64 },
+
+    /* Decoding a document and encoding it again changes one of its chunks */
+    ChunkMismatch { offset: u64, chunk_type: u16 },
+
+    /* Some of the files checked by `--verify-roundtrip` do not round-trip */
+    RoundtripFailures { failed: usize, total: usize },
+
+    /* The elements of a document are not properly nested */
+    MalformedDocument { line: u32, reason: String },
+
+    /* A reference in text XML names a resource that cannot be found */
+    UnresolvedReference { line: u32, reference: String },
+
+    /* An attribute of the Android namespace is not in the framework table */
+    UnknownAttribute { line: u32, name: String },
+
+    /* The type of the input file could not be determined */
+    UnknownFileType(String),
+
+    /* The input file is text XML, there is nothing to decode */
+    PlainTextXml(String),
+
+    /* The APK does not contain an AndroidManifest.xml */
+    MissingManifest,
+
+    /* An APK was expected, but the input file is of another type */
+    NotAnApk(String),
+
+    /* A manifest was expected, but the input file is a resource table */
+    NotAManifest(String),
+
+    /* The framework's resources were expected, but there are none */
+    MissingResources(String),
+
+    /* Error while reading an APK */
+    Zip(zip::result::ZipError),
+
+    /* Error while reading text XML or writing decoded XML */
+    Xml(quick_xml::Error),
+
+    /* Any other I/O error (e.g., when opening a file) */
+    Io(io::Error),
+}
+
+/// Convenience alias used by every parsing function of the crate
+pub type Result<T> = std::result::Result<T, AxmlError>;
+
+impl fmt::Display for AxmlError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            AxmlError::Truncated { offset } =>
+                write!(f, "truncated data at offset {:#x}", offset),
+            AxmlError::UnknownChunkType { offset, chunk_type } =>
+                write!(f, "unknown chunk type {:#06x} at offset {:#x}", chunk_type, offset),
+            AxmlError::UnexpectedChunkType { offset, expected, found } =>
+                write!(f, "expected chunk type {:#06x} but found {:#06x} at offset {:#x}",
+                       expected, found, offset),
+            AxmlError::SizeMismatch { offset, header_size, size } =>
+                write!(f, "inconsistent chunk sizes (header: {:#x}, total: {:#x}) at offset {:#x}",
+                       header_size, size, offset),
+            AxmlError::BadStringIndex { offset, index } =>
+                write!(f, "string index {} out of bounds at offset {:#x}", index, offset),
+            AxmlError::UnknownNamespace { offset, uri } =>
+                write!(f, "undeclared namespace {} at offset {:#x}", uri, offset),
+            AxmlError::InvalidUtf8 { offset } =>
+                write!(f, "invalid UTF-8 string at offset {:#x}", offset),
+            AxmlError::InvalidUtf16 { offset } =>
+                write!(f, "invalid UTF-16 string at offset {:#x}", offset),
+            AxmlError::MalformedProto { offset } =>
+                write!(f, "malformed protobuf data at offset {:#x}", offset),
+            AxmlError::RoundtripMismatch { offset } =>
+                write!(f, "round trip differs from the original at offset {:#x}", offset),
+            AxmlError::ChunkMismatch { offset, chunk_type } =>
+                write!(f, "re-encoded chunk of type {:#06x} at offset {:#x} differs from the original", chunk_type, offset),
+            AxmlError::RoundtripFailures { failed, total } =>
+                write!(f, "{} of {} files do not round-trip", failed, total),
+            AxmlError::MalformedDocument { line, reason } =>
+                write!(f, "malformed document at line {}: {}", line, reason),
+            AxmlError::UnresolvedReference { line, reference } =>
+                write!(f, "cannot resolve reference {} at line {}", reference, line),
+            AxmlError::UnknownAttribute { line, name } =>
+                write!(f, "unknown framework attribute {} at line {}", name, line),
+            AxmlError::UnknownFileType(path) =>
+                write!(f, "cannot infer file type of {} (expected an APK, binary XML, protobuf XML, resources.arsc or text XML)", path),
+            AxmlError::PlainTextXml(path) =>
+                write!(f, "{} is already text XML", path),
+            AxmlError::MissingManifest =>
+                write!(f, "no AndroidManifest.xml in APK"),
+            AxmlError::NotAnApk(path) =>
+                write!(f, "{} is not an APK", path),
+            AxmlError::NotAManifest(path) =>
+                write!(f, "{} is a resource table, not a manifest", path),
+            AxmlError::MissingResources(path) =>
+                write!(f, "no resources.arsc in {}", path),
+            AxmlError::Zip(e) => write!(f, "cannot read APK: {}", e),
+            AxmlError::Xml(e) => write!(f, "XML error: {}", e),
+            AxmlError::Io(e) => write!(f, "I/O error: {}", e),
+        }
+    }
+}
+
+impl std::error::Error for AxmlError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            AxmlError::Zip(e) => Some(e),
+            AxmlError::Xml(e) => Some(e),
+            AxmlError::Io(e) => Some(e),
+            _ => None,
+        }
+    }
+}
+
+impl From<zip::result::ZipError> for AxmlError {
+    fn from(e: zip::result::ZipError) -> Self {
+        AxmlError::Zip(e)
+    }
+}
+
+impl From<quick_xml::Error> for AxmlError {
+    fn from(e: quick_xml::Error) -> Self {
+        AxmlError::Xml(e)
+    }
+}
+
+impl From<io::Error> for AxmlError {
+    fn from(e: io::Error) -> Self {
+        AxmlError::Io(e)
+    }
+}
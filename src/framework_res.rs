@@ -0,0 +1,63 @@
+use std::collections::HashMap;
+use std::sync::OnceLock;
+
+use crate::framework_res_table::{
+    AttributeValues,
+    FRAMEWORK_ATTRIBUTES,
+    FRAMEWORK_RESOURCES,
+};
+use crate::res_resolver::{
+    ResourceName,
+    ResourceResolver,
+};
+
+/// Type, name and API level (in which it was introduced) of a public
+/// resource of the Android framework, e.g., `("attr", "label", 1)` for
+/// `0x01010001`
+pub fn framework_name(id: u32) -> Option<(&'static str, &'static str, u32)> {
+    let index = FRAMEWORK_RESOURCES.binary_search_by_key(&id, |&(res_id, ..)| res_id).ok()?;
+    let (_, type_name, name, api) = FRAMEWORK_RESOURCES[index];
+
+    Some((type_name, name, api))
+}
+
+/// ID of a public resource of the Android framework given its type and
+/// name, e.g., `0x01010001` for `("attr", "label")`
+pub fn framework_id(type_name: &str, name: &str) -> Option<u32> {
+    /* Index of the table by type and name, built on first use */
+    static IDS_BY_NAME: OnceLock<HashMap<(&str, &str), u32>> = OnceLock::new();
+
+    let ids = IDS_BY_NAME.get_or_init(|| {
+        FRAMEWORK_RESOURCES.iter()
+                           .map(|&(id, res_type, res_name, _)| ((res_type, res_name), id))
+                           .collect()
+    });
+    ids.get(&(type_name, name)).copied()
+}
+
+/// Format of a public attribute of the Android framework: the types of
+/// values it accepts (as `ResTable_map` type bits), and the names and values
+/// of its enum or flag values. Returns `None` if the format is not known.
+pub fn framework_attribute(id: u32) -> Option<(u32, AttributeValues)> {
+    let index = FRAMEWORK_ATTRIBUTES.binary_search_by_key(&id, |&(res_id, ..)| res_id).ok()?;
+    let (_, types, values) = FRAMEWORK_ATTRIBUTES[index];
+
+    Some((types, values))
+}
+
+/// Resolver for the public resources of the Android framework, which gives
+/// names like `android:attr/label`
+#[derive(Debug, Clone, Copy, Default)]
+pub struct FrameworkResources;
+
+impl ResourceResolver for FrameworkResources {
+    fn resource_name(&self, id: u32) -> Option<ResourceName> {
+        let (type_name, entry, _) = framework_name(id)?;
+
+        Some(ResourceName {
+            package: "android".to_string(),
+            type_name: type_name.to_string(),
+            entry: entry.to_string(),
+        })
+    }
+}
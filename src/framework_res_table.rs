@@ -0,0 +1,1469 @@
+/* Partial table, written by hand until it is regenerated with
+ * tools/gen_framework_res.py from an Android SDK. It holds the attributes up
+ * to 0x01010530 (API level 25) and a few later ones used in manifests, but
+ * only the most common styles, IDs, strings, colours and drawables, and its
+ * API levels are approximate. Resources which are missing can only be
+ * referenced by ID, or resolved with a framework resource table (see
+ * `load_framework`).
+ *
+ * Public resources of the Android framework, sorted by ID:
+ * (ID, type, name, API level in which the resource was introduced) */
+pub(crate) static FRAMEWORK_RESOURCES: &[(u32, &str, &str, u32)] = &[
+    (0x01010000, "attr", "theme", 1),
+    (0x01010001, "attr", "label", 1),
+    (0x01010002, "attr", "icon", 1),
+    (0x01010003, "attr", "name", 1),
+    (0x01010004, "attr", "manageSpaceActivity", 1),
+    (0x01010005, "attr", "allowClearUserData", 1),
+    (0x01010006, "attr", "permission", 1),
+    (0x01010007, "attr", "readPermission", 1),
+    (0x01010008, "attr", "writePermission", 1),
+    (0x01010009, "attr", "protectionLevel", 1),
+    (0x0101000a, "attr", "permissionGroup", 1),
+    (0x0101000b, "attr", "sharedUserId", 1),
+    (0x0101000c, "attr", "hasCode", 1),
+    (0x0101000d, "attr", "persistent", 1),
+    (0x0101000e, "attr", "enabled", 1),
+    (0x0101000f, "attr", "debuggable", 1),
+    (0x01010010, "attr", "exported", 1),
+    (0x01010011, "attr", "process", 1),
+    (0x01010012, "attr", "taskAffinity", 1),
+    (0x01010013, "attr", "multiprocess", 1),
+    (0x01010014, "attr", "finishOnTaskLaunch", 1),
+    (0x01010015, "attr", "clearTaskOnLaunch", 1),
+    (0x01010016, "attr", "stateNotNeeded", 1),
+    (0x01010017, "attr", "excludeFromRecents", 1),
+    (0x01010018, "attr", "authorities", 1),
+    (0x01010019, "attr", "syncable", 1),
+    (0x0101001a, "attr", "initOrder", 1),
+    (0x0101001b, "attr", "grantUriPermissions", 1),
+    (0x0101001c, "attr", "priority", 1),
+    (0x0101001d, "attr", "launchMode", 1),
+    (0x0101001e, "attr", "screenOrientation", 1),
+    (0x0101001f, "attr", "configChanges", 1),
+    (0x01010020, "attr", "description", 1),
+    (0x01010021, "attr", "targetPackage", 1),
+    (0x01010022, "attr", "handleProfiling", 1),
+    (0x01010023, "attr", "functionalTest", 1),
+    (0x01010024, "attr", "value", 1),
+    (0x01010025, "attr", "resource", 1),
+    (0x01010026, "attr", "mimeType", 1),
+    (0x01010027, "attr", "scheme", 1),
+    (0x01010028, "attr", "host", 1),
+    (0x01010029, "attr", "port", 1),
+    (0x0101002a, "attr", "path", 1),
+    (0x0101002b, "attr", "pathPrefix", 1),
+    (0x0101002c, "attr", "pathPattern", 1),
+    (0x0101002d, "attr", "action", 1),
+    (0x0101002e, "attr", "data", 1),
+    (0x0101002f, "attr", "targetClass", 1),
+    (0x01010030, "attr", "colorForeground", 1),
+    (0x01010031, "attr", "colorBackground", 1),
+    (0x01010032, "attr", "backgroundDimAmount", 1),
+    (0x01010033, "attr", "disabledAlpha", 1),
+    (0x01010034, "attr", "textAppearance", 1),
+    (0x01010035, "attr", "textAppearanceInverse", 1),
+    (0x01010036, "attr", "textColorPrimary", 1),
+    (0x01010037, "attr", "textColorPrimaryDisableOnly", 1),
+    (0x01010038, "attr", "textColorSecondary", 1),
+    (0x01010039, "attr", "textColorPrimaryInverse", 1),
+    (0x0101003a, "attr", "textColorSecondaryInverse", 1),
+    (0x0101003b, "attr", "textColorPrimaryNoDisable", 1),
+    (0x0101003c, "attr", "textColorSecondaryNoDisable", 1),
+    (0x0101003d, "attr", "textColorPrimaryInverseNoDisable", 1),
+    (0x0101003e, "attr", "textColorSecondaryInverseNoDisable", 1),
+    (0x0101003f, "attr", "textColorHintInverse", 1),
+    (0x01010040, "attr", "textAppearanceLarge", 1),
+    (0x01010041, "attr", "textAppearanceMedium", 1),
+    (0x01010042, "attr", "textAppearanceSmall", 1),
+    (0x01010043, "attr", "textAppearanceLargeInverse", 1),
+    (0x01010044, "attr", "textAppearanceMediumInverse", 1),
+    (0x01010045, "attr", "textAppearanceSmallInverse", 1),
+    (0x01010046, "attr", "textCheckMark", 1),
+    (0x01010047, "attr", "textCheckMarkInverse", 1),
+    (0x01010048, "attr", "buttonStyle", 1),
+    (0x01010049, "attr", "buttonStyleSmall", 1),
+    (0x0101004a, "attr", "buttonStyleInset", 1),
+    (0x0101004b, "attr", "buttonStyleToggle", 1),
+    (0x0101004c, "attr", "galleryItemBackground", 1),
+    (0x0101004d, "attr", "listPreferredItemHeight", 1),
+    (0x0101004e, "attr", "expandableListPreferredItemPaddingLeft", 1),
+    (0x0101004f, "attr", "expandableListPreferredChildPaddingLeft", 1),
+    (0x01010050, "attr", "expandableListPreferredItemIndicatorLeft", 1),
+    (0x01010051, "attr", "expandableListPreferredItemIndicatorRight", 1),
+    (0x01010052, "attr", "expandableListPreferredChildIndicatorLeft", 1),
+    (0x01010053, "attr", "expandableListPreferredChildIndicatorRight", 1),
+    (0x01010054, "attr", "windowBackground", 1),
+    (0x01010055, "attr", "windowFrame", 1),
+    (0x01010056, "attr", "windowNoTitle", 1),
+    (0x01010057, "attr", "windowIsFloating", 1),
+    (0x01010058, "attr", "windowIsTranslucent", 1),
+    (0x01010059, "attr", "windowContentOverlay", 1),
+    (0x0101005a, "attr", "windowTitleSize", 1),
+    (0x0101005b, "attr", "windowTitleStyle", 1),
+    (0x0101005c, "attr", "windowTitleBackgroundStyle", 1),
+    (0x0101005d, "attr", "alertDialogStyle", 1),
+    (0x0101005e, "attr", "panelBackground", 1),
+    (0x0101005f, "attr", "panelFullBackground", 1),
+    (0x01010060, "attr", "panelColorForeground", 1),
+    (0x01010061, "attr", "panelColorBackground", 1),
+    (0x01010062, "attr", "panelTextAppearance", 1),
+    (0x01010063, "attr", "scrollbarSize", 1),
+    (0x01010064, "attr", "scrollbarThumbHorizontal", 1),
+    (0x01010065, "attr", "scrollbarThumbVertical", 1),
+    (0x01010066, "attr", "scrollbarTrackHorizontal", 1),
+    (0x01010067, "attr", "scrollbarTrackVertical", 1),
+    (0x01010068, "attr", "scrollbarAlwaysDrawHorizontalTrack", 1),
+    (0x01010069, "attr", "scrollbarAlwaysDrawVerticalTrack", 1),
+    (0x0101006a, "attr", "absListViewStyle", 1),
+    (0x0101006b, "attr", "autoCompleteTextViewStyle", 1),
+    (0x0101006c, "attr", "checkboxStyle", 1),
+    (0x0101006d, "attr", "dropDownListViewStyle", 1),
+    (0x0101006e, "attr", "editTextStyle", 1),
+    (0x0101006f, "attr", "expandableListViewStyle", 1),
+    (0x01010070, "attr", "galleryStyle", 1),
+    (0x01010071, "attr", "gridViewStyle", 1),
+    (0x01010072, "attr", "imageButtonStyle", 1),
+    (0x01010073, "attr", "imageWellStyle", 1),
+    (0x01010074, "attr", "listViewStyle", 1),
+    (0x01010075, "attr", "listViewWhiteStyle", 1),
+    (0x01010076, "attr", "popupWindowStyle", 1),
+    (0x01010077, "attr", "progressBarStyle", 1),
+    (0x01010078, "attr", "progressBarStyleHorizontal", 1),
+    (0x01010079, "attr", "progressBarStyleSmall", 1),
+    (0x0101007a, "attr", "progressBarStyleLarge", 1),
+    (0x0101007b, "attr", "seekBarStyle", 1),
+    (0x0101007c, "attr", "ratingBarStyle", 1),
+    (0x0101007d, "attr", "ratingBarStyleSmall", 1),
+    (0x0101007e, "attr", "radioButtonStyle", 1),
+    (0x0101007f, "attr", "scrollbarStyle", 1),
+    (0x01010080, "attr", "scrollViewStyle", 1),
+    (0x01010081, "attr", "spinnerStyle", 1),
+    (0x01010082, "attr", "starStyle", 1),
+    (0x01010083, "attr", "tabWidgetStyle", 1),
+    (0x01010084, "attr", "textViewStyle", 1),
+    (0x01010085, "attr", "webViewStyle", 1),
+    (0x01010086, "attr", "dropDownItemStyle", 1),
+    (0x01010087, "attr", "spinnerDropDownItemStyle", 1),
+    (0x01010088, "attr", "dropDownHintAppearance", 1),
+    (0x01010089, "attr", "spinnerItemStyle", 1),
+    (0x0101008a, "attr", "mapViewStyle", 1),
+    (0x0101008b, "attr", "preferenceScreenStyle", 1),
+    (0x0101008c, "attr", "preferenceCategoryStyle", 1),
+    (0x0101008d, "attr", "preferenceInformationStyle", 1),
+    (0x0101008e, "attr", "preferenceStyle", 1),
+    (0x0101008f, "attr", "checkBoxPreferenceStyle", 1),
+    (0x01010090, "attr", "yesNoPreferenceStyle", 1),
+    (0x01010091, "attr", "dialogPreferenceStyle", 1),
+    (0x01010092, "attr", "editTextPreferenceStyle", 1),
+    (0x01010093, "attr", "ringtonePreferenceStyle", 1),
+    (0x01010094, "attr", "preferenceLayoutChild", 1),
+    (0x01010095, "attr", "textSize", 1),
+    (0x01010096, "attr", "typeface", 1),
+    (0x01010097, "attr", "textStyle", 1),
+    (0x01010098, "attr", "textColor", 1),
+    (0x01010099, "attr", "textColorHighlight", 1),
+    (0x0101009a, "attr", "textColorHint", 1),
+    (0x0101009b, "attr", "textColorLink", 1),
+    (0x0101009c, "attr", "state_focused", 1),
+    (0x0101009d, "attr", "state_window_focused", 1),
+    (0x0101009e, "attr", "state_enabled", 1),
+    (0x0101009f, "attr", "state_checkable", 1),
+    (0x010100a0, "attr", "state_checked", 1),
+    (0x010100a1, "attr", "state_selected", 1),
+    (0x010100a2, "attr", "state_active", 1),
+    (0x010100a3, "attr", "state_single", 1),
+    (0x010100a4, "attr", "state_first", 1),
+    (0x010100a5, "attr", "state_middle", 1),
+    (0x010100a6, "attr", "state_last", 1),
+    (0x010100a7, "attr", "state_pressed", 1),
+    (0x010100a8, "attr", "state_expanded", 1),
+    (0x010100a9, "attr", "state_empty", 1),
+    (0x010100aa, "attr", "state_above_anchor", 1),
+    (0x010100ab, "attr", "ellipsize", 1),
+    (0x010100ac, "attr", "x", 1),
+    (0x010100ad, "attr", "y", 1),
+    (0x010100ae, "attr", "windowAnimationStyle", 1),
+    (0x010100af, "attr", "gravity", 1),
+    (0x010100b0, "attr", "autoLink", 1),
+    (0x010100b1, "attr", "linksClickable", 1),
+    (0x010100b2, "attr", "entries", 1),
+    (0x010100b3, "attr", "layout_gravity", 1),
+    (0x010100b4, "attr", "windowEnterAnimation", 1),
+    (0x010100b5, "attr", "windowExitAnimation", 1),
+    (0x010100b6, "attr", "windowShowAnimation", 1),
+    (0x010100b7, "attr", "windowHideAnimation", 1),
+    (0x010100b8, "attr", "activityOpenEnterAnimation", 1),
+    (0x010100b9, "attr", "activityOpenExitAnimation", 1),
+    (0x010100ba, "attr", "activityCloseEnterAnimation", 1),
+    (0x010100bb, "attr", "activityCloseExitAnimation", 1),
+    (0x010100bc, "attr", "taskOpenEnterAnimation", 1),
+    (0x010100bd, "attr", "taskOpenExitAnimation", 1),
+    (0x010100be, "attr", "taskCloseEnterAnimation", 1),
+    (0x010100bf, "attr", "taskCloseExitAnimation", 1),
+    (0x010100c0, "attr", "taskToFrontEnterAnimation", 1),
+    (0x010100c1, "attr", "taskToFrontExitAnimation", 1),
+    (0x010100c2, "attr", "taskToBackEnterAnimation", 1),
+    (0x010100c3, "attr", "taskToBackExitAnimation", 1),
+    (0x010100c4, "attr", "orientation", 1),
+    (0x010100c5, "attr", "keycode", 1),
+    (0x010100c6, "attr", "fullDark", 1),
+    (0x010100c7, "attr", "topDark", 1),
+    (0x010100c8, "attr", "centerDark", 1),
+    (0x010100c9, "attr", "bottomDark", 1),
+    (0x010100ca, "attr", "fullBright", 1),
+    (0x010100cb, "attr", "topBright", 1),
+    (0x010100cc, "attr", "centerBright", 1),
+    (0x010100cd, "attr", "bottomBright", 1),
+    (0x010100ce, "attr", "bottomMedium", 1),
+    (0x010100cf, "attr", "centerMedium", 1),
+    (0x010100d0, "attr", "id", 1),
+    (0x010100d1, "attr", "tag", 1),
+    (0x010100d2, "attr", "scrollX", 1),
+    (0x010100d3, "attr", "scrollY", 1),
+    (0x010100d4, "attr", "background", 1),
+    (0x010100d5, "attr", "padding", 1),
+    (0x010100d6, "attr", "paddingLeft", 1),
+    (0x010100d7, "attr", "paddingTop", 1),
+    (0x010100d8, "attr", "paddingRight", 1),
+    (0x010100d9, "attr", "paddingBottom", 1),
+    (0x010100da, "attr", "focusable", 1),
+    (0x010100db, "attr", "focusableInTouchMode", 1),
+    (0x010100dc, "attr", "visibility", 1),
+    (0x010100dd, "attr", "fitsSystemWindows", 1),
+    (0x010100de, "attr", "scrollbars", 1),
+    (0x010100df, "attr", "fadingEdge", 1),
+    (0x010100e0, "attr", "fadingEdgeLength", 1),
+    (0x010100e1, "attr", "nextFocusLeft", 1),
+    (0x010100e2, "attr", "nextFocusRight", 1),
+    (0x010100e3, "attr", "nextFocusUp", 1),
+    (0x010100e4, "attr", "nextFocusDown", 1),
+    (0x010100e5, "attr", "clickable", 1),
+    (0x010100e6, "attr", "longClickable", 1),
+    (0x010100e7, "attr", "saveEnabled", 1),
+    (0x010100e8, "attr", "drawingCacheQuality", 1),
+    (0x010100e9, "attr", "duplicateParentState", 1),
+    (0x010100ea, "attr", "clipChildren", 1),
+    (0x010100eb, "attr", "clipToPadding", 1),
+    (0x010100ec, "attr", "layoutAnimation", 1),
+    (0x010100ed, "attr", "animationCache", 1),
+    (0x010100ee, "attr", "persistentDrawingCache", 1),
+    (0x010100ef, "attr", "alwaysDrawnWithCache", 1),
+    (0x010100f0, "attr", "addStatesFromChildren", 1),
+    (0x010100f1, "attr", "descendantFocusability", 1),
+    (0x010100f2, "attr", "layout", 1),
+    (0x010100f3, "attr", "inflatedId", 1),
+    (0x010100f4, "attr", "layout_width", 1),
+    (0x010100f5, "attr", "layout_height", 1),
+    (0x010100f6, "attr", "layout_margin", 1),
+    (0x010100f7, "attr", "layout_marginLeft", 1),
+    (0x010100f8, "attr", "layout_marginTop", 1),
+    (0x010100f9, "attr", "layout_marginRight", 1),
+    (0x010100fa, "attr", "layout_marginBottom", 1),
+    (0x010100fb, "attr", "listSelector", 1),
+    (0x010100fc, "attr", "drawSelectorOnTop", 1),
+    (0x010100fd, "attr", "stackFromBottom", 1),
+    (0x010100fe, "attr", "scrollingCache", 1),
+    (0x010100ff, "attr", "textFilterEnabled", 1),
+    (0x01010100, "attr", "transcriptMode", 1),
+    (0x01010101, "attr", "cacheColorHint", 1),
+    (0x01010102, "attr", "dial", 1),
+    (0x01010103, "attr", "hand_hour", 1),
+    (0x01010104, "attr", "hand_minute", 1),
+    (0x01010105, "attr", "format", 1),
+    (0x01010106, "attr", "checked", 1),
+    (0x01010107, "attr", "button", 1),
+    (0x01010108, "attr", "checkMark", 1),
+    (0x01010109, "attr", "foreground", 1),
+    (0x0101010a, "attr", "measureAllChildren", 1),
+    (0x0101010b, "attr", "groupIndicator", 1),
+    (0x0101010c, "attr", "childIndicator", 1),
+    (0x0101010d, "attr", "indicatorLeft", 1),
+    (0x0101010e, "attr", "indicatorRight", 1),
+    (0x0101010f, "attr", "childIndicatorLeft", 1),
+    (0x01010110, "attr", "childIndicatorRight", 1),
+    (0x01010111, "attr", "childDivider", 1),
+    (0x01010112, "attr", "animationDuration", 1),
+    (0x01010113, "attr", "spacing", 1),
+    (0x01010114, "attr", "horizontalSpacing", 1),
+    (0x01010115, "attr", "verticalSpacing", 1),
+    (0x01010116, "attr", "stretchMode", 1),
+    (0x01010117, "attr", "columnWidth", 1),
+    (0x01010118, "attr", "numColumns", 1),
+    (0x01010119, "attr", "src", 1),
+    (0x0101011a, "attr", "antialias", 1),
+    (0x0101011b, "attr", "filter", 1),
+    (0x0101011c, "attr", "dither", 1),
+    (0x0101011d, "attr", "scaleType", 1),
+    (0x0101011e, "attr", "adjustViewBounds", 1),
+    (0x0101011f, "attr", "maxWidth", 1),
+    (0x01010120, "attr", "maxHeight", 1),
+    (0x01010121, "attr", "tint", 1),
+    (0x01010122, "attr", "baselineAlignBottom", 1),
+    (0x01010123, "attr", "cropToPadding", 1),
+    (0x01010124, "attr", "textOn", 1),
+    (0x01010125, "attr", "textOff", 1),
+    (0x01010126, "attr", "baselineAligned", 1),
+    (0x01010127, "attr", "baselineAlignedChildIndex", 1),
+    (0x01010128, "attr", "weightSum", 1),
+    (0x01010129, "attr", "divider", 1),
+    (0x0101012a, "attr", "dividerHeight", 1),
+    (0x0101012b, "attr", "choiceMode", 1),
+    (0x0101012c, "attr", "itemTextAppearance", 1),
+    (0x0101012d, "attr", "horizontalDivider", 1),
+    (0x0101012e, "attr", "verticalDivider", 1),
+    (0x0101012f, "attr", "headerBackground", 1),
+    (0x01010130, "attr", "itemBackground", 1),
+    (0x01010131, "attr", "itemIconDisabledAlpha", 1),
+    (0x01010132, "attr", "rowHeight", 1),
+    (0x01010133, "attr", "maxRows", 1),
+    (0x01010134, "attr", "maxItemsPerRow", 1),
+    (0x01010135, "attr", "moreIcon", 1),
+    (0x01010136, "attr", "max", 1),
+    (0x01010137, "attr", "progress", 1),
+    (0x01010138, "attr", "secondaryProgress", 1),
+    (0x01010139, "attr", "indeterminate", 1),
+    (0x0101013a, "attr", "indeterminateOnly", 1),
+    (0x0101013b, "attr", "indeterminateDrawable", 1),
+    (0x0101013c, "attr", "progressDrawable", 1),
+    (0x0101013d, "attr", "indeterminateDuration", 1),
+    (0x0101013e, "attr", "indeterminateBehavior", 1),
+    (0x0101013f, "attr", "minWidth", 1),
+    (0x01010140, "attr", "minHeight", 1),
+    (0x01010141, "attr", "interpolator", 1),
+    (0x01010142, "attr", "thumb", 1),
+    (0x01010143, "attr", "thumbOffset", 1),
+    (0x01010144, "attr", "numStars", 1),
+    (0x01010145, "attr", "rating", 1),
+    (0x01010146, "attr", "stepSize", 1),
+    (0x01010147, "attr", "isIndicator", 1),
+    (0x01010148, "attr", "checkedButton", 1),
+    (0x01010149, "attr", "stretchColumns", 1),
+    (0x0101014a, "attr", "shrinkColumns", 1),
+    (0x0101014b, "attr", "collapseColumns", 1),
+    (0x0101014c, "attr", "layout_column", 1),
+    (0x0101014d, "attr", "layout_span", 1),
+    (0x0101014e, "attr", "bufferType", 1),
+    (0x0101014f, "attr", "text", 1),
+    (0x01010150, "attr", "hint", 1),
+    (0x01010151, "attr", "textScaleX", 1),
+    (0x01010152, "attr", "cursorVisible", 1),
+    (0x01010153, "attr", "maxLines", 1),
+    (0x01010154, "attr", "lines", 1),
+    (0x01010155, "attr", "height", 1),
+    (0x01010156, "attr", "minLines", 1),
+    (0x01010157, "attr", "maxEms", 1),
+    (0x01010158, "attr", "ems", 1),
+    (0x01010159, "attr", "width", 1),
+    (0x0101015a, "attr", "minEms", 1),
+    (0x0101015b, "attr", "scrollHorizontally", 1),
+    (0x0101015c, "attr", "password", 1),
+    (0x0101015d, "attr", "singleLine", 1),
+    (0x0101015e, "attr", "selectAllOnFocus", 1),
+    (0x0101015f, "attr", "includeFontPadding", 1),
+    (0x01010160, "attr", "maxLength", 1),
+    (0x01010161, "attr", "shadowColor", 1),
+    (0x01010162, "attr", "shadowDx", 1),
+    (0x01010163, "attr", "shadowDy", 1),
+    (0x01010164, "attr", "shadowRadius", 1),
+    (0x01010165, "attr", "numeric", 1),
+    (0x01010166, "attr", "digits", 1),
+    (0x01010167, "attr", "phoneNumber", 1),
+    (0x01010168, "attr", "inputMethod", 1),
+    (0x01010169, "attr", "capitalize", 1),
+    (0x0101016a, "attr", "autoText", 1),
+    (0x0101016b, "attr", "editable", 1),
+    (0x0101016c, "attr", "freezesText", 1),
+    (0x0101016d, "attr", "drawableTop", 1),
+    (0x0101016e, "attr", "drawableBottom", 1),
+    (0x0101016f, "attr", "drawableLeft", 1),
+    (0x01010170, "attr", "drawableRight", 1),
+    (0x01010171, "attr", "drawablePadding", 1),
+    (0x01010172, "attr", "completionHint", 1),
+    (0x01010173, "attr", "completionHintView", 1),
+    (0x01010174, "attr", "completionThreshold", 1),
+    (0x01010175, "attr", "dropDownSelector", 1),
+    (0x01010176, "attr", "popupBackground", 1),
+    (0x01010177, "attr", "inAnimation", 1),
+    (0x01010178, "attr", "outAnimation", 1),
+    (0x01010179, "attr", "flipInterval", 1),
+    (0x0101017a, "attr", "fillViewport", 1),
+    (0x0101017b, "attr", "prompt", 1),
+    (0x0101017c, "attr", "startYear", 1),
+    (0x0101017d, "attr", "endYear", 1),
+    (0x0101017e, "attr", "mode", 1),
+    (0x0101017f, "attr", "layout_x", 1),
+    (0x01010180, "attr", "layout_y", 1),
+    (0x01010181, "attr", "layout_weight", 1),
+    (0x01010182, "attr", "layout_toLeftOf", 1),
+    (0x01010183, "attr", "layout_toRightOf", 1),
+    (0x01010184, "attr", "layout_above", 1),
+    (0x01010185, "attr", "layout_below", 1),
+    (0x01010186, "attr", "layout_alignBaseline", 1),
+    (0x01010187, "attr", "layout_alignLeft", 1),
+    (0x01010188, "attr", "layout_alignTop", 1),
+    (0x01010189, "attr", "layout_alignRight", 1),
+    (0x0101018a, "attr", "layout_alignBottom", 1),
+    (0x0101018b, "attr", "layout_alignParentLeft", 1),
+    (0x0101018c, "attr", "layout_alignParentTop", 1),
+    (0x0101018d, "attr", "layout_alignParentRight", 1),
+    (0x0101018e, "attr", "layout_alignParentBottom", 1),
+    (0x0101018f, "attr", "layout_centerInParent", 1),
+    (0x01010190, "attr", "layout_centerHorizontal", 1),
+    (0x01010191, "attr", "layout_centerVertical", 1),
+    (0x01010192, "attr", "layout_alignWithParentIfMissing", 1),
+    (0x01010193, "attr", "layout_scale", 1),
+    (0x01010194, "attr", "visible", 1),
+    (0x01010195, "attr", "variablePadding", 1),
+    (0x01010196, "attr", "constantSize", 1),
+    (0x01010197, "attr", "oneshot", 1),
+    (0x01010198, "attr", "duration", 1),
+    (0x01010199, "attr", "drawable", 1),
+    (0x0101019a, "attr", "shape", 1),
+    (0x0101019b, "attr", "innerRadiusRatio", 1),
+    (0x0101019c, "attr", "thicknessRatio", 1),
+    (0x0101019d, "attr", "startColor", 1),
+    (0x0101019e, "attr", "endColor", 1),
+    (0x0101019f, "attr", "useLevel", 1),
+    (0x010101a0, "attr", "angle", 1),
+    (0x010101a1, "attr", "type", 1),
+    (0x010101a2, "attr", "centerX", 1),
+    (0x010101a3, "attr", "centerY", 1),
+    (0x010101a4, "attr", "gradientRadius", 1),
+    (0x010101a5, "attr", "color", 1),
+    (0x010101a6, "attr", "dashWidth", 1),
+    (0x010101a7, "attr", "dashGap", 1),
+    (0x010101a8, "attr", "radius", 1),
+    (0x010101a9, "attr", "topLeftRadius", 1),
+    (0x010101aa, "attr", "topRightRadius", 1),
+    (0x010101ab, "attr", "bottomLeftRadius", 1),
+    (0x010101ac, "attr", "bottomRightRadius", 1),
+    (0x010101ad, "attr", "left", 1),
+    (0x010101ae, "attr", "top", 1),
+    (0x010101af, "attr", "right", 1),
+    (0x010101b0, "attr", "bottom", 1),
+    (0x010101b1, "attr", "minLevel", 1),
+    (0x010101b2, "attr", "maxLevel", 1),
+    (0x010101b3, "attr", "fromDegrees", 1),
+    (0x010101b4, "attr", "toDegrees", 1),
+    (0x010101b5, "attr", "pivotX", 1),
+    (0x010101b6, "attr", "pivotY", 1),
+    (0x010101b7, "attr", "insetLeft", 1),
+    (0x010101b8, "attr", "insetRight", 1),
+    (0x010101b9, "attr", "insetTop", 1),
+    (0x010101ba, "attr", "insetBottom", 1),
+    (0x010101bb, "attr", "shareInterpolator", 1),
+    (0x010101bc, "attr", "fillBefore", 1),
+    (0x010101bd, "attr", "fillAfter", 1),
+    (0x010101be, "attr", "startOffset", 1),
+    (0x010101bf, "attr", "repeatCount", 1),
+    (0x010101c0, "attr", "repeatMode", 1),
+    (0x010101c1, "attr", "zAdjustment", 1),
+    (0x010101c2, "attr", "fromXScale", 1),
+    (0x010101c3, "attr", "toXScale", 1),
+    (0x010101c4, "attr", "fromYScale", 1),
+    (0x010101c5, "attr", "toYScale", 1),
+    (0x010101c6, "attr", "fromXDelta", 1),
+    (0x010101c7, "attr", "toXDelta", 1),
+    (0x010101c8, "attr", "fromYDelta", 1),
+    (0x010101c9, "attr", "toYDelta", 1),
+    (0x010101ca, "attr", "fromAlpha", 1),
+    (0x010101cb, "attr", "toAlpha", 1),
+    (0x010101cc, "attr", "delay", 1),
+    (0x010101cd, "attr", "animation", 1),
+    (0x010101ce, "attr", "animationOrder", 1),
+    (0x010101cf, "attr", "columnDelay", 1),
+    (0x010101d0, "attr", "rowDelay", 1),
+    (0x010101d1, "attr", "direction", 1),
+    (0x010101d2, "attr", "directionPriority", 1),
+    (0x010101d3, "attr", "factor", 1),
+    (0x010101d4, "attr", "cycles", 1),
+    (0x010101d5, "attr", "searchMode", 1),
+    (0x010101d6, "attr", "searchSuggestAuthority", 1),
+    (0x010101d7, "attr", "searchSuggestPath", 1),
+    (0x010101d8, "attr", "searchSuggestSelection", 1),
+    (0x010101d9, "attr", "searchSuggestIntentAction", 1),
+    (0x010101da, "attr", "searchSuggestIntentData", 1),
+    (0x010101db, "attr", "queryActionMsg", 1),
+    (0x010101dc, "attr", "suggestActionMsg", 1),
+    (0x010101dd, "attr", "suggestActionMsgColumn", 1),
+    (0x010101de, "attr", "menuCategory", 1),
+    (0x010101df, "attr", "orderInCategory", 1),
+    (0x010101e0, "attr", "checkableBehavior", 1),
+    (0x010101e1, "attr", "title", 1),
+    (0x010101e2, "attr", "titleCondensed", 1),
+    (0x010101e3, "attr", "alphabeticShortcut", 1),
+    (0x010101e4, "attr", "numericShortcut", 1),
+    (0x010101e5, "attr", "checkable", 1),
+    (0x010101e6, "attr", "selectable", 1),
+    (0x010101e7, "attr", "orderingFromXml", 1),
+    (0x010101e8, "attr", "key", 1),
+    (0x010101e9, "attr", "summary", 1),
+    (0x010101ea, "attr", "order", 1),
+    (0x010101eb, "attr", "widgetLayout", 1),
+    (0x010101ec, "attr", "dependency", 1),
+    (0x010101ed, "attr", "defaultValue", 1),
+    (0x010101ee, "attr", "shouldDisableView", 1),
+    (0x010101ef, "attr", "summaryOn", 1),
+    (0x010101f0, "attr", "summaryOff", 1),
+    (0x010101f1, "attr", "disableDependentsState", 1),
+    (0x010101f2, "attr", "dialogTitle", 1),
+    (0x010101f3, "attr", "dialogMessage", 1),
+    (0x010101f4, "attr", "dialogIcon", 1),
+    (0x010101f5, "attr", "positiveButtonText", 1),
+    (0x010101f6, "attr", "negativeButtonText", 1),
+    (0x010101f7, "attr", "dialogLayout", 1),
+    (0x010101f8, "attr", "entryValues", 1),
+    (0x010101f9, "attr", "ringtoneType", 1),
+    (0x010101fa, "attr", "showDefault", 1),
+    (0x010101fb, "attr", "showSilent", 1),
+    (0x010101fc, "attr", "scaleWidth", 1),
+    (0x010101fd, "attr", "scaleHeight", 1),
+    (0x010101fe, "attr", "scaleGravity", 1),
+    (0x010101ff, "attr", "ignoreGravity", 1),
+    (0x01010200, "attr", "foregroundGravity", 1),
+    (0x01010201, "attr", "tileMode", 1),
+    (0x01010202, "attr", "targetActivity", 1),
+    (0x01010203, "attr", "alwaysRetainTaskState", 1),
+    (0x01010204, "attr", "allowTaskReparenting", 1),
+    (0x01010205, "attr", "searchButtonText", 1),
+    (0x01010206, "attr", "colorForegroundInverse", 1),
+    (0x01010207, "attr", "textAppearanceButton", 1),
+    (0x01010208, "attr", "listSeparatorTextViewStyle", 1),
+    (0x01010209, "attr", "streamType", 1),
+    (0x0101020a, "attr", "clipOrientation", 1),
+    (0x0101020b, "attr", "centerColor", 1),
+    (0x0101020c, "attr", "minSdkVersion", 1),
+    (0x0101020d, "attr", "windowFullscreen", 1),
+    (0x0101020e, "attr", "unselectedAlpha", 1),
+    (0x0101020f, "attr", "progressBarStyleSmallTitle", 1),
+    (0x01010210, "attr", "ratingBarStyleIndicator", 1),
+    (0x01010211, "attr", "apiKey", 1),
+    (0x01010212, "attr", "textColorTertiary", 1),
+    (0x01010213, "attr", "textColorTertiaryInverse", 1),
+    (0x01010214, "attr", "listDivider", 1),
+    (0x01010215, "attr", "soundEffectsEnabled", 1),
+    (0x01010216, "attr", "keepScreenOn", 1),
+    (0x01010217, "attr", "lineSpacingExtra", 1),
+    (0x01010218, "attr", "lineSpacingMultiplier", 1),
+    (0x01010219, "attr", "listChoiceIndicatorSingle", 1),
+    (0x0101021a, "attr", "listChoiceIndicatorMultiple", 1),
+    (0x0101021b, "attr", "versionCode", 1),
+    (0x0101021c, "attr", "versionName", 1),
+    (0x0101021d, "attr", "marqueeRepeatLimit", 2),
+    (0x0101021e, "attr", "windowNoDisplay", 3),
+    (0x0101021f, "attr", "backgroundDimEnabled", 3),
+    (0x01010220, "attr", "inputType", 3),
+    (0x01010221, "attr", "isDefault", 3),
+    (0x01010222, "attr", "windowDisablePreview", 3),
+    (0x01010223, "attr", "privateImeOptions", 3),
+    (0x01010224, "attr", "editorExtras", 3),
+    (0x01010225, "attr", "settingsActivity", 3),
+    (0x01010226, "attr", "fastScrollEnabled", 3),
+    (0x01010227, "attr", "reqTouchScreen", 3),
+    (0x01010228, "attr", "reqKeyboardType", 3),
+    (0x01010229, "attr", "reqHardKeyboard", 3),
+    (0x0101022a, "attr", "reqNavigation", 3),
+    (0x0101022b, "attr", "windowSoftInputMode", 3),
+    (0x0101022c, "attr", "imeFullscreenBackground", 3),
+    (0x0101022d, "attr", "noHistory", 3),
+    (0x0101022e, "attr", "headerDividersEnabled", 3),
+    (0x0101022f, "attr", "footerDividersEnabled", 3),
+    (0x01010230, "attr", "candidatesTextStyleSpans", 3),
+    (0x01010231, "attr", "smoothScrollbar", 3),
+    (0x01010232, "attr", "reqFiveWayNav", 3),
+    (0x01010233, "attr", "keyBackground", 3),
+    (0x01010234, "attr", "keyTextSize", 3),
+    (0x01010235, "attr", "labelTextSize", 3),
+    (0x01010236, "attr", "keyTextColor", 3),
+    (0x01010237, "attr", "keyPreviewLayout", 3),
+    (0x01010238, "attr", "keyPreviewOffset", 3),
+    (0x01010239, "attr", "keyPreviewHeight", 3),
+    (0x0101023a, "attr", "verticalCorrection", 3),
+    (0x0101023b, "attr", "popupLayout", 3),
+    (0x0101023c, "attr", "state_long_pressable", 3),
+    (0x0101023d, "attr", "keyWidth", 3),
+    (0x0101023e, "attr", "keyHeight", 3),
+    (0x0101023f, "attr", "horizontalGap", 3),
+    (0x01010240, "attr", "verticalGap", 3),
+    (0x01010241, "attr", "rowEdgeFlags", 3),
+    (0x01010242, "attr", "codes", 3),
+    (0x01010243, "attr", "popupKeyboard", 3),
+    (0x01010244, "attr", "popupCharacters", 3),
+    (0x01010245, "attr", "keyEdgeFlags", 3),
+    (0x01010246, "attr", "isModifier", 3),
+    (0x01010247, "attr", "isSticky", 3),
+    (0x01010248, "attr", "isRepeatable", 3),
+    (0x01010249, "attr", "iconPreview", 3),
+    (0x0101024a, "attr", "keyOutputText", 3),
+    (0x0101024b, "attr", "keyLabel", 3),
+    (0x0101024c, "attr", "keyIcon", 3),
+    (0x0101024d, "attr", "keyboardMode", 3),
+    (0x0101024e, "attr", "isScrollContainer", 3),
+    (0x0101024f, "attr", "fillEnabled", 3),
+    (0x01010250, "attr", "updatePeriodMillis", 3),
+    (0x01010251, "attr", "initialLayout", 3),
+    (0x01010252, "attr", "voiceSearchMode", 3),
+    (0x01010253, "attr", "voiceLanguageModel", 3),
+    (0x01010254, "attr", "voicePromptText", 3),
+    (0x01010255, "attr", "voiceLanguage", 3),
+    (0x01010256, "attr", "voiceMaxResults", 3),
+    (0x01010257, "attr", "bottomOffset", 3),
+    (0x01010258, "attr", "topOffset", 3),
+    (0x01010259, "attr", "allowSingleTap", 3),
+    (0x0101025a, "attr", "handle", 3),
+    (0x0101025b, "attr", "content", 3),
+    (0x0101025c, "attr", "animateOnClick", 3),
+    (0x0101025d, "attr", "configure", 3),
+    (0x0101025e, "attr", "hapticFeedbackEnabled", 3),
+    (0x0101025f, "attr", "innerRadius", 3),
+    (0x01010260, "attr", "thickness", 3),
+    (0x01010261, "attr", "sharedUserLabel", 3),
+    (0x01010262, "attr", "dropDownWidth", 3),
+    (0x01010263, "attr", "dropDownAnchor", 3),
+    (0x01010264, "attr", "imeOptions", 3),
+    (0x01010265, "attr", "imeActionLabel", 3),
+    (0x01010266, "attr", "imeActionId", 3),
+    (0x01010268, "attr", "imeExtractEnterAnimation", 3),
+    (0x01010269, "attr", "imeExtractExitAnimation", 3),
+    (0x0101026a, "attr", "tension", 4),
+    (0x0101026b, "attr", "extraTension", 4),
+    (0x0101026c, "attr", "anyDensity", 4),
+    (0x0101026d, "attr", "searchSuggestThreshold", 4),
+    (0x0101026e, "attr", "includeInGlobalSearch", 4),
+    (0x0101026f, "attr", "onClick", 4),
+    (0x01010270, "attr", "targetSdkVersion", 4),
+    (0x01010271, "attr", "maxSdkVersion", 4),
+    (0x01010272, "attr", "testOnly", 4),
+    (0x01010273, "attr", "contentDescription", 4),
+    (0x01010274, "attr", "gestureStrokeWidth", 4),
+    (0x01010275, "attr", "gestureColor", 4),
+    (0x01010276, "attr", "uncertainGestureColor", 4),
+    (0x01010277, "attr", "fadeOffset", 4),
+    (0x01010278, "attr", "fadeDuration", 4),
+    (0x01010279, "attr", "gestureStrokeType", 4),
+    (0x0101027a, "attr", "gestureStrokeLengthThreshold", 4),
+    (0x0101027b, "attr", "gestureStrokeSquarenessThreshold", 4),
+    (0x0101027c, "attr", "gestureStrokeAngleThreshold", 4),
+    (0x0101027d, "attr", "eventsInterceptionEnabled", 4),
+    (0x0101027e, "attr", "fadeEnabled", 4),
+    (0x0101027f, "attr", "backupAgent", 4),
+    (0x01010280, "attr", "allowBackup", 4),
+    (0x01010281, "attr", "glEsVersion", 4),
+    (0x01010282, "attr", "queryAfterZeroResults", 4),
+    (0x01010283, "attr", "dropDownHeight", 4),
+    (0x01010284, "attr", "smallScreens", 4),
+    (0x01010285, "attr", "normalScreens", 4),
+    (0x01010286, "attr", "largeScreens", 4),
+    (0x01010287, "attr", "progressBarStyleInverse", 4),
+    (0x01010288, "attr", "progressBarStyleSmallInverse", 4),
+    (0x01010289, "attr", "progressBarStyleLargeInverse", 4),
+    (0x0101028a, "attr", "searchSettingsDescription", 4),
+    (0x0101028b, "attr", "textColorPrimaryInverseDisableOnly", 4),
+    (0x0101028c, "attr", "autoUrlDetect", 4),
+    (0x0101028d, "attr", "resizeable", 4),
+    (0x0101028e, "attr", "required", 5),
+    (0x0101028f, "attr", "accountType", 5),
+    (0x01010290, "attr", "contentAuthority", 5),
+    (0x01010291, "attr", "userVisible", 5),
+    (0x01010292, "attr", "windowShowWallpaper", 5),
+    (0x01010293, "attr", "wallpaperOpenEnterAnimation", 5),
+    (0x01010294, "attr", "wallpaperOpenExitAnimation", 5),
+    (0x01010295, "attr", "wallpaperCloseEnterAnimation", 5),
+    (0x01010296, "attr", "wallpaperCloseExitAnimation", 5),
+    (0x01010297, "attr", "wallpaperIntraOpenEnterAnimation", 5),
+    (0x01010298, "attr", "wallpaperIntraOpenExitAnimation", 5),
+    (0x01010299, "attr", "wallpaperIntraCloseEnterAnimation", 5),
+    (0x0101029a, "attr", "wallpaperIntraCloseExitAnimation", 5),
+    (0x0101029b, "attr", "supportsUploading", 5),
+    (0x0101029c, "attr", "killAfterRestore", 5),
+    (0x0101029d, "attr", "restoreNeedsApplication", 5),
+    (0x0101029e, "attr", "smallIcon", 5),
+    (0x0101029f, "attr", "accountPreferences", 5),
+    (0x010102a0, "attr", "textAppearanceSearchResultSubtitle", 5),
+    (0x010102a1, "attr", "textAppearanceSearchResultTitle", 5),
+    (0x010102a2, "attr", "summaryColumn", 5),
+    (0x010102a3, "attr", "detailColumn", 5),
+    (0x010102a4, "attr", "detailSocialSummary", 5),
+    (0x010102a5, "attr", "thumbnail", 5),
+    (0x010102a6, "attr", "detachWallpaper", 5),
+    (0x010102a7, "attr", "finishOnCloseSystemDialogs", 5),
+    (0x010102a8, "attr", "scrollbarFadeDuration", 5),
+    (0x010102a9, "attr", "scrollbarDefaultDelayBeforeFade", 5),
+    (0x010102aa, "attr", "fadeScrollbars", 5),
+    (0x010102ab, "attr", "colorBackgroundCacheHint", 5),
+    (0x010102ac, "attr", "dropDownHorizontalOffset", 5),
+    (0x010102ad, "attr", "dropDownVerticalOffset", 5),
+    (0x010102ae, "attr", "quickContactBadgeStyleWindowSmall", 5),
+    (0x010102af, "attr", "quickContactBadgeStyleWindowMedium", 5),
+    (0x010102b0, "attr", "quickContactBadgeStyleWindowLarge", 5),
+    (0x010102b1, "attr", "quickContactBadgeStyleSmallWindowSmall", 5),
+    (0x010102b2, "attr", "quickContactBadgeStyleSmallWindowMedium", 5),
+    (0x010102b3, "attr", "quickContactBadgeStyleSmallWindowLarge", 5),
+    (0x010102b4, "attr", "author", 7),
+    (0x010102b5, "attr", "autoStart", 7),
+    (0x010102b6, "attr", "expandableListViewWhiteStyle", 8),
+    (0x010102b7, "attr", "installLocation", 8),
+    (0x010102b8, "attr", "vmSafeMode", 8),
+    (0x010102b9, "attr", "webTextViewStyle", 8),
+    (0x010102ba, "attr", "restoreAnyVersion", 8),
+    (0x010102bb, "attr", "tabStripLeft", 8),
+    (0x010102bc, "attr", "tabStripRight", 8),
+    (0x010102bd, "attr", "tabStripEnabled", 8),
+    (0x010102be, "attr", "logo", 9),
+    (0x010102bf, "attr", "xlargeScreens", 9),
+    (0x010102c0, "attr", "immersive", 9),
+    (0x010102c1, "attr", "overScrollMode", 9),
+    (0x010102c2, "attr", "overScrollHeader", 9),
+    (0x010102c3, "attr", "overScrollFooter", 9),
+    (0x010102c4, "attr", "filterTouchesWhenObscured", 9),
+    (0x010102c5, "attr", "textSelectHandleLeft", 9),
+    (0x010102c6, "attr", "textSelectHandleRight", 9),
+    (0x010102c7, "attr", "textSelectHandle", 9),
+    (0x010102c8, "attr", "textSelectHandleWindowStyle", 9),
+    (0x010102c9, "attr", "popupAnimationStyle", 9),
+    (0x010102ca, "attr", "screenSize", 9),
+    (0x010102cb, "attr", "screenDensity", 9),
+    (0x010102cc, "attr", "allContactsName", 9),
+    (0x010102cd, "attr", "windowActionBar", 11),
+    (0x010102ce, "attr", "actionBarStyle", 11),
+    (0x010102cf, "attr", "navigationMode", 11),
+    (0x010102d0, "attr", "displayOptions", 11),
+    (0x010102d1, "attr", "subtitle", 11),
+    (0x010102d2, "attr", "customNavigationLayout", 11),
+    (0x010102d3, "attr", "hardwareAccelerated", 11),
+    (0x010102d4, "attr", "measureWithLargestChild", 11),
+    (0x010102d5, "attr", "animateFirstView", 11),
+    (0x010102d6, "attr", "dropDownSpinnerStyle", 11),
+    (0x010102d7, "attr", "actionDropDownStyle", 11),
+    (0x010102d8, "attr", "actionButtonStyle", 11),
+    (0x010102d9, "attr", "showAsAction", 11),
+    (0x010102da, "attr", "previewImage", 11),
+    (0x010102db, "attr", "actionModeBackground", 11),
+    (0x010102dc, "attr", "actionModeCloseDrawable", 11),
+    (0x010102dd, "attr", "windowActionModeOverlay", 11),
+    (0x010102de, "attr", "valueFrom", 11),
+    (0x010102df, "attr", "valueTo", 11),
+    (0x010102e0, "attr", "valueType", 11),
+    (0x010102e1, "attr", "propertyName", 11),
+    (0x010102e2, "attr", "ordering", 11),
+    (0x010102e3, "attr", "fragment", 11),
+    (0x010102e4, "attr", "windowActionBarOverlay", 11),
+    (0x010102e5, "attr", "fragmentOpenEnterAnimation", 11),
+    (0x010102e6, "attr", "fragmentOpenExitAnimation", 11),
+    (0x010102e7, "attr", "fragmentCloseEnterAnimation", 11),
+    (0x010102e8, "attr", "fragmentCloseExitAnimation", 11),
+    (0x010102e9, "attr", "fragmentFadeEnterAnimation", 11),
+    (0x010102ea, "attr", "fragmentFadeExitAnimation", 11),
+    (0x010102eb, "attr", "actionBarSize", 11),
+    (0x010102ec, "attr", "imeSubtypeLocale", 11),
+    (0x010102ed, "attr", "imeSubtypeMode", 11),
+    (0x010102ee, "attr", "imeSubtypeExtraValue", 11),
+    (0x010102ef, "attr", "splitMotionEvents", 11),
+    (0x010102f0, "attr", "listChoiceBackgroundIndicator", 11),
+    (0x010102f1, "attr", "spinnerMode", 11),
+    (0x010102f2, "attr", "animateLayoutChanges", 11),
+    (0x010102f3, "attr", "actionBarTabStyle", 11),
+    (0x010102f4, "attr", "actionBarTabBarStyle", 11),
+    (0x010102f5, "attr", "actionBarTabTextStyle", 11),
+    (0x010102f6, "attr", "actionOverflowButtonStyle", 11),
+    (0x010102f7, "attr", "actionModeCloseButtonStyle", 11),
+    (0x010102f8, "attr", "titleTextStyle", 11),
+    (0x010102f9, "attr", "subtitleTextStyle", 11),
+    (0x010102fa, "attr", "iconifiedByDefault", 11),
+    (0x010102fb, "attr", "actionLayout", 11),
+    (0x010102fc, "attr", "actionViewClass", 11),
+    (0x010102fd, "attr", "activatedBackgroundIndicator", 11),
+    (0x010102fe, "attr", "state_activated", 11),
+    (0x010102ff, "attr", "listPopupWindowStyle", 11),
+    (0x01010300, "attr", "popupMenuStyle", 11),
+    (0x01010301, "attr", "textAppearanceLargePopupMenu", 11),
+    (0x01010302, "attr", "textAppearanceSmallPopupMenu", 11),
+    (0x01010303, "attr", "breadCrumbTitle", 11),
+    (0x01010304, "attr", "breadCrumbShortTitle", 11),
+    (0x01010305, "attr", "listDividerAlertDialog", 11),
+    (0x01010306, "attr", "textColorAlertDialogListItem", 11),
+    (0x01010307, "attr", "loopViews", 11),
+    (0x01010308, "attr", "dialogTheme", 11),
+    (0x01010309, "attr", "alertDialogTheme", 11),
+    (0x0101030a, "attr", "dividerVertical", 11),
+    (0x0101030b, "attr", "homeAsUpIndicator", 11),
+    (0x0101030c, "attr", "enterFadeDuration", 11),
+    (0x0101030d, "attr", "exitFadeDuration", 11),
+    (0x0101030e, "attr", "selectableItemBackground", 11),
+    (0x0101030f, "attr", "autoAdvanceViewId", 11),
+    (0x01010310, "attr", "useIntrinsicSizeAsMinimum", 11),
+    (0x01010311, "attr", "actionModeCutDrawable", 11),
+    (0x01010312, "attr", "actionModeCopyDrawable", 11),
+    (0x01010313, "attr", "actionModePasteDrawable", 11),
+    (0x01010314, "attr", "textEditPasteWindowLayout", 11),
+    (0x01010315, "attr", "textEditNoPasteWindowLayout", 11),
+    (0x01010316, "attr", "textIsSelectable", 11),
+    (0x01010317, "attr", "windowEnableSplitTouch", 11),
+    (0x01010318, "attr", "indeterminateProgressStyle", 11),
+    (0x01010319, "attr", "progressBarPadding", 11),
+    (0x0101031a, "attr", "animationResolution", 11),
+    (0x0101031b, "attr", "state_accelerated", 11),
+    (0x0101031c, "attr", "baseline", 11),
+    (0x0101031d, "attr", "homeLayout", 11),
+    (0x0101031e, "attr", "opacity", 11),
+    (0x0101031f, "attr", "alpha", 11),
+    (0x01010320, "attr", "transformPivotX", 11),
+    (0x01010321, "attr", "transformPivotY", 11),
+    (0x01010322, "attr", "translationX", 11),
+    (0x01010323, "attr", "translationY", 11),
+    (0x01010324, "attr", "scaleX", 11),
+    (0x01010325, "attr", "scaleY", 11),
+    (0x01010326, "attr", "rotation", 11),
+    (0x01010327, "attr", "rotationX", 11),
+    (0x01010328, "attr", "rotationY", 11),
+    (0x01010329, "attr", "showDividers", 11),
+    (0x0101032a, "attr", "dividerPadding", 11),
+    (0x0101032b, "attr", "borderlessButtonStyle", 11),
+    (0x0101032c, "attr", "dividerHorizontal", 11),
+    (0x0101032d, "attr", "itemPadding", 11),
+    (0x0101032e, "attr", "buttonBarStyle", 11),
+    (0x0101032f, "attr", "buttonBarButtonStyle", 11),
+    (0x01010330, "attr", "segmentedButtonStyle", 11),
+    (0x01010331, "attr", "staticWallpaperPreview", 11),
+    (0x01010332, "attr", "allowParallelSyncs", 11),
+    (0x01010333, "attr", "isAlwaysSyncable", 11),
+    (0x01010334, "attr", "verticalScrollbarPosition", 11),
+    (0x01010335, "attr", "fastScrollAlwaysVisible", 11),
+    (0x01010336, "attr", "fastScrollThumbDrawable", 11),
+    (0x01010337, "attr", "fastScrollPreviewBackgroundLeft", 11),
+    (0x01010338, "attr", "fastScrollPreviewBackgroundRight", 11),
+    (0x01010339, "attr", "fastScrollTrackDrawable", 11),
+    (0x0101033a, "attr", "fastScrollOverlayPosition", 11),
+    (0x0101033b, "attr", "customTokens", 11),
+    (0x0101033c, "attr", "nextFocusForward", 11),
+    (0x0101033d, "attr", "firstDayOfWeek", 11),
+    (0x0101033e, "attr", "showWeekNumber", 11),
+    (0x0101033f, "attr", "minDate", 11),
+    (0x01010340, "attr", "maxDate", 11),
+    (0x01010341, "attr", "shownWeekCount", 11),
+    (0x01010342, "attr", "selectedWeekBackgroundColor", 11),
+    (0x01010343, "attr", "focusedMonthDateColor", 11),
+    (0x01010344, "attr", "unfocusedMonthDateColor", 11),
+    (0x01010345, "attr", "weekNumberColor", 11),
+    (0x01010346, "attr", "weekSeparatorLineColor", 11),
+    (0x01010347, "attr", "selectedDateVerticalBar", 11),
+    (0x01010348, "attr", "weekDayTextAppearance", 11),
+    (0x01010349, "attr", "dateTextAppearance", 11),
+    (0x0101034b, "attr", "spinnersShown", 11),
+    (0x0101034c, "attr", "calendarViewShown", 11),
+    (0x0101034d, "attr", "state_multiline", 11),
+    (0x0101034e, "attr", "detailsElementBackground", 11),
+    (0x0101034f, "attr", "textColorHighlightInverse", 11),
+    (0x01010350, "attr", "textColorLinkInverse", 11),
+    (0x01010351, "attr", "editTextColor", 11),
+    (0x01010352, "attr", "editTextBackground", 11),
+    (0x01010353, "attr", "horizontalScrollViewStyle", 11),
+    (0x01010354, "attr", "layerType", 11),
+    (0x01010355, "attr", "alertDialogIcon", 11),
+    (0x01010356, "attr", "windowMinWidthMajor", 11),
+    (0x01010357, "attr", "windowMinWidthMinor", 11),
+    (0x01010358, "attr", "queryHint", 11),
+    (0x01010359, "attr", "fastScrollTextColor", 11),
+    (0x0101035a, "attr", "largeHeap", 11),
+    (0x0101035b, "attr", "windowCloseOnTouchOutside", 11),
+    (0x0101035c, "attr", "datePickerStyle", 11),
+    (0x0101035d, "attr", "calendarViewStyle", 11),
+    (0x0101035e, "attr", "textEditSidePasteWindowLayout", 11),
+    (0x0101035f, "attr", "textEditSideNoPasteWindowLayout", 11),
+    (0x01010360, "attr", "actionMenuTextAppearance", 11),
+    (0x01010361, "attr", "actionMenuTextColor", 11),
+    (0x01010362, "attr", "textCursorDrawable", 12),
+    (0x01010363, "attr", "resizeMode", 12),
+    (0x01010364, "attr", "requiresSmallestWidthDp", 13),
+    (0x01010365, "attr", "compatibleWidthLimitDp", 13),
+    (0x01010366, "attr", "largestWidthLimitDp", 13),
+    (0x01010367, "attr", "state_hovered", 14),
+    (0x01010368, "attr", "state_drag_can_accept", 14),
+    (0x01010369, "attr", "state_drag_hovered", 14),
+    (0x0101036a, "attr", "stopWithTask", 14),
+    (0x0101036b, "attr", "switchTextOn", 14),
+    (0x0101036c, "attr", "switchTextOff", 14),
+    (0x0101036d, "attr", "switchPreferenceStyle", 14),
+    (0x0101036e, "attr", "switchTextAppearance", 14),
+    (0x0101036f, "attr", "track", 14),
+    (0x01010370, "attr", "switchMinWidth", 14),
+    (0x01010371, "attr", "switchPadding", 14),
+    (0x01010372, "attr", "thumbTextPadding", 14),
+    (0x01010373, "attr", "textSuggestionsWindowStyle", 14),
+    (0x01010374, "attr", "textEditSuggestionItemLayout", 14),
+    (0x01010375, "attr", "rowCount", 14),
+    (0x01010376, "attr", "rowOrderPreserved", 14),
+    (0x01010377, "attr", "columnCount", 14),
+    (0x01010378, "attr", "columnOrderPreserved", 14),
+    (0x01010379, "attr", "useDefaultMargins", 14),
+    (0x0101037a, "attr", "alignmentMode", 14),
+    (0x0101037b, "attr", "layout_row", 14),
+    (0x0101037c, "attr", "layout_rowSpan", 14),
+    (0x0101037d, "attr", "layout_columnSpan", 14),
+    (0x0101037e, "attr", "actionModeSelectAllDrawable", 14),
+    (0x0101037f, "attr", "isAuxiliary", 14),
+    (0x01010380, "attr", "accessibilityEventTypes", 14),
+    (0x01010381, "attr", "packageNames", 14),
+    (0x01010382, "attr", "accessibilityFeedbackType", 14),
+    (0x01010383, "attr", "notificationTimeout", 14),
+    (0x01010384, "attr", "accessibilityFlags", 14),
+    (0x01010385, "attr", "canRetrieveWindowContent", 14),
+    (0x01010386, "attr", "listPreferredItemHeightLarge", 14),
+    (0x01010387, "attr", "listPreferredItemHeightSmall", 14),
+    (0x01010388, "attr", "actionBarSplitStyle", 14),
+    (0x01010389, "attr", "actionProviderClass", 14),
+    (0x0101038a, "attr", "backgroundStacked", 14),
+    (0x0101038b, "attr", "backgroundSplit", 14),
+    (0x0101038c, "attr", "textAllCaps", 14),
+    (0x0101038d, "attr", "colorPressedHighlight", 14),
+    (0x0101038e, "attr", "colorLongPressedHighlight", 14),
+    (0x0101038f, "attr", "colorFocusedHighlight", 14),
+    (0x01010390, "attr", "colorActivatedHighlight", 14),
+    (0x01010391, "attr", "colorMultiSelectHighlight", 14),
+    (0x01010392, "attr", "drawableStart", 14),
+    (0x01010393, "attr", "drawableEnd", 14),
+    (0x01010394, "attr", "actionModeStyle", 14),
+    (0x01010395, "attr", "minResizeWidth", 14),
+    (0x01010396, "attr", "minResizeHeight", 14),
+    (0x01010397, "attr", "actionBarWidgetTheme", 14),
+    (0x01010398, "attr", "uiOptions", 14),
+    (0x01010399, "attr", "subtypeLocale", 14),
+    (0x0101039a, "attr", "subtypeExtraValue", 14),
+    (0x0101039b, "attr", "actionBarDivider", 14),
+    (0x0101039c, "attr", "actionBarItemBackground", 14),
+    (0x0101039d, "attr", "actionModeSplitBackground", 14),
+    (0x0101039e, "attr", "textAppearanceListItem", 14),
+    (0x0101039f, "attr", "textAppearanceListItemSmall", 14),
+    (0x010103a0, "attr", "targetDescriptions", 14),
+    (0x010103a1, "attr", "directionDescriptions", 14),
+    (0x010103a2, "attr", "overridesImplicitlyEnabledSubtype", 14),
+    (0x010103a3, "attr", "listPreferredItemPaddingLeft", 14),
+    (0x010103a4, "attr", "listPreferredItemPaddingRight", 14),
+    (0x010103a5, "attr", "requiresFadingEdge", 14),
+    (0x010103a6, "attr", "publicKey", 15),
+    (0x010103a7, "attr", "parentActivityName", 16),
+    (0x010103a9, "attr", "isolatedProcess", 16),
+    (0x010103aa, "attr", "importantForAccessibility", 16),
+    (0x010103ab, "attr", "keyboardLayout", 16),
+    (0x010103ac, "attr", "fontFamily", 16),
+    (0x010103ad, "attr", "mediaRouteButtonStyle", 16),
+    (0x010103ae, "attr", "mediaRouteTypes", 16),
+    (0x010103af, "attr", "supportsRtl", 17),
+    (0x010103b0, "attr", "textDirection", 17),
+    (0x010103b1, "attr", "textAlignment", 17),
+    (0x010103b2, "attr", "layoutDirection", 17),
+    (0x010103b3, "attr", "paddingStart", 17),
+    (0x010103b4, "attr", "paddingEnd", 17),
+    (0x010103b5, "attr", "layout_marginStart", 17),
+    (0x010103b6, "attr", "layout_marginEnd", 17),
+    (0x010103b7, "attr", "layout_toStartOf", 17),
+    (0x010103b8, "attr", "layout_toEndOf", 17),
+    (0x010103b9, "attr", "layout_alignStart", 17),
+    (0x010103ba, "attr", "layout_alignEnd", 17),
+    (0x010103bb, "attr", "layout_alignParentStart", 17),
+    (0x010103bc, "attr", "layout_alignParentEnd", 17),
+    (0x010103bd, "attr", "listPreferredItemPaddingStart", 17),
+    (0x010103be, "attr", "listPreferredItemPaddingEnd", 17),
+    (0x010103bf, "attr", "singleUser", 17),
+    (0x010103c0, "attr", "presentationTheme", 17),
+    (0x010103c1, "attr", "subtypeId", 17),
+    (0x010103c2, "attr", "initialKeyguardLayout", 17),
+    (0x010103c4, "attr", "widgetCategory", 17),
+    (0x010103c5, "attr", "permissionGroupFlags", 17),
+    (0x010103c6, "attr", "labelFor", 17),
+    (0x010103c7, "attr", "permissionFlags", 17),
+    (0x010103c8, "attr", "checkedTextViewStyle", 17),
+    (0x010103c9, "attr", "showOnLockScreen", 17),
+    (0x010103ca, "attr", "format12Hour", 17),
+    (0x010103cb, "attr", "format24Hour", 17),
+    (0x010103cc, "attr", "timeZone", 17),
+    (0x010103cd, "attr", "mipMap", 18),
+    (0x010103ce, "attr", "mirrorForRtl", 18),
+    (0x010103cf, "attr", "windowOverscan", 18),
+    (0x010103d0, "attr", "requiredForAllUsers", 18),
+    (0x010103d1, "attr", "indicatorStart", 18),
+    (0x010103d2, "attr", "indicatorEnd", 18),
+    (0x010103d3, "attr", "childIndicatorStart", 18),
+    (0x010103d4, "attr", "childIndicatorEnd", 18),
+    (0x010103d5, "attr", "restrictedAccountType", 18),
+    (0x010103d6, "attr", "requiredAccountType", 18),
+    (0x010103d7, "attr", "canRequestTouchExplorationMode", 18),
+    (0x010103d8, "attr", "canRequestEnhancedWebAccessibility", 18),
+    (0x010103d9, "attr", "canRequestFilterKeyEvents", 18),
+    (0x010103da, "attr", "layoutMode", 18),
+    (0x010103db, "attr", "keySet", 19),
+    (0x010103dc, "attr", "targetId", 19),
+    (0x010103dd, "attr", "fromScene", 19),
+    (0x010103de, "attr", "toScene", 19),
+    (0x010103df, "attr", "transition", 19),
+    (0x010103e0, "attr", "transitionOrdering", 19),
+    (0x010103e1, "attr", "fadingMode", 19),
+    (0x010103e2, "attr", "startDelay", 19),
+    (0x010103e3, "attr", "ssp", 19),
+    (0x010103e4, "attr", "sspPrefix", 19),
+    (0x010103e5, "attr", "sspPattern", 19),
+    (0x010103e6, "attr", "addPrintersActivity", 19),
+    (0x010103e7, "attr", "vendor", 19),
+    (0x010103e8, "attr", "category", 19),
+    (0x010103e9, "attr", "isAsciiCapable", 19),
+    (0x010103ea, "attr", "autoMirrored", 19),
+    (0x010103eb, "attr", "supportsSwitchingToNextInputMethod", 19),
+    (0x010103ec, "attr", "requireDeviceUnlock", 19),
+    (0x010103ed, "attr", "apduServiceBanner", 19),
+    (0x010103ee, "attr", "accessibilityLiveRegion", 19),
+    (0x010103ef, "attr", "windowTranslucentStatus", 19),
+    (0x010103f0, "attr", "windowTranslucentNavigation", 19),
+    (0x010103f1, "attr", "advancedPrintOptionsActivity", 19),
+    (0x010103f2, "attr", "banner", 20),
+    (0x010103f3, "attr", "windowSwipeToDismiss", 20),
+    (0x010103f4, "attr", "isGame", 21),
+    (0x010103f5, "attr", "allowEmbedded", 21),
+    (0x010103f6, "attr", "setupActivity", 21),
+    (0x010103f7, "attr", "fastScrollStyle", 21),
+    (0x010103f8, "attr", "windowContentTransitions", 21),
+    (0x010103f9, "attr", "windowContentTransitionManager", 21),
+    (0x010103fa, "attr", "translationZ", 21),
+    (0x010103fb, "attr", "tintMode", 21),
+    (0x010103fc, "attr", "controlX1", 21),
+    (0x010103fd, "attr", "controlY1", 21),
+    (0x010103fe, "attr", "controlX2", 21),
+    (0x010103ff, "attr", "controlY2", 21),
+    (0x01010400, "attr", "transitionName", 21),
+    (0x01010401, "attr", "transitionGroup", 21),
+    (0x01010402, "attr", "viewportWidth", 21),
+    (0x01010403, "attr", "viewportHeight", 21),
+    (0x01010404, "attr", "fillColor", 21),
+    (0x01010405, "attr", "pathData", 21),
+    (0x01010406, "attr", "strokeColor", 21),
+    (0x01010407, "attr", "strokeWidth", 21),
+    (0x01010408, "attr", "trimPathStart", 21),
+    (0x01010409, "attr", "trimPathEnd", 21),
+    (0x0101040a, "attr", "trimPathOffset", 21),
+    (0x0101040b, "attr", "strokeLineCap", 21),
+    (0x0101040c, "attr", "strokeLineJoin", 21),
+    (0x0101040d, "attr", "strokeMiterLimit", 21),
+    (0x01010429, "attr", "colorControlNormal", 21),
+    (0x0101042a, "attr", "colorControlActivated", 21),
+    (0x0101042b, "attr", "colorButtonNormal", 21),
+    (0x0101042c, "attr", "colorControlHighlight", 21),
+    (0x0101042d, "attr", "persistableMode", 21),
+    (0x0101042e, "attr", "titleTextAppearance", 21),
+    (0x0101042f, "attr", "subtitleTextAppearance", 21),
+    (0x01010430, "attr", "slideEdge", 21),
+    (0x01010431, "attr", "actionBarTheme", 21),
+    (0x01010432, "attr", "textAppearanceListItemSecondary", 21),
+    (0x01010433, "attr", "colorPrimary", 21),
+    (0x01010434, "attr", "colorPrimaryDark", 21),
+    (0x01010435, "attr", "colorAccent", 21),
+    (0x01010436, "attr", "nestedScrollingEnabled", 21),
+    (0x01010437, "attr", "windowEnterTransition", 21),
+    (0x01010438, "attr", "windowExitTransition", 21),
+    (0x01010439, "attr", "windowSharedElementEnterTransition", 21),
+    (0x0101043a, "attr", "windowSharedElementExitTransition", 21),
+    (0x0101043b, "attr", "windowAllowReturnTransitionOverlap", 21),
+    (0x0101043c, "attr", "windowAllowEnterTransitionOverlap", 21),
+    (0x0101043d, "attr", "sessionService", 21),
+    (0x0101043e, "attr", "stackViewStyle", 21),
+    (0x0101043f, "attr", "switchStyle", 21),
+    (0x01010440, "attr", "elevation", 21),
+    (0x01010441, "attr", "excludeId", 21),
+    (0x01010442, "attr", "excludeClass", 21),
+    (0x01010443, "attr", "hideOnContentScroll", 21),
+    (0x01010444, "attr", "actionOverflowMenuStyle", 21),
+    (0x01010445, "attr", "documentLaunchMode", 21),
+    (0x01010446, "attr", "maxRecents", 21),
+    (0x01010447, "attr", "autoRemoveFromRecents", 21),
+    (0x01010448, "attr", "stateListAnimator", 21),
+    (0x01010449, "attr", "toId", 21),
+    (0x0101044a, "attr", "fromId", 21),
+    (0x0101044b, "attr", "reversible", 21),
+    (0x0101044c, "attr", "splitTrack", 21),
+    (0x0101044d, "attr", "targetName", 21),
+    (0x0101044e, "attr", "excludeName", 21),
+    (0x0101044f, "attr", "matchOrder", 21),
+    (0x01010450, "attr", "windowDrawsSystemBarBackgrounds", 21),
+    (0x01010451, "attr", "statusBarColor", 21),
+    (0x01010452, "attr", "navigationBarColor", 21),
+    (0x01010453, "attr", "contentInsetStart", 21),
+    (0x01010454, "attr", "contentInsetEnd", 21),
+    (0x01010455, "attr", "contentInsetLeft", 21),
+    (0x01010456, "attr", "contentInsetRight", 21),
+    (0x01010457, "attr", "paddingMode", 21),
+    (0x01010458, "attr", "layout_rowWeight", 21),
+    (0x01010459, "attr", "layout_columnWeight", 21),
+    (0x0101045a, "attr", "translateX", 21),
+    (0x0101045b, "attr", "translateY", 21),
+    (0x0101045c, "attr", "selectableItemBackgroundBorderless", 21),
+    (0x0101045d, "attr", "elegantTextHeight", 21),
+    (0x01010461, "attr", "windowTransitionBackgroundFadeDuration", 21),
+    (0x01010462, "attr", "overlapAnchor", 21),
+    (0x01010463, "attr", "progressTint", 21),
+    (0x01010464, "attr", "progressTintMode", 21),
+    (0x01010465, "attr", "progressBackgroundTint", 21),
+    (0x01010466, "attr", "progressBackgroundTintMode", 21),
+    (0x01010467, "attr", "secondaryProgressTint", 21),
+    (0x01010468, "attr", "secondaryProgressTintMode", 21),
+    (0x01010469, "attr", "indeterminateTint", 21),
+    (0x0101046a, "attr", "indeterminateTintMode", 21),
+    (0x0101046b, "attr", "backgroundTint", 21),
+    (0x0101046c, "attr", "backgroundTintMode", 21),
+    (0x0101046d, "attr", "foregroundTint", 21),
+    (0x0101046e, "attr", "foregroundTintMode", 21),
+    (0x0101046f, "attr", "buttonTint", 21),
+    (0x01010470, "attr", "buttonTintMode", 21),
+    (0x01010471, "attr", "thumbTint", 21),
+    (0x01010472, "attr", "thumbTintMode", 21),
+    (0x01010473, "attr", "fullBackupOnly", 21),
+    (0x01010474, "attr", "propertyXName", 21),
+    (0x01010475, "attr", "propertyYName", 21),
+    (0x01010476, "attr", "relinquishTaskIdentity", 21),
+    (0x01010477, "attr", "tileModeX", 21),
+    (0x01010478, "attr", "tileModeY", 21),
+    (0x01010479, "attr", "actionModeShareDrawable", 21),
+    (0x0101047a, "attr", "actionModeFindDrawable", 21),
+    (0x0101047b, "attr", "actionModeWebSearchDrawable", 21),
+    (0x0101047c, "attr", "transitionVisibilityMode", 21),
+    (0x0101047d, "attr", "minimumHorizontalAngle", 21),
+    (0x0101047e, "attr", "minimumVerticalAngle", 21),
+    (0x0101047f, "attr", "maximumAngle", 21),
+    (0x01010480, "attr", "searchViewStyle", 21),
+    (0x01010481, "attr", "closeIcon", 21),
+    (0x01010482, "attr", "goIcon", 21),
+    (0x01010483, "attr", "searchIcon", 21),
+    (0x01010484, "attr", "voiceIcon", 21),
+    (0x01010485, "attr", "commitIcon", 21),
+    (0x01010486, "attr", "suggestionRowLayout", 21),
+    (0x01010487, "attr", "queryBackground", 21),
+    (0x01010488, "attr", "submitBackground", 21),
+    (0x01010489, "attr", "buttonBarPositiveButtonStyle", 21),
+    (0x0101048a, "attr", "buttonBarNeutralButtonStyle", 21),
+    (0x0101048b, "attr", "buttonBarNegativeButtonStyle", 21),
+    (0x0101048c, "attr", "popupElevation", 21),
+    (0x0101048d, "attr", "actionBarPopupTheme", 21),
+    (0x0101048e, "attr", "multiArch", 21),
+    (0x0101048f, "attr", "touchscreenBlocksFocus", 21),
+    (0x01010490, "attr", "windowElevation", 21),
+    (0x01010491, "attr", "launchTaskBehindTargetAnimation", 21),
+    (0x01010492, "attr", "launchTaskBehindSourceAnimation", 21),
+    (0x01010493, "attr", "restrictionType", 21),
+    (0x01010494, "attr", "dayOfWeekBackground", 21),
+    (0x01010495, "attr", "dayOfWeekTextAppearance", 21),
+    (0x01010496, "attr", "headerMonthTextAppearance", 21),
+    (0x01010497, "attr", "headerDayOfMonthTextAppearance", 21),
+    (0x01010498, "attr", "headerYearTextAppearance", 21),
+    (0x01010499, "attr", "yearListItemTextAppearance", 21),
+    (0x0101049a, "attr", "yearListSelectorColor", 21),
+    (0x0101049b, "attr", "calendarTextColor", 21),
+    (0x0101049c, "attr", "recognitionService", 21),
+    (0x0101049d, "attr", "timePickerStyle", 21),
+    (0x0101049e, "attr", "timePickerDialogTheme", 21),
+    (0x0101049f, "attr", "headerTimeTextAppearance", 21),
+    (0x010104a0, "attr", "headerAmPmTextAppearance", 21),
+    (0x010104a1, "attr", "numbersTextColor", 21),
+    (0x010104a2, "attr", "numbersBackgroundColor", 21),
+    (0x010104a3, "attr", "numbersSelectorColor", 21),
+    (0x010104a4, "attr", "amPmTextColor", 21),
+    (0x010104a5, "attr", "amPmBackgroundColor", 21),
+    (0x010104a7, "attr", "checkMarkTint", 21),
+    (0x010104a8, "attr", "checkMarkTintMode", 21),
+    (0x010104a9, "attr", "popupTheme", 21),
+    (0x010104aa, "attr", "toolbarStyle", 21),
+    (0x010104ab, "attr", "windowClipToOutline", 21),
+    (0x010104ac, "attr", "datePickerDialogTheme", 21),
+    (0x010104ad, "attr", "showText", 21),
+    (0x010104ae, "attr", "windowReturnTransition", 21),
+    (0x010104af, "attr", "windowReenterTransition", 21),
+    (0x010104b0, "attr", "windowSharedElementReturnTransition", 21),
+    (0x010104b1, "attr", "windowSharedElementReenterTransition", 21),
+    (0x010104b2, "attr", "resumeWhilePausing", 21),
+    (0x010104b3, "attr", "datePickerMode", 21),
+    (0x010104b4, "attr", "timePickerMode", 21),
+    (0x010104b5, "attr", "inset", 21),
+    (0x010104b6, "attr", "letterSpacing", 21),
+    (0x010104b7, "attr", "fontFeatureSettings", 21),
+    (0x010104b8, "attr", "outlineProvider", 21),
+    (0x010104b9, "attr", "contentAgeHint", 21),
+    (0x010104ba, "attr", "country", 21),
+    (0x010104bb, "attr", "windowSharedElementsUseOverlay", 21),
+    (0x010104bc, "attr", "reparent", 21),
+    (0x010104bd, "attr", "reparentWithOverlay", 21),
+    (0x010104be, "attr", "ambientShadowAlpha", 21),
+    (0x010104bf, "attr", "spotShadowAlpha", 21),
+    (0x010104c0, "attr", "navigationIcon", 21),
+    (0x010104c1, "attr", "navigationContentDescription", 21),
+    (0x010104c2, "attr", "fragmentExitTransition", 21),
+    (0x010104c3, "attr", "fragmentEnterTransition", 21),
+    (0x010104c4, "attr", "fragmentSharedElementEnterTransition", 21),
+    (0x010104c5, "attr", "fragmentReturnTransition", 21),
+    (0x010104c6, "attr", "fragmentSharedElementReturnTransition", 21),
+    (0x010104c7, "attr", "fragmentReenterTransition", 21),
+    (0x010104c8, "attr", "fragmentAllowEnterTransitionOverlap", 21),
+    (0x010104c9, "attr", "fragmentAllowReturnTransitionOverlap", 21),
+    (0x010104ca, "attr", "patternPathData", 21),
+    (0x010104cb, "attr", "strokeAlpha", 21),
+    (0x010104cc, "attr", "fillAlpha", 21),
+    (0x010104cd, "attr", "windowActivityTransitions", 21),
+    (0x010104ce, "attr", "colorEdgeEffect", 21),
+    (0x010104cf, "attr", "resizeClip", 21),
+    (0x010104d0, "attr", "collapseContentDescription", 21),
+    (0x010104d1, "attr", "accessibilityTraversalBefore", 22),
+    (0x010104d2, "attr", "accessibilityTraversalAfter", 22),
+    (0x010104d3, "attr", "dialogPreferredPadding", 22),
+    (0x010104d4, "attr", "searchHintIcon", 22),
+    (0x010104d5, "attr", "revisionCode", 22),
+    (0x010104d6, "attr", "drawableTint", 23),
+    (0x010104d7, "attr", "drawableTintMode", 23),
+    (0x010104d8, "attr", "fraction", 23),
+    (0x010104d9, "attr", "trackTint", 23),
+    (0x010104da, "attr", "trackTintMode", 23),
+    (0x010104db, "attr", "start", 23),
+    (0x010104dc, "attr", "end", 23),
+    (0x010104dd, "attr", "breakStrategy", 23),
+    (0x010104de, "attr", "hyphenationFrequency", 23),
+    (0x010104df, "attr", "allowUndo", 23),
+    (0x010104e0, "attr", "windowLightStatusBar", 23),
+    (0x010104e1, "attr", "numbersInnerTextColor", 23),
+    (0x010104e2, "attr", "colorBackgroundFloating", 23),
+    (0x010104e3, "attr", "titleTextColor", 23),
+    (0x010104e4, "attr", "subtitleTextColor", 23),
+    (0x010104e5, "attr", "thumbPosition", 23),
+    (0x010104e6, "attr", "scrollIndicators", 23),
+    (0x010104e7, "attr", "contextClickable", 23),
+    (0x010104e8, "attr", "fingerprintAuthDrawable", 23),
+    (0x010104e9, "attr", "logoDescription", 23),
+    (0x010104ea, "attr", "extractNativeLibs", 23),
+    (0x010104eb, "attr", "fullBackupContent", 23),
+    (0x010104ec, "attr", "usesCleartextTraffic", 23),
+    (0x010104ed, "attr", "lockTaskMode", 23),
+    (0x010104ee, "attr", "autoVerify", 23),
+    (0x010104ef, "attr", "showForAllUsers", 23),
+    (0x010104f0, "attr", "supportsAssist", 23),
+    (0x010104f1, "attr", "supportsLaunchVoiceAssistFromKeyguard", 23),
+    (0x010104f2, "attr", "listMenuViewStyle", 24),
+    (0x010104f3, "attr", "subMenuArrow", 24),
+    (0x010104f4, "attr", "defaultWidth", 24),
+    (0x010104f5, "attr", "defaultHeight", 24),
+    (0x010104f6, "attr", "resizeableActivity", 24),
+    (0x010104f7, "attr", "supportsPictureInPicture", 24),
+    (0x010104f8, "attr", "titleMargin", 24),
+    (0x010104f9, "attr", "titleMarginStart", 24),
+    (0x010104fa, "attr", "titleMarginEnd", 24),
+    (0x010104fb, "attr", "titleMarginTop", 24),
+    (0x010104fc, "attr", "titleMarginBottom", 24),
+    (0x010104fd, "attr", "maxButtonHeight", 24),
+    (0x010104fe, "attr", "buttonGravity", 24),
+    (0x010104ff, "attr", "collapseIcon", 24),
+    (0x01010500, "attr", "level", 24),
+    (0x01010501, "attr", "contextPopupMenuStyle", 24),
+    (0x01010502, "attr", "textAppearancePopupMenuHeader", 24),
+    (0x01010503, "attr", "windowBackgroundFallback", 24),
+    (0x01010504, "attr", "defaultToDeviceProtectedStorage", 24),
+    (0x01010505, "attr", "directBootAware", 24),
+    (0x01010506, "attr", "preferenceFragmentStyle", 24),
+    (0x01010507, "attr", "canControlMagnification", 24),
+    (0x01010508, "attr", "languageTag", 24),
+    (0x01010509, "attr", "pointerIcon", 24),
+    (0x0101050a, "attr", "tickMark", 24),
+    (0x0101050b, "attr", "tickMarkTint", 24),
+    (0x0101050c, "attr", "tickMarkTintMode", 24),
+    (0x0101050d, "attr", "canPerformGestures", 24),
+    (0x0101050e, "attr", "externalService", 24),
+    (0x0101050f, "attr", "supportsLocalInteraction", 24),
+    (0x01010510, "attr", "startX", 24),
+    (0x01010511, "attr", "startY", 24),
+    (0x01010512, "attr", "endX", 24),
+    (0x01010513, "attr", "endY", 24),
+    (0x01010514, "attr", "offset", 24),
+    (0x01010515, "attr", "use32bitAbi", 24),
+    (0x01010516, "attr", "bitmap", 24),
+    (0x01010517, "attr", "hotSpotX", 24),
+    (0x01010518, "attr", "hotSpotY", 24),
+    (0x01010519, "attr", "version", 24),
+    (0x0101051a, "attr", "backupInForeground", 24),
+    (0x0101051b, "attr", "countDown", 24),
+    (0x0101051c, "attr", "canRecord", 24),
+    (0x0101051d, "attr", "tunerCount", 24),
+    (0x0101051e, "attr", "fillType", 24),
+    (0x0101051f, "attr", "popupEnterTransition", 24),
+    (0x01010520, "attr", "popupExitTransition", 24),
+    (0x01010521, "attr", "forceHasOverlappingRendering", 24),
+    (0x01010522, "attr", "contentInsetStartWithNavigation", 24),
+    (0x01010523, "attr", "contentInsetEndWithActions", 24),
+    (0x01010524, "attr", "numberPickerStyle", 24),
+    (0x01010525, "attr", "enableVrMode", 24),
+    (0x01010527, "attr", "networkSecurityConfig", 24),
+    (0x01010528, "attr", "shortcutId", 25),
+    (0x01010529, "attr", "shortcutShortLabel", 25),
+    (0x0101052a, "attr", "shortcutLongLabel", 25),
+    (0x0101052b, "attr", "shortcutDisabledMessage", 25),
+    (0x0101052c, "attr", "roundIcon", 25),
+    (0x0101052d, "attr", "contextUri", 25),
+    (0x0101052e, "attr", "contextDescription", 25),
+    (0x0101052f, "attr", "showMetadataInPreview", 25),
+    (0x01010530, "attr", "colorSecondary", 25),
+    (0x01010572, "attr", "compileSdkVersion", 28),
+    (0x01010573, "attr", "compileSdkVersionCodename", 28),
+    (0x0101057a, "attr", "appComponentFactory", 28),
+    (0x01010603, "attr", "requestLegacyExternalStorage", 29),
+    (0x01020000, "id", "background", 1),
+    (0x01020001, "id", "checkbox", 1),
+    (0x01020002, "id", "content", 1),
+    (0x01020003, "id", "edit", 1),
+    (0x01020004, "id", "empty", 1),
+    (0x01020005, "id", "hint", 1),
+    (0x01020006, "id", "icon", 1),
+    (0x01020007, "id", "icon1", 1),
+    (0x01020008, "id", "icon2", 1),
+    (0x01020009, "id", "input", 1),
+    (0x0102000a, "id", "list", 1),
+    (0x0102000b, "id", "message", 1),
+    (0x0102000c, "id", "primary", 1),
+    (0x0102000d, "id", "progress", 1),
+    (0x0102000e, "id", "selectedIcon", 1),
+    (0x0102000f, "id", "secondaryProgress", 1),
+    (0x01020010, "id", "summary", 1),
+    (0x01020011, "id", "tabcontent", 1),
+    (0x01020012, "id", "tabhost", 1),
+    (0x01020013, "id", "tabs", 1),
+    (0x01020014, "id", "text1", 1),
+    (0x01020015, "id", "text2", 1),
+    (0x01020016, "id", "title", 1),
+    (0x01020017, "id", "toggle", 1),
+    (0x01020018, "id", "widget_frame", 1),
+    (0x01020019, "id", "button1", 1),
+    (0x0102001a, "id", "button2", 1),
+    (0x0102001b, "id", "button3", 1),
+    (0x0102002c, "id", "home", 11),
+    (0x01030000, "style", "Animation", 1),
+    (0x01030001, "style", "Animation.Activity", 1),
+    (0x01030002, "style", "Animation.Dialog", 1),
+    (0x01030003, "style", "Animation.Translucent", 1),
+    (0x01030004, "style", "Animation.Toast", 1),
+    (0x01030005, "style", "Theme", 1),
+    (0x01030006, "style", "Theme.NoTitleBar", 1),
+    (0x01030007, "style", "Theme.NoTitleBar.Fullscreen", 1),
+    (0x01030008, "style", "Theme.Black", 1),
+    (0x01030009, "style", "Theme.Black.NoTitleBar", 1),
+    (0x0103000a, "style", "Theme.Black.NoTitleBar.Fullscreen", 1),
+    (0x0103000b, "style", "Theme.Dialog", 1),
+    (0x0103000c, "style", "Theme.Light", 1),
+    (0x0103000d, "style", "Theme.Light.NoTitleBar", 1),
+    (0x0103000e, "style", "Theme.Light.NoTitleBar.Fullscreen", 1),
+    (0x0103000f, "style", "Theme.Translucent", 1),
+    (0x01030010, "style", "Theme.Translucent.NoTitleBar", 1),
+    (0x01030011, "style", "Theme.Translucent.NoTitleBar.Fullscreen", 1),
+    (0x01030055, "style", "Theme.NoDisplay", 3),
+    (0x0103006b, "style", "Theme.Holo", 11),
+    (0x0103006e, "style", "Theme.Holo.Light", 11),
+    (0x01030128, "style", "Theme.DeviceDefault", 14),
+    (0x01030224, "style", "Theme.Material", 21),
+    (0x01030237, "style", "Theme.Material.Light", 21),
+    (0x01040000, "string", "cancel", 1),
+    (0x01040009, "string", "no", 1),
+    (0x0104000a, "string", "ok", 1),
+    (0x01040013, "string", "yes", 1),
+    (0x01050000, "dimen", "app_icon_size", 1),
+    (0x0106000b, "color", "white", 1),
+    (0x0106000c, "color", "black", 1),
+    (0x0106000d, "color", "transparent", 1),
+    (0x01080027, "drawable", "ic_dialog_alert", 1),
+    (0x01080093, "drawable", "sym_def_app_icon", 1),
+];
+
+/* Names and values of the enum or flag values of an attribute */
+pub(crate) type AttributeValues = &'static [(&'static str, u32)];
+
+/* Formats of the public attributes of the Android framework, sorted by ID:
+ * (ID, types of values accepted as ResTable_map type bits, enum or flag
+ * values). Only the attributes used in manifests are listed until the table
+ * is regenerated. */
+pub(crate) static FRAMEWORK_ATTRIBUTES: &[(u32, u32, AttributeValues)] = &[
+    (0x01010000, 0x1, &[]),
+    (0x01010001, 0x3, &[]),
+    (0x01010002, 0x1, &[]),
+    (0x01010003, 0x2, &[]),
+    (0x01010004, 0x2, &[]),
+    (0x01010005, 0x8, &[]),
+    (0x01010006, 0x2, &[]),
+    (0x01010007, 0x2, &[]),
+    (0x01010008, 0x2, &[]),
+    (0x01010009, 0x20000, &[("normal", 0x0), ("dangerous", 0x1), ("signature", 0x2), ("signatureOrSystem", 0x3), ("privileged", 0x10), ("system", 0x10), ("development", 0x20), ("appop", 0x40), ("pre23", 0x80), ("installer", 0x100), ("verifier", 0x200), ("preinstalled", 0x400), ("setup", 0x800), ("instant", 0x1000), ("runtime", 0x2000), ("oem", 0x4000), ("vendorPrivileged", 0x8000), ("textClassifier", 0x10000)]),
+    (0x0101000a, 0x2, &[]),
+    (0x0101000b, 0x2, &[]),
+    (0x0101000c, 0x8, &[]),
+    (0x0101000d, 0x8, &[]),
+    (0x0101000e, 0x8, &[]),
+    (0x0101000f, 0x8, &[]),
+    (0x01010010, 0x8, &[]),
+    (0x01010011, 0x2, &[]),
+    (0x01010012, 0x2, &[]),
+    (0x01010013, 0x8, &[]),
+    (0x01010014, 0x8, &[]),
+    (0x01010015, 0x8, &[]),
+    (0x01010016, 0x8, &[]),
+    (0x01010017, 0x8, &[]),
+    (0x01010018, 0x2, &[]),
+    (0x01010019, 0x8, &[]),
+    (0x0101001a, 0x4, &[]),
+    (0x0101001b, 0x8, &[]),
+    (0x0101001c, 0x4, &[]),
+    (0x0101001d, 0x10000, &[("standard", 0x0), ("singleTop", 0x1), ("singleTask", 0x2), ("singleInstance", 0x3), ("singleInstancePerTask", 0x4)]),
+    (0x0101001e, 0x10000, &[("unspecified", 0xffffffff), ("landscape", 0x0), ("portrait", 0x1), ("user", 0x2), ("behind", 0x3), ("sensor", 0x4), ("nosensor", 0x5), ("sensorLandscape", 0x6), ("sensorPortrait", 0x7), ("reverseLandscape", 0x8), ("reversePortrait", 0x9), ("fullSensor", 0xa), ("userLandscape", 0xb), ("userPortrait", 0xc), ("fullUser", 0xd), ("locked", 0xe)]),
+    (0x0101001f, 0x20000, &[("mcc", 0x1), ("mnc", 0x2), ("locale", 0x4), ("touchscreen", 0x8), ("keyboard", 0x10), ("keyboardHidden", 0x20), ("navigation", 0x40), ("orientation", 0x80), ("screenLayout", 0x100), ("uiMode", 0x200), ("screenSize", 0x400), ("smallestScreenSize", 0x800), ("density", 0x1000), ("layoutDirection", 0x2000), ("colorMode", 0x4000), ("grammaticalGender", 0x8000), ("fontWeightAdjustment", 0x10000000), ("fontScale", 0x40000000)]),
+    (0x01010020, 0x3, &[]),
+    (0x01010021, 0x2, &[]),
+    (0x01010022, 0x8, &[]),
+    (0x01010023, 0x8, &[]),
+    (0x01010025, 0x1, &[]),
+    (0x01010026, 0x2, &[]),
+    (0x01010027, 0x2, &[]),
+    (0x01010028, 0x2, &[]),
+    (0x01010029, 0x2, &[]),
+    (0x0101002a, 0x2, &[]),
+    (0x0101002b, 0x2, &[]),
+    (0x0101002c, 0x2, &[]),
+    (0x01010202, 0x2, &[]),
+    (0x01010203, 0x8, &[]),
+    (0x01010204, 0x8, &[]),
+    (0x0101020c, 0x6, &[]),
+    (0x0101021b, 0x4, &[]),
+    (0x0101021c, 0x2, &[]),
+    (0x0101022b, 0x20000, &[("stateUnspecified", 0x0), ("stateUnchanged", 0x1), ("stateHidden", 0x2), ("stateAlwaysHidden", 0x3), ("stateVisible", 0x4), ("stateAlwaysVisible", 0x5), ("adjustUnspecified", 0x0), ("adjustResize", 0x10), ("adjustPan", 0x20), ("adjustNothing", 0x30)]),
+    (0x0101022d, 0x8, &[]),
+    (0x01010261, 0x1, &[]),
+    (0x01010270, 0x6, &[]),
+    (0x01010271, 0x4, &[]),
+    (0x01010272, 0x8, &[]),
+    (0x0101027f, 0x2, &[]),
+    (0x01010280, 0x8, &[]),
+    (0x01010281, 0x4, &[]),
+    (0x0101028e, 0x8, &[]),
+    (0x0101029c, 0x8, &[]),
+    (0x010102b7, 0x10000, &[("auto", 0x0), ("internalOnly", 0x1), ("preferExternal", 0x2)]),
+    (0x010102b8, 0x8, &[]),
+    (0x010102ba, 0x8, &[]),
+    (0x010102be, 0x1, &[]),
+    (0x010102c0, 0x8, &[]),
+    (0x010102d3, 0x8, &[]),
+    (0x0101035a, 0x8, &[]),
+    (0x01010398, 0x20000, &[("none", 0x0), ("splitActionBarWhenNarrow", 0x1)]),
+    (0x010103a7, 0x2, &[]),
+    (0x010103a9, 0x8, &[]),
+    (0x010103af, 0x8, &[]),
+    (0x010103f2, 0x1, &[]),
+    (0x01010445, 0x10000, &[("none", 0x0), ("intoExisting", 0x1), ("always", 0x2), ("never", 0x3)]),
+    (0x01010446, 0x4, &[]),
+    (0x01010447, 0x8, &[]),
+    (0x010104ea, 0x8, &[]),
+    (0x010104eb, 0x9, &[]),
+    (0x010104ec, 0x8, &[]),
+    (0x010104f6, 0x8, &[]),
+    (0x01010505, 0x8, &[]),
+    (0x01010527, 0x1, &[]),
+    (0x0101052c, 0x1, &[]),
+    (0x01010572, 0x4, &[]),
+    (0x01010573, 0x2, &[]),
+    (0x0101057a, 0x2, &[]),
+    (0x01010603, 0x8, &[]),
+];
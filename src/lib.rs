@@ -7,25 +7,87 @@ pub mod resource_map;
 pub mod data_value_type;
 pub mod res_value;
 pub mod res_table;
+pub mod error;
+pub mod chunk_walker;
+pub mod axml_reader;
+pub mod axml_document;
+pub mod res_resolver;
+pub mod res_config;
+pub mod framework_res;
+pub mod namespaces;
+pub mod apk_bundle;
+pub mod proto_xml;
+pub mod axml_encoder;
+pub mod raw_chunk;
+mod framework_res_table;
+mod read_ext;
+mod write_ext;
+mod proto_reader;
+mod axml_writer;
 
-use std::{
-    fs,
-    collections::HashMap,
+use std::fs;
+use std::path::{
+    Component,
+    Path,
+    PathBuf,
 };
 use std::io::{
     Read,
+    Seek,
     Cursor,
 };
 use crate::cli::ArgType;
+use crate::apk_bundle::is_bundle;
+use crate::proto_xml::is_proto_xml;
 use crate::chunk_header::ChunkHeader;
-use crate::resource_map::ResourceMap;
-use crate::res_table::{
-    ResTable,
-    ResTablePackage
-};
-use crate::string_pool::StringPool;
+use crate::chunk_walker::UnknownChunk;
+use crate::res_table::ResTable;
 use crate::xml_types::XmlTypes;
 
+pub use crate::error::{
+    AxmlError,
+    Result,
+};
+pub use crate::axml_reader::{
+    AxmlEvent,
+    AxmlReader,
+};
+pub use crate::res_resolver::{
+    ResourceName,
+    ResourceResolver,
+};
+pub use crate::res_value::{
+    Color,
+    ResValue,
+};
+pub use crate::axml_document::{
+    AxmlDocument,
+    Element,
+    Node,
+    Text,
+};
+pub use crate::res_config::ResConfig;
+pub use crate::proto_xml::ProtoXmlReader;
+pub use crate::apk_bundle::{
+    ApkBundle,
+    SplitApk,
+};
+pub use crate::framework_res::{
+    framework_attribute,
+    framework_id,
+    framework_name,
+    FrameworkResources,
+};
+pub use crate::axml_encoder::{
+    encode_xml,
+    XmlEncoder,
+};
+pub use crate::raw_chunk::{
+    verify_roundtrip,
+    RawChunk,
+    RawDocument,
+};
+
 /// Representation of an app's manifest contents
 #[derive(Debug, Default)]
 pub struct ManifestContents {
@@ -41,40 +103,285 @@ pub struct ManifestContents {
     pub requested_perms: Vec<String>,
 
     pub main_entry_point: Option<String>,
+
+    /// Chunks that were skipped while decoding the manifest, either because
+    /// their type is unknown or because they cannot be decoded (yet)
+    pub unknown_chunks: Vec<UnknownChunk>,
+
+    /// Errors of the resource tables which could not be parsed, with their
+    /// path (the path of their APK in a bundle). References to their
+    /// resources are left as raw IDs.
+    pub resources_errors: Vec<(String, AxmlError)>,
+}
+
+/// Options of `parse_app_manifest_with` and `parse_apk_reader_with`
+#[derive(Debug, Clone, Copy, Default)]
+pub struct ManifestOptions {
+    /// Keep references to the app's resources as raw IDs (e.g.,
+    /// `@0x7f120034`) instead of resolving them with the APK's
+    /// `resources.arsc`
+    pub raw_ids: bool,
+}
+
+/* Signatures of a zip local file header, and of the end of central
+ * directory record which starts an empty archive */
+const ZIP_LOCAL_HEADER: &[u8] = b"PK\x03\x04";
+const ZIP_EMPTY_ARCHIVE: &[u8] = b"PK\x05\x06";
+
+/* Byte order mark which may precede a text XML declaration */
+const UTF8_BOM: &[u8] = b"\xef\xbb\xbf";
+
+/* Manifest of the base module of an Android App Bundle (`.aab`) */
+const AAB_MANIFEST: &str = "base/manifest/AndroidManifest.xml";
+
+/* Number of bytes read from a file to detect its type */
+const SNIFF_SIZE: u64 = 512;
+
+/// Detect the type of a file from its first bytes:
+///
+///   * a zip header for an APK (or a bundle of APKs),
+///   * a `RES_XML_TYPE` chunk header for binary XML,
+///   * a `RES_TABLE_TYPE` chunk header for a resource table,
+///   * an XML declaration for text XML,
+///   * the key of an element or of a text node for the protobuf XML of
+///     Android App Bundles, which is decoded like binary XML.
+///
+/// Returns `None` if the contents do not look like any of these.
+pub fn detect_file_type(header: &[u8]) -> Option<ArgType> {
+    if header.starts_with(ZIP_LOCAL_HEADER) || header.starts_with(ZIP_EMPTY_ARCHIVE) {
+        return Some(ArgType::Apk);
+    }
+
+    /* Chunk type, then header size */
+    if let [type_lo, type_hi, size_lo, size_hi, ..] = *header {
+        let chunk_type = u16::from_le_bytes([type_lo, type_hi]);
+        let header_size = u16::from_le_bytes([size_lo, size_hi]);
+
+        if chunk_type == XmlTypes::ResXmlType as u16 && header_size == 0x08 {
+            return Some(ArgType::Axml);
+        }
+        if chunk_type == XmlTypes::ResTableType as u16 && header_size == 0x0c {
+            return Some(ArgType::Arsc);
+        }
+    }
+
+    let text = header.strip_prefix(UTF8_BOM).unwrap_or(header);
+    let start = text.iter().position(|byte| !byte.is_ascii_whitespace()).unwrap_or(text.len());
+    if text[start..].starts_with(b"<?xml") {
+        return Some(ArgType::PlainXml);
+    }
+
+    if is_proto_xml(header) {
+        return Some(ArgType::Axml);
+    }
+
+    None
+}
+
+/// Infer the type of a file from its contents
+pub fn infer_arg_type(file_path: &str) -> Result<ArgType> {
+    let mut header = Vec::new();
+    fs::File::open(file_path)?.take(SNIFF_SIZE).read_to_end(&mut header)?;
+
+    detect_file_type(&header).ok_or_else(|| AxmlError::UnknownFileType(file_path.to_string()))
+}
+
+/// Read a file from an opened archive, `None` if there is no such file
+pub(crate) fn read_zip_entry<R: Read + Seek>(archive: &mut zip::ZipArchive<R>, entry_name: &str) -> Result<Option<Vec<u8>>> {
+    let mut raw_file = match archive.by_name(entry_name) {
+        Ok(file) => file,
+        Err(zip::result::ZipError::FileNotFound) => return Ok(None),
+        Err(e) => return Err(e.into()),
+    };
+
+    let mut contents = Vec::new();
+    raw_file.read_to_end(&mut contents)?;
+    Ok(Some(contents))
+}
+
+/// Read the manifest of an APK or, for an Android App Bundle, of its base
+/// module (which is a protobuf `XmlNode` rather than binary XML)
+fn read_manifest_entry<R: Read + Seek>(archive: &mut zip::ZipArchive<R>) -> Result<Vec<u8>> {
+    if let Some(manifest) = read_zip_entry(archive, "AndroidManifest.xml")? {
+        return Ok(manifest);
+    }
+
+    read_zip_entry(archive, AAB_MANIFEST)?.ok_or(AxmlError::MissingManifest)
+}
+
+/// XML files of an APK, as `(path in the APK, contents)` pairs: the
+/// manifest, and every XML resource under `res/` (layouts, drawables,
+/// navigation graphs, etc.). Entries whose path would escape the output
+/// directory when extracted (e.g., `../evil.xml`) are skipped.
+///
+/// For a bundle of split APKs, the files of each APK are given under a
+/// directory named after the APK (e.g., `base/AndroidManifest.xml`). For an
+/// Android App Bundle, these are the manifests and XML resources of every
+/// module (e.g., `base/manifest/AndroidManifest.xml`), as protobuf.
+pub fn read_apk_xml_files(file_path: &str) -> Result<Vec<(PathBuf, Vec<u8>)>> {
+    read_apk_entries(file_path, false)
+}
+
+/// Read the XML files of an APK, see `read_apk_xml_files`, and its
+/// resource table(s): every file this crate can decode
+pub fn read_apk_binary_files(file_path: &str) -> Result<Vec<(PathBuf, Vec<u8>)>> {
+    read_apk_entries(file_path, true)
+}
+
+fn read_apk_entries(file_path: &str, with_resources: bool) -> Result<Vec<(PathBuf, Vec<u8>)>> {
+    let zipfile = fs::File::open(file_path)?;
+    let mut archive = zip::ZipArchive::new(zipfile)?;
+    let mut xml_files = Vec::new();
+
+    if !is_bundle(&mut archive) {
+        read_xml_entries(&mut archive, Path::new(""), with_resources, &mut xml_files)?;
+        return Ok(xml_files);
+    }
+
+    for apk_name in apk_bundle::apk_names(&mut archive) {
+        let apk_dir = Path::new(apk_name.trim_end_matches(".apk"));
+        if !apk_dir.components().all(|component| matches!(component, Component::Normal(_))) {
+            continue;
+        }
+
+        let raw_apk = match read_zip_entry(&mut archive, &apk_name)? {
+            Some(raw_apk) => raw_apk,
+            None => continue,
+        };
+
+        /* Entries named like APKs which are not, as `ApkBundle` skips them */
+        let mut apk_archive = match zip::ZipArchive::new(Cursor::new(raw_apk)) {
+            Ok(apk_archive) => apk_archive,
+            Err(_) => continue,
+        };
+        read_xml_entries(&mut apk_archive, apk_dir, with_resources, &mut xml_files)?;
+    }
+
+    Ok(xml_files)
+}
+
+/// Read the XML files of an opened APK, see `read_apk_xml_files`, giving
+/// their path under `prefix`. The resource table is read as well if
+/// `with_resources` is set.
+fn read_xml_entries<R: Read + Seek>(archive: &mut zip::ZipArchive<R>,
+                                    prefix: &Path,
+                                    with_resources: bool,
+                                    xml_files: &mut Vec<(PathBuf, Vec<u8>)>) -> Result<()> {
+    let is_aab = archive.by_name(AAB_MANIFEST).is_ok();
+    for index in 0..archive.len() {
+        let mut raw_file = archive.by_index(index)?;
+        /* Files of Android App Bundles are in one directory per module */
+        let name = match raw_file.name().split_once('/') {
+            Some((_, module_path)) if is_aab => module_path,
+            _ => raw_file.name(),
+        };
+        let wanted = name == "AndroidManifest.xml"
+                     || name == "manifest/AndroidManifest.xml"
+                     || (name.starts_with("res/") && name.ends_with(".xml"))
+                     || (with_resources && name == "resources.arsc");
+        if !wanted || raw_file.is_dir() {
+            continue;
+        }
+
+        let entry_path = match raw_file.enclosed_name() {
+            Some(entry_path) => prefix.join(entry_path),
+            None => continue,
+        };
+
+        let mut contents = Vec::new();
+        raw_file.read_to_end(&mut contents)?;
+        xml_files.push((entry_path, contents));
+    }
+
+    Ok(())
 }
 
 /// Open the file, read the contents, and create a `Cursor` of the raw data
 /// for easier handling when parsing the XML data.
-fn create_cursor(file_path: &str) -> Cursor<Vec<u8>> {
+pub fn create_cursor(arg_type: ArgType, file_path: &str) -> Result<Cursor<Vec<u8>>> {
 
     let mut axml_cursor = Vec::new();
 
-    let arg_type = match file_path.split(".").last() {
-        Some("apk") => cli::ArgType::Apk,
-        Some("xml") => cli::ArgType::Axml,
-        Some("arsc") => cli::ArgType::Arsc,
-        _ => panic!("Cannot infer file type from path"),
-    };
-
-    if arg_type == cli::ArgType::Apk {
+    if arg_type == ArgType::Apk {
         // If we are dealing with an APK, we must first extract the binary XML from it
         // In this case we assume the user wants to decode the app manifest so we extract that
-
-        let zipfile = std::fs::File::open(file_path).unwrap();
-        let mut archive = zip::ZipArchive::new(zipfile).unwrap();
-        let mut raw_file = match archive.by_name("AndroidManifest.xml") {
-            Ok(file) => file,
-            Err(..) => {
-                panic!("Error: no AndroidManifest.xml in APK");
-            }
+        // For split APKs, this is the manifest of the base APK
+        let zipfile = fs::File::open(file_path)?;
+        let mut archive = zip::ZipArchive::new(zipfile)?;
+        axml_cursor = match is_bundle(&mut archive) {
+            true => ApkBundle::from_archive(&mut archive)?.base.manifest,
+            false => read_manifest_entry(&mut archive)?,
         };
-        raw_file.read_to_end(&mut axml_cursor).expect("Error: cannot read manifest from app");
     } else {
-        let mut raw_file = fs::File::open(file_path).expect("Error: cannot open AXML file");
-        raw_file.read_to_end(&mut axml_cursor).expect("Error: cannot read AXML file");
+        let mut raw_file = fs::File::open(file_path)?;
+        raw_file.read_to_end(&mut axml_cursor)?;
     }
 
-    Cursor::new(axml_cursor)
+    Ok(Cursor::new(axml_cursor))
+}
+
+/// Load the resource table of an APK, which is used to resolve references
+/// to the app's resources. Returns `None` for other types of files, and for
+/// APKs without a `resources.arsc`. For split APKs, the tables of the base
+/// APK and of its configuration splits are merged, leaving out the ones
+/// which cannot be parsed (see `ApkBundle::resources_errors`). Fails if the
+/// table of a single APK cannot be parsed: callers which can do without it
+/// should carry on with `None`, as `parse_apk_reader` does.
+pub fn load_resources(arg_type: ArgType, file_path: &str) -> Result<Option<ResTable>> {
+    if arg_type != ArgType::Apk {
+        return Ok(None);
+    }
+
+    let zipfile = fs::File::open(file_path)?;
+    let mut archive = zip::ZipArchive::new(zipfile)?;
+    if is_bundle(&mut archive) {
+        return Ok(ApkBundle::from_archive(&mut archive)?.resources);
+    }
+
+    let raw_table = match read_zip_entry(&mut archive, "resources.arsc")? {
+        Some(raw_table) => raw_table,
+        None => return Ok(None),
+    };
+
+    Ok(Some(parse_res_table(Cursor::new(raw_table))?))
+}
+
+/// Load the resource table of the Android framework, from the `android.jar`
+/// of an SDK platform or from the `resources.arsc` extracted from it, e.g.,
+/// for `XmlEncoder::with_framework`
+pub fn load_framework(file_path: &str) -> Result<ResTable> {
+    match infer_arg_type(file_path)? {
+        ArgType::Arsc => parse_arsc_reader(fs::File::open(file_path)?),
+        arg_type => load_resources(arg_type, file_path)?.ok_or_else(|| AxmlError::MissingResources(file_path.to_string())),
+    }
+}
+
+/// Parse a resource table, starting with its chunk type
+pub(crate) fn parse_res_table<T: AsRef<[u8]>>(mut table_cursor: Cursor<T>) -> Result<ResTable> {
+    XmlTypes::parse_block_type(&mut table_cursor)?;
+    ResTable::parse(&mut table_cursor)
+}
+
+/// Parse a binary XML document read from any source (e.g., a file, or an
+/// entry of an archive opened by the caller).
+///
+/// Decoding works on bytes in memory, borrowing the strings of the string
+/// pool, so the whole document is read first. Use `AxmlReader::from_bytes`
+/// to decode a document which is already in memory without copying it.
+pub fn parse_axml_reader<R: Read>(mut axml_reader: R) -> Result<AxmlDocument> {
+    let mut axml_bytes = Vec::new();
+    axml_reader.read_to_end(&mut axml_bytes)?;
+
+    AxmlDocument::from_bytes(&axml_bytes)
+}
+
+/// Parse a resource table (`resources.arsc`) read from any source. As for
+/// `parse_axml_reader`, the whole table is read in memory first.
+pub fn parse_arsc_reader<R: Read>(mut arsc_reader: R) -> Result<ResTable> {
+    let mut arsc_bytes = Vec::new();
+    arsc_reader.read_to_end(&mut arsc_bytes)?;
+
+    parse_res_table(Cursor::new(arsc_bytes))
 }
 
 /// Parse an app's manifest and extract interesting contents
@@ -86,90 +393,142 @@ fn create_cursor(file_path: &str) -> Cursor<Vec<u8>> {
 ///   * list of services names
 ///   * list of content providers names
 ///   * list of broadcast receiver names
-fn get_manifest_contents(mut axml_cursor: Cursor<Vec<u8>>) -> ManifestContents {
+///
+/// If the app's resource table is given, references to string, bool and
+/// integer resources are replaced by their value. The manifest can be binary
+/// XML, or a protobuf `XmlNode` as found in Android App Bundles.
+fn get_manifest_contents(axml_bytes: &[u8], resources: Option<&ResTable>) -> Result<ManifestContents> {
     let mut contents = ManifestContents::default();
 
-    let mut global_strings = Vec::new();
-    let mut namespace_prefixes = HashMap::<String, String>::new();
-    // let mut writer = Vec::new();
-
-    loop {
-        if let Ok(block_type) = XmlTypes::parse_block_type(&mut axml_cursor) {
-            match block_type {
-                XmlTypes::ResNullType => continue,
-                XmlTypes::ResStringPoolType => {
-                    let _ = StringPool::from_buff(&mut axml_cursor, &mut global_strings);
-                },
-                XmlTypes::ResTableType => {
-                    let _ = ResTable::parse(&mut axml_cursor);
-                },
-                XmlTypes::ResXmlType => {
-                    axml_cursor.set_position(axml_cursor.position() - 2);
-                    let _ = ChunkHeader::from_buff(&mut axml_cursor, XmlTypes::ResXmlType);
-                },
-                XmlTypes::ResXmlStartNamespaceType => {
-                    parser::parse_start_namespace(&mut axml_cursor, &global_strings, &mut namespace_prefixes);
-                },
-                XmlTypes::ResXmlEndNamespaceType => {
-                    parser::parse_end_namespace(&mut axml_cursor, &global_strings);
-                },
-                XmlTypes::ResXmlStartElementType => {
-                    let (element_type, attrs) = parser::parse_start_element(&mut axml_cursor, &global_strings, &namespace_prefixes).unwrap();
-
-                    // Get element name from the attributes
-                    // We only care about package name, activites, services, content providers and
-                    // broadcast receivers which all have their name in the "android" namespace
-                    let mut element_name = String::new();
-
-                    for (attr_key, attr_val) in attrs.iter() {
-                        if attr_key == "android:name" {
-                            element_name = attr_val.to_string();
-                            break;
-                        }
-                    }
+    let events: Box<dyn Iterator<Item = Result<AxmlEvent>>> = match is_proto_xml(axml_bytes) {
+        true => {
+            let mut reader = ProtoXmlReader::from_bytes(axml_bytes);
+            if let Some(resources) = resources {
+                reader = reader.with_resources(resources);
+            }
+            Box::new(reader)
+        },
+        false => {
+            /* Anything else than binary XML (e.g., a resource table) would
+             * only give unknown chunks, and thus an empty manifest */
+            ChunkHeader::from_buff(&mut Cursor::new(axml_bytes), XmlTypes::ResXmlType)?;
 
-                    match element_type.as_str() {
-                        "activity" => contents.activities.push(element_name),
-                        "service"  => contents.services.push(element_name),
-                        "provider" => contents.providers.push(element_name),
-                        "receiver" => contents.receivers.push(element_name),
-                        "permission" => contents.created_perms.push(element_name),
-                        "uses-permission" => contents.requested_perms.push(element_name),
-                        "action" if element_name == "android.intent.action.MAIN" => contents.main_entry_point = contents.activities.last().cloned(),
-                        _ => { }
+            let mut reader = AxmlReader::from_bytes(axml_bytes);
+            if let Some(resources) = resources {
+                reader = reader.with_resources(resources);
+            }
+            Box::new(reader)
+        },
+    };
+
+    for event in events {
+        match event? {
+            AxmlEvent::StartElement { name: element_type, attrs, .. } => {
+                // Get element name from the attributes
+                // We only care about package name, activites, services, content providers and
+                // broadcast receivers which all have their name in the "android" namespace
+                let mut element_name = String::new();
+
+                for attr in attrs.iter() {
+                    if attr.qualified_name() == "android:name" {
+                        element_name = attr.value.to_string();
+                        break;
                     }
+                }
 
-                    // Package name is in the "manifest" element and with the "package" key
-                    if element_type == "manifest" {
-                        for (attr_key, attr_val) in attrs.iter() {
-                            if attr_key == "package" {
-                                contents.pkg_name = attr_val.to_string();
-                                break;
-                            }
+                match element_type.as_str() {
+                    "activity" => contents.activities.push(element_name),
+                    "service"  => contents.services.push(element_name),
+                    "provider" => contents.providers.push(element_name),
+                    "receiver" => contents.receivers.push(element_name),
+                    "permission" => contents.created_perms.push(element_name),
+                    "uses-permission" => contents.requested_perms.push(element_name),
+                    "action" if element_name == "android.intent.action.MAIN" => contents.main_entry_point = contents.activities.last().cloned(),
+                    _ => { }
+                }
+
+                // Package name is in the "manifest" element and with the "package" key
+                if element_type == "manifest" {
+                    for attr in attrs.iter() {
+                        if attr.qualified_name() == "package" {
+                            contents.pkg_name = attr.value.to_string();
+                            break;
                         }
                     }
-                },
-                XmlTypes::ResXmlEndElementType => {
-                    parser::parse_end_element(&mut axml_cursor, &global_strings).unwrap();
-                },
+                }
+            },
+            AxmlEvent::UnknownChunk(chunk) => contents.unknown_chunks.push(chunk),
+            _ => { },
+        }
+    }
 
-                XmlTypes::ResXmlResourceMapType => {
-                    let _ = ResourceMap::from_buff(&mut axml_cursor);
-                },
+    Ok(contents)
+}
 
-                _ => { },
-            }
-        }
-        else  {
-            break;
-        }
+/// Convenience function to parse the manifest of an APK. References to the
+/// app's resources are resolved with its resource table, see
+/// `parse_app_manifest_with` to keep them as raw IDs.
+pub fn parse_app_manifest(file_path: &str) -> Result<ManifestContents> {
+    parse_app_manifest_with(file_path, &ManifestOptions::default())
+}
+
+/// Parse the manifest of an APK (or a binary XML manifest) with the given
+/// options
+pub fn parse_app_manifest_with(file_path: &str, options: &ManifestOptions) -> Result<ManifestContents> {
+    let arg_type = infer_arg_type(file_path)?;
+    match arg_type {
+        ArgType::Apk => return parse_apk_reader_with(fs::File::open(file_path)?, options),
+        ArgType::PlainXml => return Err(AxmlError::PlainTextXml(file_path.to_string())),
+        ArgType::Arsc => return Err(AxmlError::NotAManifest(file_path.to_string())),
+        _ => { },
     }
 
-    contents
+    let cursor = create_cursor(arg_type, file_path)?;
+    get_manifest_contents(cursor.get_ref(), None)
 }
 
-/// Convenience function to parse the manifest of an APK
-pub fn parse_app_manifest(file_path: &str) -> ManifestContents {
-    let cursor = create_cursor(file_path);
-    get_manifest_contents(cursor)
+/// Parse a binary XML manifest which is already in memory (e.g., extracted
+/// from an APK by the caller), or the protobuf manifest of an Android App
+/// Bundle. References to resources are left as is, since the resource table
+/// is not available.
+pub fn parse_manifest_bytes(axml_bytes: &[u8]) -> Result<ManifestContents> {
+    get_manifest_contents(axml_bytes, None)
+}
+
+/// Parse the manifest of an APK read from any seekable source (e.g., an
+/// APK held in memory, or nested in another archive). As with
+/// `parse_app_manifest`, the APK's resource table is used to resolve
+/// references when there is one. Bundles of split APKs are accepted too, in
+/// which case the manifest of the base APK is parsed, and so are Android App
+/// Bundles, in which case the manifest of the base module is parsed.
+pub fn parse_apk_reader<R: Read + Seek>(apk_reader: R) -> Result<ManifestContents> {
+    parse_apk_reader_with(apk_reader, &ManifestOptions::default())
+}
+
+/// Parse the manifest of an APK read from any seekable source, see
+/// `parse_apk_reader`, with the given options
+pub fn parse_apk_reader_with<R: Read + Seek>(apk_reader: R, options: &ManifestOptions) -> Result<ManifestContents> {
+    let mut archive = zip::ZipArchive::new(apk_reader)?;
+    if is_bundle(&mut archive) {
+        let bundle = ApkBundle::from_archive(&mut archive)?;
+        let resources = bundle.resources.as_ref().filter(|_| !options.raw_ids);
+        let mut contents = get_manifest_contents(&bundle.base.manifest, resources)?;
+        contents.resources_errors = bundle.resources_errors;
+        return Ok(contents);
+    }
+    let raw_manifest = read_manifest_entry(&mut archive)?;
+
+    /* A resource table which cannot be parsed only leaves the references
+     * unresolved */
+    let mut resources_errors = Vec::new();
+    let resources = match read_zip_entry(&mut archive, "resources.arsc")? {
+        Some(raw_table) if !options.raw_ids => parse_res_table(Cursor::new(raw_table))
+                                                   .map_err(|e| resources_errors.push(("resources.arsc".to_string(), e)))
+                                                   .ok(),
+        _ => None,
+    };
+
+    let mut contents = get_manifest_contents(&raw_manifest, resources.as_ref())?;
+    contents.resources_errors = resources_errors;
+    Ok(contents)
 }
@@ -1,8 +1,9 @@
 #![allow(non_snake_case, unused_variables, dead_code)]
 
-use std::{
-    fs,
-    collections::HashMap,
+use std::fs;
+use std::path::{
+    Path,
+    PathBuf,
 };
 use std::io::{
     Write,
@@ -11,100 +12,291 @@ use std::io::{
 
 use quick_xml::Writer;
 
-use axml_parser::create_cursor;
-use axml_parser::chunk_header::ChunkHeader;
-use axml_parser::resource_map::ResourceMap;
+use axml_parser::{
+    create_cursor,
+    detect_file_type,
+    infer_arg_type,
+    load_framework,
+    load_resources,
+    read_apk_binary_files,
+    read_apk_xml_files,
+    verify_roundtrip,
+};
 use axml_parser::res_table::{
     ResTable,
-    ResTablePackage
+    ResTableEntryValue,
+};
+use axml_parser::res_resolver::{
+    self,
+    ResourceResolver,
 };
-use axml_parser::string_pool::StringPool;
 use axml_parser::xml_types::XmlTypes;
+use axml_parser::{
+    AxmlEvent,
+    AxmlReader,
+    FrameworkResources,
+    ProtoXmlReader,
+    XmlEncoder,
+};
+use axml_parser::proto_xml::is_proto_xml;
 use axml_parser::parser;
 use axml_parser::cli;
+use axml_parser::{
+    AxmlError,
+    Result,
+};
 
 fn main() {
     // Check CLI arguments
     let args = cli::parse_args();
 
-    // Check the file type
-    let arg_type = args.get_arg_type();
+    if let Err(e) = run(args) {
+        eprintln!("Error: {e}");
+        std::process::exit(1);
+    }
+}
+
+fn run(args: cli::Args) -> Result<()> {
+
+    // Check the file type, looking at its contents if it was not given
     let arg_path = args.get_arg_path();
 
+    // Compiling text XML needs no decoding, `--encode` requires `--output`
+    if let (true, Some(output)) = (args.encode, &args.output) {
+        let text = fs::read_to_string(&arg_path)?;
+        let framework = args.framework.as_deref().map(load_framework).transpose()?;
+
+        let mut encoder = XmlEncoder::new();
+        if let Some(framework) = framework.as_ref() {
+            encoder = encoder.with_framework(framework);
+        }
+        fs::write(output, encoder.encode(&text)?)?;
+        return Ok(());
+    }
+
+    let arg_type = match args.get_arg_type() {
+        Some(arg_type) => arg_type,
+        None => infer_arg_type(&arg_path)?,
+    };
+
+    if args.verify_roundtrip {
+        return verify_files(arg_type, &arg_path);
+    }
+
+    if args.all_xml && arg_type != cli::ArgType::Apk {
+        return Err(AxmlError::NotAnApk(arg_path));
+    }
+
+    // Load the APK's resources to resolve references, unless asked not to.
+    // A resource table which cannot be parsed only leaves them unresolved.
+    let resources = match args.raw_ids {
+        true => None,
+        false => load_resources(arg_type, &arg_path).unwrap_or_else(|e| {
+            eprintln!("Warning: cannot load the resources of {}, references are left as IDs: {}", arg_path, e);
+            None
+        }),
+    };
+
+    if args.all_xml {
+        let output_dir = args.output.as_deref().unwrap_or_else(|| Path::new("."));
+        return decode_apk_xml(&arg_path, output_dir, resources.as_ref());
+    }
+
     // Create cursor over input file contents
-    let mut axml_cursor = create_cursor(arg_type, &arg_path);
+    let axml_cursor = create_cursor(arg_type, &arg_path)?;
+
+    let str_result = match arg_type {
+        cli::ArgType::Arsc => dump_resources(axml_cursor)?,
+        cli::ArgType::PlainXml => String::from_utf8_lossy(axml_cursor.get_ref()).into_owned(),
+        _ => decode_xml(axml_cursor, resources.as_ref())?,
+    };
+
+    if let Some(output) = args.output {
+        let mut file = fs::File::create(output)?;
+        file.write_all(str_result.as_bytes())?;
+    } else {
+        println!("{str_result}");
+    }
+
+    Ok(())
+}
+
 
-    let mut global_strings = Vec::new();
-    let mut namespace_prefixes = HashMap::<String, String>::new();
+/// Decode a binary XML document into text XML
+fn decode_xml(axml_cursor: Cursor<Vec<u8>>, resources: Option<&ResTable>) -> Result<String> {
     let mut writer = Writer::new_with_indent(Cursor::new(Vec::new()), b' ', 2);
 
-    // Now parsing the rest of the file
-    loop {
-        let block_type = XmlTypes::parse_block_type(&mut axml_cursor);
-        let block_type = match block_type {
-            Ok(block) => block,
-            Err(e) => break,
-        };
+    /* Name the references with the app's resources, then the framework's */
+    let mut resolvers = Vec::<&dyn ResourceResolver>::new();
+    if let Some(resources) = resources {
+        resolvers.push(resources);
+    }
+    resolvers.push(&FrameworkResources);
 
-        match block_type {
-            XmlTypes::ResNullType => continue,
-            XmlTypes::ResStringPoolType => {
-                let _foo = StringPool::from_buff(&mut axml_cursor, &mut global_strings)
-                                       .expect("Error: cannot parse string pool header");
-            },
-            XmlTypes::ResTableType => {
-                ResTable::parse(&mut axml_cursor); // .expect("Error: cannot parse resource table");
-            },
-            XmlTypes::ResXmlType => {
-                // TODO: should we do something more here?
-                /* Go back 2 bytes, to account from the block type */
-                let initial_offset = axml_cursor.position();
-                axml_cursor.set_position(initial_offset - 2);
-
-                let _ = ChunkHeader::from_buff(&mut axml_cursor, XmlTypes::ResXmlType)
-                        .expect("Error: cannot parse AXML header");
-            },
-            XmlTypes::ResXmlStartNamespaceType => {
-                parser::parse_start_namespace(&mut axml_cursor, &global_strings, &mut namespace_prefixes);
+    /* The XML files of Android App Bundles are protobuf, not binary XML */
+    let axml_bytes = axml_cursor.get_ref();
+    let reader: Box<dyn Iterator<Item = Result<AxmlEvent>>> = match is_proto_xml(axml_bytes) {
+        true => {
+            let mut reader = ProtoXmlReader::from_bytes(axml_bytes);
+            if let Some(resources) = resources {
+                reader = reader.with_resources(resources);
+            }
+            Box::new(reader.with_resolver(&resolvers))
+        },
+        false => {
+            let mut reader = AxmlReader::from_bytes(axml_bytes);
+            if let Some(resources) = resources {
+                reader = reader.with_resources(resources);
+            }
+            Box::new(reader.with_resolver(&resolvers))
+        },
+    };
+
+    for event in reader {
+        match event? {
+            AxmlEvent::StartElement { name, prefix, namespaces, attrs, line, .. } => {
+                for attr in attrs.iter() {
+                    if let (Some(pool_name), Some(id)) = (&attr.pool_name, attr.resource_id) {
+                        eprintln!("Warning: attribute {:#010x} is named \"{}\" in the string pool instead of \"{}\" (line {})",
+                                  id, pool_name, attr.name, line);
+                    }
+                }
+                let name = parser::qualified_name(prefix.as_deref(), &name);
+                parser::handle_event(&mut writer, name, attrs, &namespaces, XmlTypes::ResXmlStartElementType)?;
             },
-            XmlTypes::ResXmlEndNamespaceType => {
-                parser::parse_end_namespace(&mut axml_cursor, &global_strings);
+            AxmlEvent::EndElement { name, prefix, .. } => {
+                let name = parser::qualified_name(prefix.as_deref(), &name);
+                parser::handle_event(&mut writer, name, Vec::new(), &[], XmlTypes::ResXmlEndElementType)?;
             },
-            XmlTypes::ResXmlStartElementType => {
-                let (element_name, attrs) = parser::parse_start_element(&mut axml_cursor, &global_strings, &namespace_prefixes).unwrap();
-                parser::handle_event(&mut writer, element_name, attrs, &namespace_prefixes, XmlTypes::ResXmlStartElementType);
+            AxmlEvent::CData { text, .. } => {
+                parser::handle_text(&mut writer, &text)?;
             },
-            XmlTypes::ResXmlEndElementType => {
-                let element_name = parser::parse_end_element(&mut axml_cursor, &global_strings).unwrap();
-                parser::handle_event(&mut writer, element_name, Vec::new(), &namespace_prefixes, XmlTypes::ResXmlEndElementType);
+            AxmlEvent::UnknownChunk(chunk) => {
+                /* Chunks we cannot decode are skipped */
+                eprintln!("Warning: skipping chunk of type {:#06x} at offset {:#x} ({} bytes)",
+                          chunk.chunk_type, chunk.offset, chunk.data.len());
             },
-            XmlTypes::ResXmlCDataType => panic!("TODO: RES_XML_CDATA_TYPE"),
-            XmlTypes::ResXmlLastChunkType => panic!("TODO: RES_XML_LAST_CHUNK_TYPE"),
+            _ => { },
+        }
+    }
 
-            XmlTypes::ResXmlResourceMapType => {
-                let resource_map = ResourceMap::from_buff(&mut axml_cursor)
-                                                .expect("Error: cannot parse resource map");
+    let result = writer.into_inner().into_inner();
+    Ok(String::from_utf8_lossy(&result).into_owned())
+}
+
+/// Decode every XML file of an APK into `output_dir`, in the same tree as
+/// in the APK. Files which are already text XML are copied as is, and files
+/// which cannot be decoded are skipped with a warning.
+fn decode_apk_xml(apk_path: &str, output_dir: &Path, resources: Option<&ResTable>) -> Result<()> {
+    for (entry_path, contents) in read_apk_xml_files(apk_path)? {
+        let decoded = match detect_file_type(&contents) {
+            Some(cli::ArgType::Axml) => decode_xml(Cursor::new(contents), resources),
+            Some(cli::ArgType::PlainXml) => Ok(String::from_utf8_lossy(&contents).into_owned()),
+            _ => {
+                eprintln!("Warning: skipping {}, which is not an XML file", entry_path.display());
+                continue;
             },
+        };
 
-            XmlTypes::ResTablePackageType => {
-                let chunk = ResTablePackage::parse(&mut axml_cursor);
-                println!("chunk: {:#?}", chunk);
-                panic!("TODO: RES_TABLE_PACKAGE_TYPE");
+        let decoded = match decoded {
+            Ok(decoded) => decoded,
+            Err(e) => {
+                eprintln!("Warning: cannot decode {}: {}", entry_path.display(), e);
+                continue;
             },
-            XmlTypes::ResTableTypeType => panic!("TODO: RES_TABLE_TYPE_TYPE"),
-            XmlTypes::ResTableTypeSpecType => panic!("TODO: RES_TABLE_TYPE_SPEC_TYPE"),
-            XmlTypes::ResTableLibraryType => panic!("TODO: RES_TABLE_LIBRARY_TYPE"),
+        };
+
+        let output_path = output_dir.join(&entry_path);
+        if let Some(parent) = output_path.parent() {
+            fs::create_dir_all(parent)?;
         }
+        fs::write(output_path, decoded)?;
     }
 
-    let result = writer.into_inner().into_inner();
-    let str_result = String::from_utf8(result).unwrap();
+    Ok(())
+}
 
-    if args.output.is_some() {
-        let mut file = fs::File::create(&args.output.unwrap()).unwrap();
-        file.write_all(str_result.as_bytes()).unwrap();
-    } else {
-        println!("{str_result}");
+/// Check that every binary XML file and resource table of the input (the
+/// file itself, or the files of an APK) round-trips, listing the ones which
+/// do not
+fn verify_files(arg_type: cli::ArgType, path: &str) -> Result<()> {
+    let files = match arg_type {
+        cli::ArgType::Apk => read_apk_binary_files(path)?,
+        _ => vec![(PathBuf::from(path), fs::read(path)?)],
+    };
+
+    let mut verified = 0;
+    let mut failed = 0;
+    for (entry_path, contents) in files {
+        /* Protobuf XML has no chunks to compare */
+        if is_proto_xml(&contents)
+           || !matches!(detect_file_type(&contents), Some(cli::ArgType::Axml) | Some(cli::ArgType::Arsc)) {
+            continue;
+        }
+
+        verified += 1;
+        if let Err(e) = verify_roundtrip(&contents) {
+            eprintln!("{}: {}", entry_path.display(), e);
+            failed += 1;
+        }
+    }
+
+    println!("{} of {} files round-trip exactly", verified - failed, verified);
+    if failed > 0 {
+        return Err(AxmlError::RoundtripFailures { failed, total: verified });
     }
+
+    Ok(())
 }
 
+/// List every resource of a resource table, with its value in each configuration
+fn dump_resources(mut axml_cursor: Cursor<Vec<u8>>) -> Result<String> {
+    XmlTypes::parse_block_type(&mut axml_cursor)?;
+    let table = ResTable::parse(&mut axml_cursor)?;
+    let resolvers: Vec<&dyn ResourceResolver> = vec![&table, &FrameworkResources];
+    let mut output = String::new();
+
+    for package in table.packages.iter() {
+        output.push_str(&format!("package {:#04x} {}\n", package.id, package.name()));
+
+        for res_id in table.resource_ids() {
+            if res_id >> 24 != package.id {
+                continue;
+            }
+
+            let name = match table.resource_name(res_id) {
+                Some(name) => format!("{}/{}", name.type_name, name.entry),
+                None => "?".to_string(),
+            };
+            output.push_str(&format!("  {:#010x} {}\n", res_id, name));
+
+            for (res_type, entry) in table.entries(res_id) {
+                let config = match res_type.config.is_default() {
+                    true => "[default]".to_string(),
+                    false => format!("[{}]", res_type.config),
+                };
+                match &entry.value {
+                    ResTableEntryValue::Simple(value) => {
+                        output.push_str(&format!("    {} = {}\n", config, value.format(&table.strings, Some(&resolvers))));
+                    },
+                    ResTableEntryValue::Complex { parent, values } => {
+                        output.push_str(&format!("    {} parent={}\n", config,
+                                                 res_resolver::format_reference('@', *parent, Some(&resolvers))));
+                        for (key, value) in values.iter() {
+                            output.push_str(&format!("      {} = {}\n",
+                                                     res_resolver::format_reference('?', *key, Some(&resolvers)),
+                                                     value.format(&table.strings, Some(&resolvers))));
+                        }
+                    },
+                }
+            }
+        }
+    }
+
+    for chunk in table.unknown_chunks.iter() {
+        eprintln!("Warning: skipping chunk of type {:#06x} at offset {:#x} ({} bytes)",
+                  chunk.chunk_type, chunk.offset, chunk.data.len());
+    }
+
+    Ok(output)
+}
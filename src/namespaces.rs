@@ -0,0 +1,132 @@
+/* Conventional prefixes of well-known namespaces, used when a document
+ * refers to one of them without declaring it */
+const WELL_KNOWN_PREFIXES: [(&str, &str); 3] = [
+    ("http://schemas.android.com/apk/res/android", "android"),
+    ("http://schemas.android.com/apk/res-auto", "app"),
+    ("http://schemas.android.com/tools", "tools"),
+];
+
+/// Conventional prefix of a well-known namespace, e.g., `android`
+pub fn well_known_prefix(uri: &str) -> Option<&'static str> {
+    WELL_KNOWN_PREFIXES.iter()
+                       .find(|(known_uri, _)| *known_uri == uri)
+                       .map(|(_, prefix)| *prefix)
+}
+
+/**
+ * Namespace declarations in scope at a given point of a document.
+ *
+ * Declarations are pushed when a start namespace chunk is read, and popped
+ * at the matching end namespace chunk, so that an inner declaration can
+ * shadow an outer one for the same prefix. Declarations made since the last
+ * element are "pending": they belong on the next element, which is where
+ * they must appear in the text XML.
+ */
+#[derive(Debug, Clone, Default)]
+pub struct NamespaceStack {
+    /* `(prefix, uri)` bindings, the innermost one is last */
+    bindings: Vec<(String, String)>,
+
+    /* Number of bindings which were not attached to an element yet */
+    pending: usize,
+
+    /* Number of elements which are open */
+    depth: usize,
+
+    /* Bindings made up by `prefix_or_declare`, with the depth of the
+     * element they are declared on. They end with that element. */
+    synthetic: Vec<(usize, String, String)>,
+}
+
+impl NamespaceStack {
+    pub fn new() -> Self {
+        NamespaceStack::default()
+    }
+
+    /// Bind `prefix` to `uri` until the matching `pop`
+    pub fn push(&mut self, prefix: &str, uri: &str) {
+        self.bindings.push((prefix.to_string(), uri.to_string()));
+        self.pending += 1;
+    }
+
+    /// Remove the innermost binding of `prefix` to `uri`
+    pub fn pop(&mut self, prefix: &str, uri: &str) {
+        let position = self.bindings.iter()
+                                    .rposition(|(bound_prefix, bound_uri)| bound_prefix == prefix && bound_uri == uri);
+
+        if let Some(position) = position {
+            self.bindings.remove(position);
+            if position >= self.bindings.len() + 1 - self.pending {
+                self.pending -= 1;
+            }
+        }
+    }
+
+    /// Prefix bound to `uri` in the current scope, if any
+    pub fn prefix(&self, uri: &str) -> Option<&str> {
+        self.bindings.iter()
+                     .rev()
+                     .find(|(_, bound_uri)| bound_uri == uri)
+                     .map(|(prefix, _)| prefix.as_str())
+    }
+
+    /// URI bound to `prefix` in the current scope, if any
+    pub fn uri(&self, prefix: &str) -> Option<&str> {
+        self.bindings.iter()
+                     .rev()
+                     .find(|(bound_prefix, _)| bound_prefix == prefix)
+                     .map(|(_, uri)| uri.as_str())
+    }
+
+    /// Prefix bound to `uri`. If the URI was never declared, a prefix is
+    /// made up for it (its conventional prefix if it is a well-known
+    /// namespace, `ns0`, `ns1`, etc. otherwise) and declared on the next
+    /// element.
+    pub fn prefix_or_declare(&mut self, uri: &str) -> String {
+        if let Some(prefix) = self.prefix(uri) {
+            return prefix.to_string();
+        }
+
+        let well_known = well_known_prefix(uri).map(str::to_string)
+                                               .filter(|prefix| self.uri(prefix).is_none());
+
+        let prefix = well_known.unwrap_or_else(|| {
+            (0..).map(|index| format!("ns{}", index))
+                 .find(|prefix| self.uri(prefix).is_none())
+                 .unwrap_or_default()
+        });
+
+        self.push(&prefix, uri);
+        self.synthetic.push((self.depth, prefix.clone(), uri.to_string()));
+        prefix
+    }
+
+    /// Enter an element, returning the declarations which were made since
+    /// the previous element, and which are therefore scoped on this one
+    pub fn start_element(&mut self) -> Vec<(String, String)> {
+        let start = self.bindings.len() - self.pending;
+        self.pending = 0;
+        self.depth += 1;
+
+        self.bindings[start..].to_vec()
+    }
+
+    /// Leave an element, ending the scope of the prefixes made up for it
+    pub fn end_element(&mut self) {
+        self.depth = self.depth.saturating_sub(1);
+
+        while let Some((depth, prefix, uri)) = self.synthetic.pop() {
+            if depth < self.depth {
+                self.synthetic.push((depth, prefix, uri));
+                break;
+            }
+
+            self.pop(&prefix, &uri);
+        }
+    }
+
+    /// All bindings in scope, outermost first
+    pub fn bindings(&self) -> &[(String, String)] {
+        &self.bindings
+    }
+}
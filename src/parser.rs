@@ -1,202 +1,357 @@
-use std::collections::HashMap;
 use std::borrow::Cow;
-use std::io::{
-    Error,
-    Cursor,
-};
-
-use byteorder::{
-    LittleEndian,
-    ReadBytesExt
-};
+use std::io::Cursor;
 
 use quick_xml::Writer;
-use quick_xml::events::{Event, BytesEnd, BytesStart};
-use quick_xml::events::attributes::Attribute;
-use quick_xml::name::QName;
+use quick_xml::events::{Event, BytesEnd, BytesStart, BytesText};
 
 use crate::xml_types::XmlTypes;
 use crate::chunk_header::ChunkHeader;
-use crate::data_value_type::DataValueType;
 use crate::res_value::ResValue;
+use crate::axml_reader::AxmlEvent;
+use crate::res_resolver::ResourceResolver;
+use crate::framework_res;
+use crate::namespaces::NamespaceStack;
+use crate::string_pool::StringPool;
+use crate::read_ext::ReadExt;
+use crate::error::{
+    AxmlError,
+    Result,
+};
+
+/// Marker used in place of a string index when there is no string
+pub(crate) const NO_ENTRY: u32 = 0xffffffff;
+
+/// Namespace of the attributes defined by the Android framework
+pub const ANDROID_NS_URI: &str = "http://schemas.android.com/apk/res/android";
+
+/// Package ID of the Android framework's resources
+pub(crate) const FRAMEWORK_PACKAGE_ID: u32 = 0x01;
+
+/// An attribute of an element, as decoded from a start element chunk
+#[derive(Debug, Clone)]
+pub struct Attribute {
+    /// Namespace URI of the attribute, if any
+    pub namespace: Option<String>,
+
+    /// Prefix bound to the namespace URI, if any
+    pub prefix: Option<String>,
+
+    /// Local name of the attribute
+    pub name: String,
+
+    /// Resource ID of the attribute, as given by the resource map
+    pub resource_id: Option<u32>,
+
+    /// Name of the attribute in the string pool, if it differs from the
+    /// canonical name given by its resource ID (e.g., because the pool was
+    /// obfuscated)
+    pub pool_name: Option<String>,
+
+    /// Original string value of the attribute, if it was kept by aapt
+    pub raw_value: Option<String>,
+
+    /// Typed value of the attribute
+    pub typed_value: ResValue,
+
+    /// Value of the attribute as it would appear in a text XML file
+    pub value: String,
+}
+
+impl Attribute {
+    /// Name of the attribute including its namespace prefix, e.g., `android:name`
+    pub fn qualified_name(&self) -> String {
+        qualified_name(self.prefix.as_deref(), &self.name)
+    }
+}
 
+/// Name of an element or attribute including its namespace prefix, if any.
+/// The default namespace has an empty prefix.
+pub fn qualified_name(prefix: Option<&str>, name: &str) -> String {
+    match prefix {
+        Some(prefix) if !prefix.is_empty() => format!("{}:{}", prefix, name),
+        _ => name.to_string(),
+    }
+}
+
+/// Get a string from the string pool, failing if the index is out of bounds.
+/// `offset` is the position of the index in the buffer, used for error reporting.
+pub(crate) fn get_string<'a>(strings: &'a StringPool, index: u32, offset: u64) -> Result<Cow<'a, str>> {
+    strings.get(index)
+           .ok_or(AxmlError::BadStringIndex { offset, index })
+}
 
-pub fn parse_start_namespace(axml_buff: &mut Cursor<Vec<u8>>,
-                             strings: &[String],
-                             namespaces: &mut HashMap::<String, String>) {
+/// Canonical name of the attribute with the given resource ID, using the
+/// framework's public attributes and then the resolver, if any
+fn attribute_name(id: u32, resolver: Option<&dyn ResourceResolver>) -> Option<String> {
+    if let Some(("attr", name, _)) = framework_res::framework_name(id) {
+        return Some(name.to_string());
+    }
+
+    resolver.and_then(|resolver| resolver.resource_name(id))
+            .filter(|name| name.type_name == "attr")
+            .map(|name| name.entry)
+}
+
+pub fn parse_start_namespace<T: AsRef<[u8]>>(axml_buff: &mut Cursor<T>,
+                             strings: &StringPool,
+                             namespaces: &mut NamespaceStack) -> Result<AxmlEvent> {
     /* Go back 2 bytes, to account from the block type */
     let offset = axml_buff.position();
     axml_buff.set_position(offset - 2);
 
     /* Parse chunk header */
-    let header = ChunkHeader::from_buff(axml_buff, XmlTypes::ResXmlStartNamespaceType)
-                 .expect("Error: cannot get header from start namespace chunk");
+    let header = ChunkHeader::from_buff(axml_buff, XmlTypes::ResXmlStartNamespaceType)?;
 
-    let line_number = axml_buff.read_u32::<LittleEndian>().unwrap();
-    let comment = axml_buff.read_u32::<LittleEndian>().unwrap();
-    let prefix = axml_buff.read_u32::<LittleEndian>().unwrap();
-    let uri = axml_buff.read_u32::<LittleEndian>().unwrap();
+    let line = axml_buff.read_u32_le()?;
+    let _comment = axml_buff.read_u32_le()?;
 
-    let prefix_str = strings.get(prefix as usize).unwrap();
-    let uri_str = strings.get(uri as usize).unwrap();
-    namespaces.insert(uri_str.to_string(), prefix_str.to_string());
+    /* The header may be larger than what we know of, the data starts right after it */
+    axml_buff.set_position(offset - 2 + header.header_size as u64);
+    let prefix_offset = axml_buff.position();
+    let prefix = axml_buff.read_u32_le()?;
+    let uri_offset = axml_buff.position();
+    let uri = axml_buff.read_u32_le()?;
+
+    let prefix = get_string(strings, prefix, prefix_offset)?.into_owned();
+    let uri = get_string(strings, uri, uri_offset)?.into_owned();
+    namespaces.push(&prefix, &uri);
+
+    Ok(AxmlEvent::StartNamespace { prefix, uri, line })
 }
 
-pub fn parse_end_namespace(axml_buff: &mut Cursor<Vec<u8>>,
-                           strings: &[String]) {
+pub fn parse_end_namespace<T: AsRef<[u8]>>(axml_buff: &mut Cursor<T>,
+                           strings: &StringPool,
+                           namespaces: &mut NamespaceStack) -> Result<AxmlEvent> {
     /* Go back 2 bytes, to account from the block type */
     let offset = axml_buff.position();
     axml_buff.set_position(offset - 2);
 
     /* Parse chunk header */
-    let header = ChunkHeader::from_buff(axml_buff, XmlTypes::ResXmlEndNamespaceType)
-                 .expect("Error: cannot get header from start namespace chunk");
+    let header = ChunkHeader::from_buff(axml_buff, XmlTypes::ResXmlEndNamespaceType)?;
+
+    let line = axml_buff.read_u32_le()?;
+    let _comment = axml_buff.read_u32_le()?;
 
-    let line_number = axml_buff.read_u32::<LittleEndian>().unwrap();
-    let comment = axml_buff.read_u32::<LittleEndian>().unwrap();
-    let prefix = axml_buff.read_u32::<LittleEndian>().unwrap();
-    let uri = axml_buff.read_u32::<LittleEndian>().unwrap();
+    /* The header may be larger than what we know of, the data starts right after it */
+    axml_buff.set_position(offset - 2 + header.header_size as u64);
+    let prefix_offset = axml_buff.position();
+    let prefix = axml_buff.read_u32_le()?;
+    let uri_offset = axml_buff.position();
+    let uri = axml_buff.read_u32_le()?;
+
+    let prefix = get_string(strings, prefix, prefix_offset)?.into_owned();
+    let uri = get_string(strings, uri, uri_offset)?.into_owned();
+    namespaces.pop(&prefix, &uri);
+
+    Ok(AxmlEvent::EndNamespace { prefix, uri, line })
 }
 
-pub fn parse_start_element(axml_buff: &mut Cursor<Vec<u8>>,
-                           strings: &[String],
-                           namespace_prefixes: &HashMap::<String, String>) -> Result<(String, Vec<(String, String)>), Error> {
+pub fn parse_start_element<T: AsRef<[u8]>>(axml_buff: &mut Cursor<T>,
+                           strings: &StringPool,
+                           namespaces: &mut NamespaceStack,
+                           resource_map: &[u32],
+                           resolver: Option<&dyn ResourceResolver>) -> Result<AxmlEvent> {
     /* Go back 2 bytes, to account from the block type */
     let offset = axml_buff.position();
     axml_buff.set_position(offset - 2);
 
     /* Parse chunk header */
-    let header = ChunkHeader::from_buff(axml_buff, XmlTypes::ResXmlStartElementType)
-                 .expect("Error: cannot get header from start namespace chunk");
-
-    let line_number = axml_buff.read_u32::<LittleEndian>().unwrap();
-    let comment = axml_buff.read_u32::<LittleEndian>().unwrap();
-    let namespace = axml_buff.read_u32::<LittleEndian>().unwrap();
-    let name = axml_buff.read_u32::<LittleEndian>().unwrap();
-    let attribute_size = axml_buff.read_u32::<LittleEndian>().unwrap();
-    let attribute_count = axml_buff.read_u16::<LittleEndian>().unwrap();
-    let id_index = axml_buff.read_u16::<LittleEndian>().unwrap();
-    let class_index = axml_buff.read_u16::<LittleEndian>().unwrap();
-    let style_index = axml_buff.read_u16::<LittleEndian>().unwrap();
-
-    let mut decoded_attrs = Vec::<(String, String)>::new();
-    for _ in 0..attribute_count {
-        let attr_namespace = axml_buff.read_u32::<LittleEndian>().unwrap();
-        let attr_name = axml_buff.read_u32::<LittleEndian>().unwrap();
-        let attr_raw_val = axml_buff.read_u32::<LittleEndian>().unwrap();
-        let data_value_type = ResValue::from_buff(axml_buff).unwrap();
-
-        let mut decoded_attr_key = String::new();
+    let header = ChunkHeader::from_buff(axml_buff, XmlTypes::ResXmlStartElementType)?;
+
+    let line = axml_buff.read_u32_le()?;
+    let _comment = axml_buff.read_u32_le()?;
+
+    /* The header may be larger than what we know of, the data starts right after it */
+    let data_start = offset - 2 + header.header_size as u64;
+    axml_buff.set_position(data_start);
+    let namespace = axml_buff.read_u32_le()?;
+    let name_offset = axml_buff.position();
+    let name = axml_buff.read_u32_le()?;
+    let attribute_start = axml_buff.read_u16_le()?;
+    let attribute_size = axml_buff.read_u16_le()?;
+    let attribute_count = axml_buff.read_u16_le()?;
+    let _id_index = axml_buff.read_u16_le()?;
+    let _class_index = axml_buff.read_u16_le()?;
+    let _style_index = axml_buff.read_u16_le()?;
+
+    let ns = match namespace {
+        NO_ENTRY => None,
+        _ => Some(get_string(strings, namespace, data_start)?.into_owned()),
+    };
+    let prefix = ns.as_ref().map(|uri| namespaces.prefix_or_declare(uri));
+
+    let mut decoded_attrs = Vec::<Attribute>::new();
+    for i in 0..attribute_count as u64 {
+        /* Attributes are located relatively to the start of the element data */
+        let attr_offset = data_start + attribute_start as u64 + i * attribute_size as u64;
+        axml_buff.set_position(attr_offset);
+        let attr_namespace = axml_buff.read_u32_le()?;
+        let attr_name = axml_buff.read_u32_le()?;
+        let attr_raw_val = axml_buff.read_u32_le()?;
+        let data_value_type = ResValue::from_buff(axml_buff)?;
+
         let mut decoded_attr_val = String::new();
 
-        if attr_namespace != 0xffffffff {
-            let ns_prefix = namespace_prefixes.get(strings.get(attr_namespace as usize).unwrap()).unwrap();
-            decoded_attr_key.push_str(ns_prefix);
-            decoded_attr_key.push(':');
-        } else {
-            // TODO
+        let mut attr_ns = None;
+        let mut attr_prefix = None;
+        if attr_namespace != NO_ENTRY {
+            let ns_uri = get_string(strings, attr_namespace, attr_offset)?;
+            attr_prefix = Some(namespaces.prefix_or_declare(&ns_uri));
+            attr_ns = Some(ns_uri.into_owned());
         }
 
-        decoded_attr_key.push_str(strings.get(attr_name as usize).unwrap());
+        /* Android identifies attributes by the resource ID that the resource
+         * map gives to their name, the string itself can be blank or scrambled */
+        let pool_name = get_string(strings, attr_name, attr_offset + 4)?;
+        let resource_id = resource_map.get(attr_name as usize)
+                                      .copied()
+                                      .filter(|&id| id != 0);
+        let (attr_name, pool_name) = match resource_id.and_then(|id| attribute_name(id, resolver)) {
+            Some(canonical) if canonical != pool_name => (canonical, Some(pool_name.to_string())),
+            Some(canonical) => (canonical, None),
+            None => (pool_name.into_owned(), None),
+        };
 
-        if attr_raw_val != 0xffffffff {
-            decoded_attr_val.push_str(&strings.get(attr_raw_val as usize).unwrap().to_string());
+        /* The namespace of framework attributes can be stripped as well */
+        if attr_ns.is_none() && resource_id.map(|id| id >> 24) == Some(FRAMEWORK_PACKAGE_ID) {
+            attr_prefix = Some(namespaces.prefix_or_declare(ANDROID_NS_URI));
+            attr_ns = Some(ANDROID_NS_URI.to_string());
+        }
+
+        let mut raw_value = None;
+        if attr_raw_val != NO_ENTRY {
+            let raw = get_string(strings, attr_raw_val, attr_offset + 8)?;
+            decoded_attr_val.push_str(&raw);
+            raw_value = Some(raw.into_owned());
         } else {
-            match data_value_type.data_type {
-                DataValueType::TypeNull => println!("TODO: DataValueType::TypeNull"),
-                DataValueType::TypeReference => {
-                    decoded_attr_val.push_str("type1/");
-                    decoded_attr_val.push_str(&data_value_type.data.to_string());
-                },
-                DataValueType::TypeAttribute => println!("TODO: DataValueType::TypeAttribute"),
-                DataValueType::TypeString => println!("TODO: DataValueType::TypeString"),
-                DataValueType::TypeFloat => println!("TODO: DataValueType::TypeFloat"),
-                DataValueType::TypeDimension => println!("TODO: DataValueType::TypeDimension"),
-                DataValueType::TypeFraction => println!("TODO: DataValueType::TypeFraction"),
-                DataValueType::TypeDynamicReference => println!("TODO: DataValueType::TypeDynamicReference"),
-                DataValueType::TypeDynamicAttribute => println!("TODO: DataValueType::TypeDynamicAttribute"),
-                DataValueType::TypeIntDec => decoded_attr_val.push_str(&data_value_type.data.to_string()),
-                DataValueType::TypeIntHex => {
-                    decoded_attr_val.push_str("0x");
-                    decoded_attr_val.push_str(&format!("{:x}", &data_value_type.data).to_string());
-                },
-                DataValueType::TypeIntBoolean => {
-                    if data_value_type.data == 0 {
-                        decoded_attr_val.push_str("false");
-                    } else {
-                        decoded_attr_val.push_str("true");
-                    }
-                },
-                DataValueType::TypeIntColorArgb8 => println!("TODO: DataValueType::TypeIntColorArgb8"),
-                DataValueType::TypeIntColorRgb8 => println!("TODO: DataValueType::TypeIntColorRgb8"),
-                DataValueType::TypeIntColorArgb4 => println!("TODO: DataValueType::TypeIntColorArgb4"),
-                DataValueType::TypeIntColorRgb4 => println!("TODO: DataValueType::TypeIntColorRgb4"),
-            }
+            decoded_attr_val.push_str(&data_value_type.format(strings, resolver));
         }
-        decoded_attrs.push((decoded_attr_key, decoded_attr_val));
+        decoded_attrs.push(Attribute {
+            namespace: attr_ns,
+            prefix: attr_prefix,
+            name: attr_name,
+            resource_id,
+            pool_name,
+            raw_value,
+            typed_value: data_value_type,
+            value: decoded_attr_val,
+        });
     }
 
-    Ok((strings.get(name as usize).unwrap().to_string(), decoded_attrs))
+    Ok(AxmlEvent::StartElement {
+        name: get_string(strings, name, name_offset)?.into_owned(),
+        ns,
+        prefix,
+        namespaces: namespaces.start_element(),
+        attrs: decoded_attrs,
+        line,
+    })
+}
+
+pub fn parse_end_element<T: AsRef<[u8]>>(axml_buff: &mut Cursor<T>,
+                         strings: &StringPool,
+                         namespaces: &mut NamespaceStack) -> Result<AxmlEvent> {
+    /* Go back 2 bytes, to account from the block type */
+    let offset = axml_buff.position();
+    axml_buff.set_position(offset - 2);
+
+    /* Parse chunk header */
+    let header = ChunkHeader::from_buff(axml_buff, XmlTypes::ResXmlEndElementType)?;
+
+    let line = axml_buff.read_u32_le()?;
+    let _comment = axml_buff.read_u32_le()?;
+
+    /* The header may be larger than what we know of, the data starts right after it */
+    let data_start = offset - 2 + header.header_size as u64;
+    axml_buff.set_position(data_start);
+    let namespace = axml_buff.read_u32_le()?;
+    let name_offset = axml_buff.position();
+    let name = axml_buff.read_u32_le()?;
+
+    let ns = match namespace {
+        NO_ENTRY => None,
+        _ => Some(get_string(strings, namespace, data_start)?.into_owned()),
+    };
+
+    let prefix = ns.as_ref()
+                   .and_then(|uri| namespaces.prefix(uri))
+                   .map(|prefix| prefix.to_string());
+    namespaces.end_element();
+
+    Ok(AxmlEvent::EndElement {
+        name: get_string(strings, name, name_offset)?.into_owned(),
+        ns,
+        prefix,
+        line,
+    })
 }
 
-pub fn parse_end_element(axml_buff: &mut Cursor<Vec<u8>>,
-                         strings: &[String]) -> Result<String, Error> {
+pub fn parse_cdata<T: AsRef<[u8]>>(axml_buff: &mut Cursor<T>,
+                   strings: &StringPool) -> Result<AxmlEvent> {
     /* Go back 2 bytes, to account from the block type */
     let offset = axml_buff.position();
     axml_buff.set_position(offset - 2);
 
     /* Parse chunk header */
-    let header = ChunkHeader::from_buff(axml_buff, XmlTypes::ResXmlEndElementType)
-                 .expect("Error: cannot get header from start namespace chunk");
+    let header = ChunkHeader::from_buff(axml_buff, XmlTypes::ResXmlCDataType)?;
 
-    let line_number = axml_buff.read_u32::<LittleEndian>().unwrap();
-    let comment = axml_buff.read_u32::<LittleEndian>().unwrap();
-    let namespace = axml_buff.read_u32::<LittleEndian>().unwrap();
-    let name = axml_buff.read_u32::<LittleEndian>().unwrap();
+    let line = axml_buff.read_u32_le()?;
+    let _comment = axml_buff.read_u32_le()?;
 
-    Ok(strings.get(name as usize).unwrap().to_string())
+    /* The header may be larger than what we know of, the data starts right after it */
+    axml_buff.set_position(offset - 2 + header.header_size as u64);
+    let data_offset = axml_buff.position();
+    let data = axml_buff.read_u32_le()?;
+    let typed_value = ResValue::from_buff(axml_buff)?;
+
+    Ok(AxmlEvent::CData {
+        text: get_string(strings, data, data_offset)?.into_owned(),
+        typed_value,
+        line,
+    })
 }
 
+/// Write the start or end tag of an element, other block types are ignored
 pub fn handle_event<T> (writer: &mut Writer<T>,
                         element_name: String,
-                        element_attrs: Vec<(String, String)>,
-                        namespace_prefixes: &HashMap::<String, String>,
-                        block_type: XmlTypes) where T: std::io::Write {
+                        element_attrs: Vec<Attribute>,
+                        namespace_decls: &[(String, String)],
+                        block_type: XmlTypes) -> Result<()> where T: std::io::Write {
     match block_type {
         XmlTypes::ResXmlStartElementType => {
-            // let mut elem = BytesStart::from_content(element_name.as_bytes(), element_name.len());
             let mut elem = BytesStart::new(&element_name);
 
-            if element_name == "manifest" {
-                for (k, v) in namespace_prefixes.iter() {
-                    if v == "android" {
-                        let mut key = String::new();
-                        key.push_str("xmlns:");
-                        key.push_str(v);
-                        let attr = Attribute {
-                            key: QName(key.as_bytes()),
-                            value: Cow::Borrowed(k.as_bytes())
-                        };
-                        elem.push_attribute(attr);
-                        break;
-                    }
-                }
+            /* Declare the namespaces scoped on this element */
+            for (prefix, uri) in namespace_decls.iter() {
+                let key = match prefix.is_empty() {
+                    true => "xmlns".to_string(),
+                    false => format!("xmlns:{}", prefix),
+                };
+                elem.push_attribute((key.as_str(), uri.as_str()));
             }
 
-            for (attr_key, attr_val) in element_attrs {
-                let attr = Attribute {
-                    key: QName(attr_key.as_bytes()),
-                    value: Cow::Borrowed(attr_val.as_bytes())
-                };
-                elem.push_attribute(attr);
+            /* Values are escaped, they can contain quotes or ampersands */
+            for element_attr in element_attrs {
+                let attr_key = element_attr.qualified_name();
+                elem.push_attribute((attr_key.as_str(), element_attr.value.as_str()));
             }
 
-            assert!(writer.write_event(Event::Start(elem)).is_ok());
+            writer.write_event(Event::Start(elem))?;
 
         },
         XmlTypes::ResXmlEndElementType => {
-            assert!(writer.write_event(Event::End(BytesEnd::new(element_name))).is_ok());
+            writer.write_event(Event::End(BytesEnd::new(element_name)))?;
         },
-        _ => println!("{:02X}, other", block_type),
+        /* Other chunks have no counterpart in text XML */
+        _ => { },
     }
+
+    Ok(())
+}
+
+/// Write the text content of an element, escaping it as needed
+pub fn handle_text<T>(writer: &mut Writer<T>, text: &str) -> Result<()> where T: std::io::Write {
+    writer.write_event(Event::Text(BytesText::new(text)))?;
+
+    Ok(())
 }
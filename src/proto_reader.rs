@@ -0,0 +1,154 @@
+use crate::error::{
+    AxmlError,
+    Result,
+};
+
+/* Wire types of the protobuf encoding */
+const WIRE_VARINT: u32 = 0;
+const WIRE_FIXED64: u32 = 1;
+pub(crate) const WIRE_LEN: u32 = 2;
+const WIRE_FIXED32: u32 = 5;
+
+/// Value of a field, as encoded on the wire
+#[derive(Debug, Clone, Copy)]
+pub(crate) enum ProtoValue<'a> {
+    Varint(u64),
+    Fixed64(u64),
+    Fixed32(u32),
+
+    /* Strings, bytes and embedded messages */
+    Bytes(&'a [u8]),
+}
+
+impl<'a> ProtoValue<'a> {
+    /// Value of an integer field (`uint32`, `int32`, `bool`, enums), 0 if
+    /// the field has another wire type
+    pub fn as_u32(&self) -> u32 {
+        match *self {
+            ProtoValue::Varint(value) | ProtoValue::Fixed64(value) => value as u32,
+            ProtoValue::Fixed32(value) => value,
+            ProtoValue::Bytes(_) => 0,
+        }
+    }
+
+    /// Value of a `string` field, decoded lossily
+    pub fn as_string(&self) -> String {
+        match *self {
+            ProtoValue::Bytes(bytes) => String::from_utf8_lossy(bytes).into_owned(),
+            _ => String::new(),
+        }
+    }
+
+    /// Reader over the fields of an embedded message. `offset` is the
+    /// position of the message in the whole input, for error reporting.
+    pub fn as_message(&self, offset: u64) -> ProtoReader<'a> {
+        match *self {
+            ProtoValue::Bytes(bytes) => ProtoReader::with_offset(bytes, offset),
+            _ => ProtoReader::with_offset(&[], offset),
+        }
+    }
+}
+
+/**
+ * Reader for the fields of a message encoded with protobuf, as used by
+ * aapt2 for the files of Android App Bundles.
+ *
+ * This only decodes the wire format: fields are returned in the order in
+ * which they are encoded, with their number and raw value, and giving them
+ * a meaning is up to the caller.
+ */
+#[derive(Debug, Clone)]
+pub(crate) struct ProtoReader<'a> {
+    data: &'a [u8],
+    position: usize,
+
+    /* Position of `data` in the whole input */
+    base_offset: u64,
+}
+
+impl<'a> ProtoReader<'a> {
+    pub fn new(data: &'a [u8]) -> Self {
+        ProtoReader::with_offset(data, 0)
+    }
+
+    fn with_offset(data: &'a [u8], base_offset: u64) -> Self {
+        ProtoReader {
+            data,
+            position: 0,
+            base_offset,
+        }
+    }
+
+    /// Position of the next field in the whole input
+    pub fn offset(&self) -> u64 {
+        self.base_offset + self.position as u64
+    }
+
+    fn read_varint(&mut self) -> Result<u64> {
+        let offset = self.offset();
+        let mut value = 0u64;
+
+        for shift in (0..64).step_by(7) {
+            let byte = *self.data.get(self.position)
+                                 .ok_or(AxmlError::Truncated { offset })?;
+            self.position += 1;
+
+            value |= ((byte & 0x7f) as u64) << shift;
+            if byte & 0x80 == 0 {
+                return Ok(value);
+            }
+        }
+
+        Err(AxmlError::MalformedProto { offset })
+    }
+
+    fn read_bytes(&mut self, len: usize) -> Result<&'a [u8]> {
+        let offset = self.offset();
+        let bytes = self.position.checked_add(len)
+                                 .and_then(|end| self.data.get(self.position..end))
+                                 .ok_or(AxmlError::Truncated { offset })?;
+        self.position += len;
+
+        Ok(bytes)
+    }
+
+    /// Read the next field, as `(field number, value, offset of the
+    /// value)`. Returns `None` at the end of the message.
+    pub fn next_field(&mut self) -> Result<Option<(u32, ProtoValue<'a>, u64)>> {
+        if self.position >= self.data.len() {
+            return Ok(None);
+        }
+
+        let offset = self.offset();
+        let key = self.read_varint()?;
+        let field = (key >> 3) as u32;
+        let value_offset = self.offset();
+
+        let value = match (key & 0x7) as u32 {
+            WIRE_VARINT => ProtoValue::Varint(self.read_varint()?),
+            WIRE_FIXED64 => {
+                let bytes = self.read_bytes(8)?;
+                let mut raw = [0u8; 8];
+                raw.copy_from_slice(bytes);
+                ProtoValue::Fixed64(u64::from_le_bytes(raw))
+            },
+            WIRE_LEN => {
+                let len = self.read_varint()? as usize;
+                ProtoValue::Bytes(self.read_bytes(len)?)
+            },
+            WIRE_FIXED32 => {
+                let bytes = self.read_bytes(4)?;
+                ProtoValue::Fixed32(u32::from_le_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]))
+            },
+            _ => return Err(AxmlError::MalformedProto { offset }),
+        };
+
+        /* Values of embedded messages start after their length */
+        let value_offset = match value {
+            ProtoValue::Bytes(bytes) => self.offset() - bytes.len() as u64,
+            _ => value_offset,
+        };
+
+        Ok(Some((field, value, value_offset)))
+    }
+}
@@ -0,0 +1,426 @@
+use crate::axml_reader::AxmlEvent;
+use crate::data_value_type::DataValueType;
+use crate::namespaces::NamespaceStack;
+use crate::parser::{
+    Attribute,
+    ANDROID_NS_URI,
+    FRAMEWORK_PACKAGE_ID,
+};
+use crate::proto_reader::{
+    ProtoReader,
+    WIRE_LEN,
+};
+use crate::res_resolver::ResourceResolver;
+use crate::res_table::ResTable;
+use crate::res_value::ResValue;
+use crate::string_pool::StringPool;
+use crate::error::{
+    AxmlError,
+    Result,
+};
+
+/* Field numbers of the messages of aapt2's Resources.proto which make up
+ * an XmlNode */
+const NODE_ELEMENT: u32 = 1;
+const NODE_TEXT: u32 = 2;
+const NODE_SOURCE: u32 = 3;
+
+const ELEMENT_NAMESPACE_DECLARATION: u32 = 1;
+const ELEMENT_NAMESPACE_URI: u32 = 2;
+const ELEMENT_NAME: u32 = 3;
+const ELEMENT_ATTRIBUTE: u32 = 4;
+const ELEMENT_CHILD: u32 = 5;
+
+const NAMESPACE_PREFIX: u32 = 1;
+const NAMESPACE_URI: u32 = 2;
+
+const ATTRIBUTE_NAMESPACE_URI: u32 = 1;
+const ATTRIBUTE_NAME: u32 = 2;
+const ATTRIBUTE_VALUE: u32 = 3;
+const ATTRIBUTE_RESOURCE_ID: u32 = 5;
+const ATTRIBUTE_COMPILED_ITEM: u32 = 6;
+
+const SOURCE_LINE_NUMBER: u32 = 1;
+
+/* Fields of an Item, i.e., a compiled value */
+const ITEM_REF: u32 = 1;
+const ITEM_STR: u32 = 2;
+const ITEM_RAW_STR: u32 = 3;
+const ITEM_STYLED_STR: u32 = 4;
+const ITEM_FILE: u32 = 5;
+const ITEM_PRIM: u32 = 7;
+
+/* Fields of a Reference, and its types */
+const REFERENCE_TYPE: u32 = 1;
+const REFERENCE_ID: u32 = 2;
+const REFERENCE_NAME: u32 = 3;
+const REFERENCE_TYPE_ATTRIBUTE: u32 = 1;
+
+/* The value of String, RawString, StyledString and FileReference messages
+ * (the path, for the latter) is always their first field */
+const STRING_VALUE: u32 = 1;
+
+/* Fields of a Primitive, one per type of value */
+const PRIM_NULL: u32 = 1;
+const PRIM_EMPTY: u32 = 2;
+const PRIM_FLOAT: u32 = 3;
+const PRIM_INT_DECIMAL: u32 = 6;
+const PRIM_INT_HEXADECIMAL: u32 = 7;
+const PRIM_BOOLEAN: u32 = 8;
+const PRIM_COLOR_ARGB8: u32 = 9;
+const PRIM_COLOR_RGB8: u32 = 10;
+const PRIM_COLOR_ARGB4: u32 = 11;
+const PRIM_COLOR_RGB4: u32 = 12;
+const PRIM_DIMENSION: u32 = 13;
+const PRIM_FRACTION: u32 = 14;
+
+/* Strings are not in a string pool in this format, so string values have no
+ * valid index */
+const NO_STRING_INDEX: u32 = 0xffffffff;
+
+/* Maximum depth of the elements, to avoid overflowing the stack on
+ * malicious input */
+const MAX_DEPTH: usize = 512;
+
+/**
+ * Reader for the XML files of Android App Bundles (`.aab`), which aapt2
+ * stores as protobuf `XmlNode` messages instead of binary XML.
+ *
+ * It yields the same events as `AxmlReader`, so that both formats can be
+ * handled by the same code. The whole document is decoded on the first
+ * call to `next`.
+ */
+pub struct ProtoXmlReader<'a> {
+    bytes: &'a [u8],
+    resolver: Option<&'a dyn ResourceResolver>,
+    resources: Option<&'a ResTable>,
+    events: Option<std::vec::IntoIter<Result<AxmlEvent>>>,
+}
+
+impl<'a> ProtoXmlReader<'a> {
+    pub fn from_bytes(bytes: &'a [u8]) -> Self {
+        ProtoXmlReader {
+            bytes,
+            resolver: None,
+            resources: None,
+            events: None,
+        }
+    }
+
+    /// Use `resolver` to give symbolic names to the references which aapt2
+    /// did not name itself
+    pub fn with_resolver(mut self, resolver: &'a dyn ResourceResolver) -> Self {
+        self.resolver = Some(resolver);
+        self
+    }
+
+    /// Use the resource table of the app to name references, and to replace
+    /// references to string, bool and integer resources by their value, as
+    /// `AxmlReader::with_resources` does
+    pub fn with_resources(mut self, resources: &'a ResTable) -> Self {
+        self.resolver = Some(resources);
+        self.resources = Some(resources);
+        self
+    }
+
+    /// Decode the whole document
+    fn decode(&self) -> Result<Vec<AxmlEvent>> {
+        let mut decoder = Decoder {
+            namespaces: NamespaceStack::new(),
+            resolver: self.resolver,
+            resources: self.resources,
+            events: Vec::new(),
+        };
+
+        decoder.node(ProtoReader::new(self.bytes), 0)?;
+        Ok(decoder.events)
+    }
+}
+
+impl Iterator for ProtoXmlReader<'_> {
+    type Item = Result<AxmlEvent>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.events.is_none() {
+            let events = match self.decode() {
+                Ok(events) => events.into_iter().map(Ok).collect::<Vec<_>>(),
+                Err(e) => vec![Err(e)],
+            };
+            self.events = Some(events.into_iter());
+        }
+
+        self.events.as_mut()?.next()
+    }
+}
+
+/// State of the decoding of a document
+struct Decoder<'a> {
+    namespaces: NamespaceStack,
+    resolver: Option<&'a dyn ResourceResolver>,
+    resources: Option<&'a ResTable>,
+    events: Vec<AxmlEvent>,
+}
+
+impl Decoder<'_> {
+    /// Decode an XmlNode: either an element (and its children), or text
+    fn node(&mut self, mut node: ProtoReader<'_>, depth: usize) -> Result<()> {
+        let mut element = None;
+        let mut text = None;
+        let mut line = 0;
+
+        while let Some((field, value, offset)) = node.next_field()? {
+            match field {
+                NODE_ELEMENT => element = Some(value.as_message(offset)),
+                NODE_TEXT => text = Some(value.as_string()),
+                NODE_SOURCE => line = source_line(value.as_message(offset))?,
+                _ => { },
+            }
+        }
+
+        if let Some(element) = element {
+            if depth >= MAX_DEPTH {
+                return Err(AxmlError::MalformedDocument {
+                    line,
+                    reason: "elements are nested too deeply".to_string(),
+                });
+            }
+            self.element(element, line, depth)?;
+        } else if let Some(text) = text {
+            self.events.push(AxmlEvent::CData {
+                text,
+                typed_value: ResValue::new(DataValueType::TypeNull, 0),
+                line,
+            });
+        }
+
+        Ok(())
+    }
+
+    /// Decode an XmlElement, emitting the events of its namespace
+    /// declarations, of the element itself, and of its children
+    fn element(&mut self, mut element: ProtoReader<'_>, line: u32, depth: usize) -> Result<()> {
+        let mut declarations = Vec::new();
+        let mut ns = None;
+        let mut name = String::new();
+        let mut raw_attrs = Vec::new();
+        let mut children = Vec::new();
+
+        while let Some((field, value, offset)) = element.next_field()? {
+            match field {
+                ELEMENT_NAMESPACE_DECLARATION => declarations.push(namespace(value.as_message(offset))?),
+                ELEMENT_NAMESPACE_URI => ns = Some(value.as_string()).filter(|uri| !uri.is_empty()),
+                ELEMENT_NAME => name = value.as_string(),
+                ELEMENT_ATTRIBUTE => raw_attrs.push(value.as_message(offset)),
+                ELEMENT_CHILD => children.push(value.as_message(offset)),
+                _ => { },
+            }
+        }
+
+        for (prefix, uri) in declarations.iter() {
+            self.namespaces.push(prefix, uri);
+            self.events.push(AxmlEvent::StartNamespace {
+                prefix: prefix.clone(),
+                uri: uri.clone(),
+                line,
+            });
+        }
+
+        let prefix = ns.as_ref().map(|uri| self.namespaces.prefix_or_declare(uri));
+        let mut attrs = Vec::new();
+        for raw_attr in raw_attrs {
+            attrs.push(self.attribute(raw_attr)?);
+        }
+
+        self.events.push(AxmlEvent::StartElement {
+            name: name.clone(),
+            ns: ns.clone(),
+            prefix: prefix.clone(),
+            namespaces: self.namespaces.start_element(),
+            attrs,
+            line,
+        });
+
+        for child in children {
+            self.node(child, depth + 1)?;
+        }
+
+        self.events.push(AxmlEvent::EndElement { name, ns, prefix, line });
+        self.namespaces.end_element();
+
+        for (prefix, uri) in declarations.into_iter().rev() {
+            self.namespaces.pop(&prefix, &uri);
+            self.events.push(AxmlEvent::EndNamespace { prefix, uri, line });
+        }
+
+        Ok(())
+    }
+
+    /// Decode an XmlAttribute
+    fn attribute(&mut self, mut attr: ProtoReader<'_>) -> Result<Attribute> {
+        let mut attr_ns = None;
+        let mut name = String::new();
+        let mut raw_value = None;
+        let mut resource_id = None;
+        let mut compiled = None;
+
+        while let Some((field, value, offset)) = attr.next_field()? {
+            match field {
+                ATTRIBUTE_NAMESPACE_URI => attr_ns = Some(value.as_string()).filter(|uri| !uri.is_empty()),
+                ATTRIBUTE_NAME => name = value.as_string(),
+                ATTRIBUTE_VALUE => raw_value = Some(value.as_string()),
+                ATTRIBUTE_RESOURCE_ID => resource_id = Some(value.as_u32()).filter(|&id| id != 0),
+                ATTRIBUTE_COMPILED_ITEM => compiled = Some(item(value.as_message(offset))?),
+                _ => { },
+            }
+        }
+
+        /* As in binary XML, the namespace of framework attributes can be
+         * stripped */
+        if attr_ns.is_none() && resource_id.map(|id| id >> 24) == Some(FRAMEWORK_PACKAGE_ID) {
+            attr_ns = Some(ANDROID_NS_URI.to_string());
+        }
+        let prefix = attr_ns.as_ref().map(|uri| self.namespaces.prefix_or_declare(uri));
+
+        /* The text of strings and named references is kept as is, other
+         * values are formatted like in binary XML */
+        let (typed_value, value) = match compiled {
+            Some((typed_value, Some(text))) => (typed_value, text),
+            Some((typed_value, None)) => (typed_value, typed_value.format(&StringPool::empty(), self.resolver)),
+            None => (ResValue::new(DataValueType::TypeString, NO_STRING_INDEX), raw_value.clone().unwrap_or_default()),
+        };
+        let value = self.resources.and_then(|resources| resources.substitute(&typed_value))
+                                  .unwrap_or(value);
+
+        Ok(Attribute {
+            namespace: attr_ns,
+            prefix,
+            name,
+            resource_id,
+            pool_name: None,
+            raw_value: raw_value.filter(|raw| !raw.is_empty()),
+            typed_value,
+            value,
+        })
+    }
+}
+
+/// Decode an XmlNamespace, as a `(prefix, uri)` pair
+fn namespace(mut declaration: ProtoReader<'_>) -> Result<(String, String)> {
+    let mut prefix = String::new();
+    let mut uri = String::new();
+
+    while let Some((field, value, _)) = declaration.next_field()? {
+        match field {
+            NAMESPACE_PREFIX => prefix = value.as_string(),
+            NAMESPACE_URI => uri = value.as_string(),
+            _ => { },
+        }
+    }
+
+    Ok((prefix, uri))
+}
+
+/// Line number of a SourcePosition
+fn source_line(mut source: ProtoReader<'_>) -> Result<u32> {
+    let mut line = 0;
+    while let Some((field, value, _)) = source.next_field()? {
+        if field == SOURCE_LINE_NUMBER {
+            line = value.as_u32();
+        }
+    }
+
+    Ok(line)
+}
+
+/// First field of a message, used for the messages which wrap a string
+fn string_value(mut message: ProtoReader<'_>) -> Result<String> {
+    while let Some((field, value, _)) = message.next_field()? {
+        if field == STRING_VALUE {
+            return Ok(value.as_string());
+        }
+    }
+
+    Ok(String::new())
+}
+
+/// Decode an Item, i.e., a compiled value, as its equivalent `ResValue`
+/// and, for strings and named references, its text
+fn item(mut item: ProtoReader<'_>) -> Result<(ResValue, Option<String>)> {
+    let mut decoded = (ResValue::new(DataValueType::TypeNull, 0), None);
+
+    while let Some((field, value, offset)) = item.next_field()? {
+        decoded = match field {
+            ITEM_REF => reference(value.as_message(offset))?,
+            ITEM_STR
+            | ITEM_RAW_STR
+            | ITEM_STYLED_STR
+            | ITEM_FILE => {
+                let text = string_value(value.as_message(offset))?;
+                (ResValue::new(DataValueType::TypeString, NO_STRING_INDEX), Some(text))
+            },
+            ITEM_PRIM => (primitive(value.as_message(offset))?, None),
+            _ => continue,
+        };
+    }
+
+    Ok(decoded)
+}
+
+/// Decode a Reference. aapt2 keeps the name of the resource, if known.
+fn reference(mut reference: ProtoReader<'_>) -> Result<(ResValue, Option<String>)> {
+    let mut data_type = DataValueType::TypeReference;
+    let mut id = 0;
+    let mut name = None;
+
+    while let Some((field, value, _)) = reference.next_field()? {
+        match field {
+            REFERENCE_TYPE if value.as_u32() == REFERENCE_TYPE_ATTRIBUTE => data_type = DataValueType::TypeAttribute,
+            REFERENCE_ID => id = value.as_u32(),
+            REFERENCE_NAME => name = Some(value.as_string()).filter(|name| !name.is_empty()),
+            _ => { },
+        }
+    }
+
+    let marker = match data_type {
+        DataValueType::TypeAttribute => '?',
+        _ => '@',
+    };
+    Ok((ResValue::new(data_type, id), name.map(|name| format!("{}{}", marker, name))))
+}
+
+/// Decode a Primitive
+fn primitive(mut primitive: ProtoReader<'_>) -> Result<ResValue> {
+    let mut decoded = ResValue::new(DataValueType::TypeNull, 0);
+
+    while let Some((field, value, _)) = primitive.next_field()? {
+        let data = value.as_u32();
+        decoded = match field {
+            PRIM_NULL => ResValue::new(DataValueType::TypeNull, 0),
+            PRIM_EMPTY => ResValue::new(DataValueType::TypeNull, 1),
+            PRIM_FLOAT => ResValue::new(DataValueType::TypeFloat, data),
+            PRIM_INT_DECIMAL => ResValue::new(DataValueType::TypeIntDec, data),
+            PRIM_INT_HEXADECIMAL => ResValue::new(DataValueType::TypeIntHex, data),
+            /* Like aapt, true is stored as all bits set */
+            PRIM_BOOLEAN => ResValue::new(DataValueType::TypeIntBoolean, if data != 0 { 0xffffffff } else { 0 }),
+            PRIM_COLOR_ARGB8 => ResValue::new(DataValueType::TypeIntColorArgb8, data),
+            PRIM_COLOR_RGB8 => ResValue::new(DataValueType::TypeIntColorRgb8, data),
+            PRIM_COLOR_ARGB4 => ResValue::new(DataValueType::TypeIntColorArgb4, data),
+            PRIM_COLOR_RGB4 => ResValue::new(DataValueType::TypeIntColorRgb4, data),
+            PRIM_DIMENSION => ResValue::new(DataValueType::TypeDimension, data),
+            PRIM_FRACTION => ResValue::new(DataValueType::TypeFraction, data),
+            _ => continue,
+        };
+    }
+
+    Ok(decoded)
+}
+
+/// Whether the contents of a file look like a protobuf XmlNode rather than
+/// binary XML: the key of its first field is the one of an element or of a
+/// text node (binary XML starts with the type of its first chunk, 0x0003)
+pub fn is_proto_xml(bytes: &[u8]) -> bool {
+    let element_key = (NODE_ELEMENT << 3 | WIRE_LEN) as u8;
+    let text_key = (NODE_TEXT << 3 | WIRE_LEN) as u8;
+
+    matches!(bytes.first(), Some(&key) if key == element_key || key == text_key)
+}
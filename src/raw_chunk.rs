@@ -0,0 +1,494 @@
+use std::io::Cursor;
+
+use crate::axml_document::AxmlDocument;
+use crate::data_value_type::DataValueType;
+use crate::res_table::ResTable;
+use crate::string_pool::StringPool;
+use crate::write_ext::{
+    self,
+    CHUNK_HEADER_SIZE,
+};
+use crate::xml_types::XmlTypes;
+use crate::parse_res_table;
+use crate::error::{
+    AxmlError,
+    Result,
+};
+
+/* Chunks which are only made of other chunks, after their header */
+const CONTAINER_TYPES: [XmlTypes; 3] = [
+    XmlTypes::ResXmlType,
+    XmlTypes::ResTableType,
+    XmlTypes::ResTablePackageType,
+];
+
+/// Contents of a chunk, after its header
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum RawBody {
+    /* Children of a container chunk (a binary XML document, a resource
+     * table or a package), in order */
+    Chunks(Vec<RawPart>),
+
+    /* Data of any other chunk, as is */
+    Data(Vec<u8>),
+}
+
+/// A part of the body of a container chunk
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum RawPart {
+    Chunk(RawChunk),
+
+    /* Bytes which are not a chunk: padding between chunks, or data that
+     * does not have a valid chunk header */
+    Bytes(Vec<u8>),
+}
+
+/**
+ * A chunk kept exactly as it is in the input, for byte-exact round trips.
+ *
+ * Nothing is interpreted beyond the common chunk header: the rest of the
+ * header (including fields added by newer versions of the format) and the
+ * data are kept verbatim, and so are padding, unused string pool entries
+ * and chunks of unknown types. Only the sizes of the header are computed
+ * again when the chunk is written, so that a chunk can be changed or
+ * replaced (e.g., by a string pool from `encode_string_pool`) without
+ * touching the rest of the file.
+ */
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RawChunk {
+    /* Raw chunk type */
+    pub chunk_type: u16,
+
+    /* Rest of the header, after the type and the sizes */
+    pub header: Vec<u8>,
+
+    pub body: RawBody,
+}
+
+/// Header size and size of the chunk at `offset`, if it has a valid header
+/// and ends before `end`
+fn chunk_bounds(data: &[u8], offset: usize, end: usize) -> Option<(usize, usize)> {
+    let header = data.get(offset..offset + CHUNK_HEADER_SIZE as usize)?;
+    let header_size = u16::from_le_bytes([header[2], header[3]]) as usize;
+    let size = u32::from_le_bytes([header[4], header[5], header[6], header[7]]) as usize;
+
+    let is_valid = header_size >= CHUNK_HEADER_SIZE as usize
+                   && size >= header_size
+                   && offset.checked_add(size).is_some_and(|chunk_end| chunk_end <= end);
+    is_valid.then_some((header_size, size))
+}
+
+impl RawChunk {
+    /// Read the chunk at `offset`, which must have valid sizes and fit in
+    /// `data`
+    fn parse_at(data: &[u8], offset: usize) -> Result<Self> {
+        let (header_size, size) = chunk_bounds(data, offset, data.len()).ok_or_else(|| {
+            match data.get(offset + 2..offset + CHUNK_HEADER_SIZE as usize) {
+                Some(sizes) => AxmlError::SizeMismatch {
+                    offset: offset as u64,
+                    header_size: u16::from_le_bytes([sizes[0], sizes[1]]),
+                    size: u32::from_le_bytes([sizes[2], sizes[3], sizes[4], sizes[5]]),
+                },
+                None => AxmlError::Truncated { offset: offset as u64 },
+            }
+        })?;
+
+        let chunk_type = u16::from_le_bytes([data[offset], data[offset + 1]]);
+        let header = data[offset + CHUNK_HEADER_SIZE as usize..offset + header_size].to_vec();
+        let (start, end) = (offset + header_size, offset + size);
+
+        let is_container = CONTAINER_TYPES.iter().any(|&container| container as u16 == chunk_type);
+        let body = match is_container {
+            true => RawBody::Chunks(RawChunk::parse_parts(data, start, end)?),
+            false => RawBody::Data(data[start..end].to_vec()),
+        };
+
+        Ok(RawChunk {
+            chunk_type,
+            header,
+            body,
+        })
+    }
+
+    /// Read the children of a container chunk, between `start` and `end`
+    fn parse_parts(data: &[u8], start: usize, end: usize) -> Result<Vec<RawPart>> {
+        let mut parts = Vec::new();
+        let mut offset = start;
+
+        while offset < end {
+            /* Null padding between chunks is skipped two bytes at a time,
+             * like `ChunkWalker` does, and whatever follows a broken chunk
+             * header is kept as is */
+            let size = match chunk_bounds(data, offset, end) {
+                Some((_, size)) => size,
+                None if data[offset..end].starts_with(&[0, 0]) => {
+                    match parts.last_mut() {
+                        Some(RawPart::Bytes(padding)) => padding.extend_from_slice(&[0, 0]),
+                        _ => parts.push(RawPart::Bytes(vec![0, 0])),
+                    }
+                    offset += 2;
+                    continue;
+                },
+                None => {
+                    parts.push(RawPart::Bytes(data[offset..end].to_vec()));
+                    break;
+                },
+            };
+
+            parts.push(RawPart::Chunk(RawChunk::parse_at(&data[..end], offset)?));
+            offset += size;
+        }
+
+        Ok(parts)
+    }
+
+    /// Append the chunk to `out`
+    pub fn write(&self, out: &mut Vec<u8>) {
+        let mut body = Vec::new();
+        match &self.body {
+            RawBody::Chunks(parts) => for part in parts.iter() {
+                match part {
+                    RawPart::Chunk(chunk) => chunk.write(&mut body),
+                    RawPart::Bytes(bytes) => body.extend_from_slice(bytes),
+                }
+            },
+            RawBody::Data(data) => body.extend_from_slice(data),
+        }
+
+        write_ext::write_chunk(out, self.chunk_type, &self.header, &body);
+    }
+
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut out = Vec::new();
+        self.write(&mut out);
+        out
+    }
+
+    /// Direct children of a container chunk, empty for other chunks
+    pub fn children(&self) -> impl Iterator<Item = &RawChunk> + '_ {
+        let parts = match &self.body {
+            RawBody::Chunks(parts) => parts.as_slice(),
+            RawBody::Data(_) => &[],
+        };
+
+        parts.iter().filter_map(|part| match part {
+            RawPart::Chunk(chunk) => Some(chunk),
+            RawPart::Bytes(_) => None,
+        })
+    }
+
+    /// First chunk of the given type in this subtree (this chunk included),
+    /// in the order of the file
+    pub fn find(&self, chunk_type: XmlTypes) -> Option<&RawChunk> {
+        if self.chunk_type == chunk_type as u16 {
+            return Some(self);
+        }
+
+        self.children().find_map(|child| child.find(chunk_type))
+    }
+
+    /// First chunk of the given type in this subtree, to change or replace it
+    pub fn find_mut(&mut self, chunk_type: XmlTypes) -> Option<&mut RawChunk> {
+        if self.chunk_type == chunk_type as u16 {
+            return Some(self);
+        }
+
+        match &mut self.body {
+            RawBody::Chunks(parts) => parts.iter_mut().find_map(|part| match part {
+                RawPart::Chunk(chunk) => chunk.find_mut(chunk_type),
+                RawPart::Bytes(_) => None,
+            }),
+            RawBody::Data(_) => None,
+        }
+    }
+
+    /// Decoded view of a string pool chunk
+    pub fn string_pool(&self) -> Result<StringPool<'static>> {
+        let mut cursor = Cursor::new(self.to_bytes());
+        XmlTypes::parse_block_type(&mut cursor)?;
+        StringPool::from_buff(&mut cursor)
+    }
+}
+
+/**
+ * Lossless representation of a binary XML document or of a resource
+ * table: the tree of its chunks, see `RawChunk`, and whatever follows the
+ * root chunk in the file.
+ *
+ * Writing it back with `to_bytes` gives the exact bytes it was read from.
+ * The decoded view of the same data is given by `xml_document` and
+ * `res_table`.
+ */
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RawDocument {
+    pub root: RawChunk,
+
+    /* Bytes after the end of the root chunk */
+    pub trailing: Vec<u8>,
+}
+
+impl RawDocument {
+    pub fn parse(bytes: &[u8]) -> Result<Self> {
+        let root = RawChunk::parse_at(bytes, 0)?;
+        let root_size = chunk_bounds(bytes, 0, bytes.len()).map_or(0, |(_, size)| size);
+
+        Ok(RawDocument {
+            root,
+            trailing: bytes[root_size..].to_vec(),
+        })
+    }
+
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut out = self.root.to_bytes();
+        out.extend_from_slice(&self.trailing);
+        out
+    }
+
+    /// Decoded view of a binary XML document
+    pub fn xml_document(&self) -> Result<AxmlDocument> {
+        AxmlDocument::from_bytes(&self.to_bytes())
+    }
+
+    /// Decoded view of a resource table
+    pub fn res_table(&self) -> Result<ResTable> {
+        parse_res_table(Cursor::new(self.to_bytes()))
+    }
+}
+
+/// Children of a container chunk, with their offsets in the file given
+/// the offset of the container
+fn children_with_offsets(chunk: &RawChunk, offset: usize) -> Vec<(usize, &RawChunk)> {
+    let parts = match &chunk.body {
+        RawBody::Chunks(parts) => parts.as_slice(),
+        RawBody::Data(_) => &[],
+    };
+
+    let mut offset = offset + CHUNK_HEADER_SIZE as usize + chunk.header.len();
+    let mut children = Vec::new();
+    for part in parts.iter() {
+        match part {
+            RawPart::Chunk(child) => {
+                children.push((offset, child));
+                offset += child.to_bytes().len();
+            },
+            RawPart::Bytes(bytes) => offset += bytes.len(),
+        }
+    }
+
+    children
+}
+
+/* Field of an XML node chunk, with references to the string pool resolved */
+#[derive(Debug, PartialEq, Eq, PartialOrd, Ord)]
+enum NodeField {
+    Value(u32),
+    String(Option<String>),
+}
+
+/// Fields of an XML node chunk which survive decoding and encoding it
+/// again: everything but its comment, the line of an end node, the layout
+/// and the order of its attributes, with the strings instead of their
+/// indices in `pool`
+fn node_fields(chunk: &RawChunk, pool: &StringPool) -> Vec<NodeField> {
+    let data = match &chunk.body {
+        RawBody::Data(data) => data.as_slice(),
+        RawBody::Chunks(_) => &[],
+    };
+    let u16_at = |offset: usize| data.get(offset..offset + 2)
+                                     .map_or(0, |bytes| u16::from_le_bytes([bytes[0], bytes[1]]) as u32);
+    let u32_at = |offset: usize| data.get(offset..offset + 4)
+                                     .map_or(0, |bytes| u32::from_le_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]));
+    let string = |offset: usize| NodeField::String(pool.get(u32_at(offset)).map(|string| string.into_owned()));
+
+    /* Res_value at `offset`: its type, then its data */
+    let typed_value = |offset: usize| {
+        let data_type = data.get(offset + 3).copied().unwrap_or(0);
+        let value = match DataValueType::from_val(data_type) {
+            DataValueType::TypeString => string(offset + 4),
+            _ => NodeField::Value(u32_at(offset + 4)),
+        };
+        [NodeField::Value(data_type as u32), value]
+    };
+
+    /* Ends of elements and namespaces take the line of their start */
+    let line = chunk.header.get(..4).map_or(0, |bytes| u32::from_le_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]));
+    let chunk_type = XmlTypes::from_u16(chunk.chunk_type);
+    let mut fields = match chunk_type {
+        Some(XmlTypes::ResXmlEndElementType | XmlTypes::ResXmlEndNamespaceType) => Vec::new(),
+        _ => vec![NodeField::Value(line)],
+    };
+    match chunk_type {
+        Some(XmlTypes::ResXmlStartElementType) => {
+            fields.extend([string(0), string(4)]);
+
+            /* Attributes in any order, since they are sorted when they are
+             * written, with the id, class and style attributes by name */
+            let (start, size, count) = (u16_at(8) as usize, u16_at(10) as usize, u16_at(12) as usize);
+            let mut attributes: Vec<Vec<NodeField>> = (0..count).map(|index| start + index * size).map(|attr| {
+                let mut attribute = vec![string(attr), string(attr + 4), string(attr + 8)];
+                attribute.extend(typed_value(attr + 12));
+                attribute
+            }).collect();
+            for offset in [14, 16, 18] {
+                fields.extend(match u16_at(offset) as usize {
+                    0 => [NodeField::String(None), NodeField::String(None)],
+                    index => [string(start + (index - 1) * size), string(start + (index - 1) * size + 4)],
+                });
+            }
+            attributes.sort();
+            fields.extend(attributes.into_iter().flatten());
+        },
+        Some(XmlTypes::ResXmlCDataType) => {
+            fields.push(string(0));
+            fields.extend(typed_value(4));
+        },
+        /* Namespaces (prefix and URI) and end of elements (namespace and name) */
+        _ => fields.extend([string(0), string(4)]),
+    }
+
+    fields
+}
+
+/**
+ * Chunks of a binary XML document, grouped the way `AxmlDocument` keeps
+ * them: the string pool and the resource map, the nodes in order, and the
+ * other chunks in order, each with its offset in the file.
+ */
+#[derive(Default)]
+struct XmlChunks<'a> {
+    string_pool: Option<(usize, &'a RawChunk)>,
+    resource_map: Option<(usize, &'a RawChunk)>,
+    nodes: Vec<(usize, &'a RawChunk)>,
+    unknown: Vec<(usize, &'a RawChunk)>,
+}
+
+impl<'a> XmlChunks<'a> {
+    fn new(document: &'a RawDocument) -> Self {
+        let mut chunks = XmlChunks::default();
+        for (offset, chunk) in children_with_offsets(&document.root, 0) {
+            match XmlTypes::from_u16(chunk.chunk_type) {
+                Some(XmlTypes::ResStringPoolType) if chunks.string_pool.is_none() =>
+                    chunks.string_pool = Some((offset, chunk)),
+                Some(XmlTypes::ResXmlResourceMapType) if chunks.resource_map.is_none() =>
+                    chunks.resource_map = Some((offset, chunk)),
+                Some(XmlTypes::ResXmlStartNamespaceType | XmlTypes::ResXmlEndNamespaceType
+                     | XmlTypes::ResXmlStartElementType | XmlTypes::ResXmlEndElementType
+                     | XmlTypes::ResXmlCDataType) => chunks.nodes.push((offset, chunk)),
+                _ => chunks.unknown.push((offset, chunk)),
+            }
+        }
+
+        chunks
+    }
+
+    fn string_pool(&self) -> Result<StringPool<'static>> {
+        match self.string_pool {
+            Some((_, chunk)) => chunk.string_pool(),
+            None => Ok(StringPool::empty()),
+        }
+    }
+}
+
+/// Attribute names of the resource map of a binary XML document with their
+/// IDs, sorted, since the map is indexed like the string pool
+fn resource_ids(chunks: &XmlChunks, pool: &StringPool) -> Vec<(String, u32)> {
+    let ids = match chunks.resource_map {
+        Some((_, RawChunk { body: RawBody::Data(ids), .. })) => ids.as_slice(),
+        _ => &[],
+    };
+
+    let mut names: Vec<(String, u32)> = ids.chunks_exact(4).enumerate().map(|(index, id)| {
+        let name = pool.get(index as u32).map(|name| name.into_owned()).unwrap_or_default();
+        (name, u32::from_le_bytes([id[0], id[1], id[2], id[3]]))
+    }).collect();
+    names.sort();
+    names
+}
+
+/// Check that the chunks of `encoded`, the encoding of the decoded
+/// `original`, hold the same data: the same strings in the string pool
+/// (in any order, since `AxmlDocument` rebuilds it), the same IDs for the
+/// same attribute names in the resource map, the same nodes in the same
+/// order, and the same unknown chunks, which `AxmlDocument` moves after the
+/// resource map. Fails with
+/// `AxmlError::ChunkMismatch` at the first original chunk that differs.
+fn compare_xml_chunks(original: &RawDocument, encoded: &RawDocument) -> Result<()> {
+    let end = original.root.to_bytes().len();
+    let (original, encoded) = (XmlChunks::new(original), XmlChunks::new(encoded));
+    let (original_pool, encoded_pool) = (original.string_pool()?, encoded.string_pool()?);
+
+    /* The original chunk at `index`, or the end of the document with the
+     * type of the chunk it lacks */
+    let mismatch = |original: &[(usize, &RawChunk)], encoded: &[(usize, &RawChunk)], index: usize| {
+        let (offset, chunk_type) = match (original.get(index), encoded.get(index)) {
+            (Some(&(offset, chunk)), _) => (offset, chunk.chunk_type),
+            (None, Some(&(_, chunk))) => (end, chunk.chunk_type),
+            (None, None) => (end, 0),
+        };
+        AxmlError::ChunkMismatch { offset: offset as u64, chunk_type }
+    };
+
+    let strings = |pool: &StringPool| {
+        let mut strings: Vec<String> = pool.iter().map(|string| string.into_owned()).collect();
+        strings.sort();
+        strings.dedup();
+        strings
+    };
+    if strings(&original_pool) != strings(&encoded_pool) {
+        let pools = (original.string_pool.as_slice(), encoded.string_pool.as_slice());
+        return Err(mismatch(pools.0, pools.1, 0));
+    }
+
+    if resource_ids(&original, &original_pool) != resource_ids(&encoded, &encoded_pool) {
+        let maps = (original.resource_map.as_slice(), encoded.resource_map.as_slice());
+        return Err(mismatch(maps.0, maps.1, 0));
+    }
+
+    for index in 0..original.nodes.len().max(encoded.nodes.len()) {
+        let fields = |chunks: &XmlChunks, pool| chunks.nodes.get(index).map(|&(_, chunk)| {
+            (chunk.chunk_type, node_fields(chunk, pool))
+        });
+        if fields(&original, &original_pool) != fields(&encoded, &encoded_pool) {
+            return Err(mismatch(&original.nodes, &encoded.nodes, index));
+        }
+    }
+
+    for index in 0..original.unknown.len().max(encoded.unknown.len()) {
+        let chunk = |chunks: &[(usize, &RawChunk)]| chunks.get(index).map(|&(_, chunk)| chunk.to_bytes());
+        if chunk(&original.unknown) != chunk(&encoded.unknown) {
+            return Err(mismatch(&original.unknown, &encoded.unknown, index));
+        }
+    }
+
+    Ok(())
+}
+
+/// Check that a binary XML document or a resource table round-trips.
+///
+/// The lossless representation must write it back byte for byte, which
+/// fails with `AxmlError::RoundtripMismatch` at the first byte that
+/// differs. It must then decode as well; a binary XML document is encoded
+/// again from its decoded view, `AxmlDocument`, and compared with the
+/// original chunk by chunk, see `compare_xml_chunks`. Resource tables are
+/// only decoded, since there is no encoder for them.
+pub fn verify_roundtrip(bytes: &[u8]) -> Result<()> {
+    let document = RawDocument::parse(bytes)?;
+    let written = document.to_bytes();
+
+    if written != bytes {
+        let offset = written.iter()
+                            .zip(bytes.iter())
+                            .position(|(written, original)| written != original)
+                            .unwrap_or(written.len().min(bytes.len()));
+        return Err(AxmlError::RoundtripMismatch { offset: offset as u64 });
+    }
+
+    if document.root.chunk_type == XmlTypes::ResTableType as u16 {
+        document.res_table()?;
+    } else {
+        let encoded = RawDocument::parse(&document.xml_document()?.to_bytes())?;
+        compare_xml_chunks(&document, &encoded)?;
+    }
+
+    Ok(())
+}
@@ -0,0 +1,35 @@
+use std::io::Cursor;
+use byteorder::{
+    LittleEndian,
+    ReadBytesExt
+};
+use crate::error::{
+    AxmlError,
+    Result,
+};
+
+/* Little-endian integer readers which report a short read as an
+ * `AxmlError::Truncated` error carrying the offset of the failed read,
+ * instead of a bare `std::io::Error`. */
+pub(crate) trait ReadExt {
+    fn read_u8_le(&mut self) -> Result<u8>;
+    fn read_u16_le(&mut self) -> Result<u16>;
+    fn read_u32_le(&mut self) -> Result<u32>;
+}
+
+impl<T: AsRef<[u8]>> ReadExt for Cursor<T> {
+    fn read_u8_le(&mut self) -> Result<u8> {
+        let offset = self.position();
+        self.read_u8().map_err(|_| AxmlError::Truncated { offset })
+    }
+
+    fn read_u16_le(&mut self) -> Result<u16> {
+        let offset = self.position();
+        self.read_u16::<LittleEndian>().map_err(|_| AxmlError::Truncated { offset })
+    }
+
+    fn read_u32_le(&mut self) -> Result<u32> {
+        let offset = self.position();
+        self.read_u32::<LittleEndian>().map_err(|_| AxmlError::Truncated { offset })
+    }
+}
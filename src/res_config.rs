@@ -0,0 +1,700 @@
+use std::fmt;
+use std::io::Cursor;
+
+use crate::read_ext::ReadExt;
+use crate::error::{
+    AxmlError,
+    Result,
+};
+
+/* Masks and values of the fields packing several settings */
+const MASK_SCREENSIZE: u8 = 0x0f;
+const MASK_SCREENLONG: u8 = 0x30;
+const MASK_LAYOUTDIR: u8 = 0xc0;
+const MASK_SCREENROUND: u8 = 0x03;
+const MASK_WIDE_COLOR_GAMUT: u8 = 0x03;
+const MASK_HDR: u8 = 0x0c;
+const MASK_UI_MODE_TYPE: u8 = 0x0f;
+const MASK_UI_MODE_NIGHT: u8 = 0x30;
+const MASK_KEYSHIDDEN: u8 = 0x03;
+const MASK_NAVHIDDEN: u8 = 0x0c;
+const MASK_GRAMMATICAL_GENDER: u8 = 0x03;
+
+const SCREENSIZE_NORMAL: u8 = 0x02;
+
+const KEYSHIDDEN_NO: u8 = 0x01;
+const KEYSHIDDEN_SOFT: u8 = 0x03;
+
+const DENSITY_DEFAULT: u16 = 0;
+const DENSITY_MEDIUM: u16 = 160;
+const DENSITY_ANY: u16 = 0xfffe;
+const DENSITY_NONE: u16 = 0xffff;
+
+/**
+ * Describes a particular resource configuration, i.e., the set of
+ * qualifiers (locale, density, orientation, night mode, SDK version, etc.)
+ * for which the entries of a ResTable_type are defined.
+ *
+ * A value of 0 for a field means "any": the configuration does not depend
+ * on that setting. The same structure is used to describe the settings of a
+ * device when looking for the best resource for it.
+ */
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ResConfig {
+    /* Number of bytes in this structure, as stored in the file */
+    pub size: u32,
+
+    /* Mobile country code and mobile network code */
+    pub mcc: u16,
+    pub mnc: u16,
+
+    /* ISO-639 language code and ISO-3166 region code. Three-letter codes
+     * are packed on two bytes, with the high bit of the first byte set. */
+    pub language: [u8; 2],
+    pub country: [u8; 2],
+
+    pub orientation: u8,
+    pub touchscreen: u8,
+    pub density: u16,
+
+    pub keyboard: u8,
+    pub navigation: u8,
+    pub input_flags: u8,
+
+    /* Screen size in pixels */
+    pub screen_width: u16,
+    pub screen_height: u16,
+
+    pub sdk_version: u16,
+    pub minor_version: u16,
+
+    /* Screen size class, long screen and layout direction */
+    pub screen_layout: u8,
+
+    /* UI mode type and night mode */
+    pub ui_mode: u8,
+    pub smallest_screen_width_dp: u16,
+
+    pub screen_width_dp: u16,
+    pub screen_height_dp: u16,
+
+    /* ISO-15924 script code and BCP 47 variant of the locale */
+    pub locale_script: [u8; 4],
+    pub locale_variant: [u8; 8],
+
+    /* Round screen */
+    pub screen_layout2: u8,
+
+    /* Wide colour gamut and HDR */
+    pub color_mode: u8,
+
+    /* Grammatical gender */
+    pub grammatical_inflection: u8,
+
+    /* Whether the script was computed from the language (and should not be
+     * part of the qualifiers) */
+    pub locale_script_was_computed: bool,
+
+    /* Unicode numbering system of the locale */
+    pub locale_numbering_system: [u8; 8],
+}
+
+impl ResConfig {
+    /// Parse a ResTable_config. Fields beyond the size announced in the
+    /// structure are left to 0, and unknown trailing fields are skipped.
+    pub fn from_buff<T: AsRef<[u8]>>(axml_buff: &mut Cursor<T>) -> Result<Self> {
+        let offset = axml_buff.position();
+        let size = axml_buff.read_u32_le()?;
+
+        let end = offset + size as u64;
+        let raw = axml_buff.get_ref().as_ref()
+                           .get(offset as usize..end as usize)
+                           .ok_or(AxmlError::Truncated { offset })?
+                           .to_vec();
+        axml_buff.set_position(end);
+
+        /* Read a field if it is within the announced size */
+        let byte = |index: usize| raw.get(index).copied().unwrap_or(0);
+        let short = |index: usize| u16::from_le_bytes([byte(index), byte(index + 1)]);
+        let bytes = |index: usize, out: &mut [u8]| {
+            for (i, out_byte) in out.iter_mut().enumerate() {
+                *out_byte = byte(index + i);
+            }
+        };
+
+        let mut config = ResConfig {
+            size,
+            mcc: short(4),
+            mnc: short(6),
+            orientation: byte(12),
+            touchscreen: byte(13),
+            density: short(14),
+            keyboard: byte(16),
+            navigation: byte(17),
+            input_flags: byte(18),
+            grammatical_inflection: byte(19),
+            screen_width: short(20),
+            screen_height: short(22),
+            sdk_version: short(24),
+            minor_version: short(26),
+            screen_layout: byte(28),
+            ui_mode: byte(29),
+            smallest_screen_width_dp: short(30),
+            screen_width_dp: short(32),
+            screen_height_dp: short(34),
+            screen_layout2: byte(48),
+            color_mode: byte(49),
+            locale_script_was_computed: byte(52) != 0,
+            ..ResConfig::default()
+        };
+        bytes(8, &mut config.language);
+        bytes(10, &mut config.country);
+        bytes(36, &mut config.locale_script);
+        bytes(40, &mut config.locale_variant);
+        bytes(53, &mut config.locale_numbering_system);
+
+        Ok(config)
+    }
+
+    /// Whether this is the default configuration, i.e., it has no qualifier
+    pub fn is_default(&self) -> bool {
+        *self == ResConfig { size: self.size, ..ResConfig::default() }
+    }
+
+    /// Language of the locale, e.g., `fr`
+    pub fn language(&self) -> String {
+        unpack_language_or_region(self.language, b'a')
+    }
+
+    /// Region of the locale, e.g., `CA`
+    pub fn region(&self) -> String {
+        unpack_language_or_region(self.country, b'0')
+    }
+
+    /// Name of the resource directory for a given type, e.g.,
+    /// `values-fr-rCA-night-v29` for `values`
+    pub fn dir_name(&self, base: &str) -> String {
+        match self.is_default() {
+            true => base.to_string(),
+            false => format!("{}-{}", base, self),
+        }
+    }
+
+    /// Whether the resources of this configuration can be used on a device
+    /// with the configuration `device`
+    pub fn matches(&self, device: &ResConfig) -> bool {
+        if self.mcc != 0 && self.mcc != device.mcc {
+            return false;
+        }
+        if self.mnc != 0 && self.mnc != device.mnc {
+            return false;
+        }
+
+        if self.language[0] != 0 && self.language != device.language {
+            return false;
+        }
+        if self.country[0] != 0 && self.country != device.country {
+            return false;
+        }
+        if self.locale_script[0] != 0 && !self.locale_script_was_computed
+            && device.locale_script[0] != 0 && self.locale_script != device.locale_script {
+            return false;
+        }
+        if self.locale_variant[0] != 0 && self.locale_variant != device.locale_variant {
+            return false;
+        }
+
+        let masked_mismatch = |mine: u8, theirs: u8, mask: u8| {
+            mine & mask != 0 && mine & mask != theirs & mask
+        };
+
+        if masked_mismatch(self.screen_layout, device.screen_layout, MASK_LAYOUTDIR)
+            || masked_mismatch(self.screen_layout, device.screen_layout, MASK_SCREENLONG)
+            || masked_mismatch(self.screen_layout2, device.screen_layout2, MASK_SCREENROUND)
+            || masked_mismatch(self.color_mode, device.color_mode, MASK_WIDE_COLOR_GAMUT)
+            || masked_mismatch(self.color_mode, device.color_mode, MASK_HDR)
+            || masked_mismatch(self.ui_mode, device.ui_mode, MASK_UI_MODE_TYPE)
+            || masked_mismatch(self.ui_mode, device.ui_mode, MASK_UI_MODE_NIGHT)
+            || masked_mismatch(self.input_flags, device.input_flags, MASK_NAVHIDDEN)
+            || masked_mismatch(self.grammatical_inflection, device.grammatical_inflection, MASK_GRAMMATICAL_GENDER) {
+            return false;
+        }
+
+        /* Screen size classes are "at most": a large layout can be used on an xlarge screen */
+        let my_size = self.screen_layout & MASK_SCREENSIZE;
+        if my_size != 0 && my_size > device.screen_layout & MASK_SCREENSIZE {
+            return false;
+        }
+
+        /* Screen dimensions are also "at most" */
+        if (self.smallest_screen_width_dp != 0 && self.smallest_screen_width_dp > device.smallest_screen_width_dp)
+            || (self.screen_width_dp != 0 && self.screen_width_dp > device.screen_width_dp)
+            || (self.screen_height_dp != 0 && self.screen_height_dp > device.screen_height_dp)
+            || (self.screen_width != 0 && self.screen_width > device.screen_width)
+            || (self.screen_height != 0 && self.screen_height > device.screen_height) {
+            return false;
+        }
+
+        let field_mismatch = |mine: u8, theirs: u8| mine != 0 && mine != theirs;
+        if field_mismatch(self.orientation, device.orientation)
+            || field_mismatch(self.touchscreen, device.touchscreen)
+            || field_mismatch(self.keyboard, device.keyboard)
+            || field_mismatch(self.navigation, device.navigation) {
+            return false;
+        }
+
+        /* A request for "keys not hidden" is also satisfied by "soft keys" */
+        let my_keys_hidden = self.input_flags & MASK_KEYSHIDDEN;
+        let device_keys_hidden = device.input_flags & MASK_KEYSHIDDEN;
+        if my_keys_hidden != 0 && my_keys_hidden != device_keys_hidden
+            && !(my_keys_hidden == KEYSHIDDEN_NO && device_keys_hidden == KEYSHIDDEN_SOFT) {
+            return false;
+        }
+
+        /* The platform version is "at least" */
+        if self.sdk_version != 0 && self.sdk_version > device.sdk_version {
+            return false;
+        }
+        if self.minor_version != 0 && self.minor_version != device.minor_version {
+            return false;
+        }
+
+        true
+    }
+
+    /// Whether this configuration is a better match than `other` for the
+    /// device configuration `device`. Both configurations are expected to
+    /// match the device. This follows the precedence order of Android's
+    /// `ResTable_config::isBetterThan`.
+    pub fn is_better_than(&self, other: &ResConfig, device: &ResConfig) -> bool {
+        /* For the fields which must be equal to the device's to match, the
+         * configuration which specifies the field is the better one */
+        let specified = |mine: u32, theirs: u32, requested: u32| -> Option<bool> {
+            match mine != theirs && requested != 0 {
+                true => Some(mine != 0),
+                false => None,
+            }
+        };
+
+        if let Some(better) = specified(self.mcc as u32, other.mcc as u32, device.mcc as u32)
+            .or_else(|| specified(self.mnc as u32, other.mnc as u32, device.mnc as u32)) {
+            return better;
+        }
+
+        if let Some(better) = self.is_locale_better_than(other, device) {
+            return better;
+        }
+
+        if let Some(better) = specified((self.grammatical_inflection & MASK_GRAMMATICAL_GENDER) as u32,
+                                        (other.grammatical_inflection & MASK_GRAMMATICAL_GENDER) as u32,
+                                        (device.grammatical_inflection & MASK_GRAMMATICAL_GENDER) as u32)
+            .or_else(|| specified((self.screen_layout & MASK_LAYOUTDIR) as u32,
+                                  (other.screen_layout & MASK_LAYOUTDIR) as u32,
+                                  (device.screen_layout & MASK_LAYOUTDIR) as u32)) {
+            return better;
+        }
+
+        /* For "at most" dimensions, the closest to the device (i.e., the
+         * largest) is the better one */
+        if self.smallest_screen_width_dp != other.smallest_screen_width_dp {
+            return self.smallest_screen_width_dp > other.smallest_screen_width_dp;
+        }
+
+        if self.screen_width_dp != other.screen_width_dp || self.screen_height_dp != other.screen_height_dp {
+            let mut my_delta = 0;
+            let mut other_delta = 0;
+            if device.screen_width_dp != 0 {
+                my_delta += device.screen_width_dp as i32 - self.screen_width_dp as i32;
+                other_delta += device.screen_width_dp as i32 - other.screen_width_dp as i32;
+            }
+            if device.screen_height_dp != 0 {
+                my_delta += device.screen_height_dp as i32 - self.screen_height_dp as i32;
+                other_delta += device.screen_height_dp as i32 - other.screen_height_dp as i32;
+            }
+            if my_delta != other_delta {
+                return my_delta < other_delta;
+            }
+        }
+
+        /* An unspecified screen size counts as normal, unless the device's
+         * is small; if both are then equal, the specified one is better */
+        let my_size = self.screen_layout & MASK_SCREENSIZE;
+        let other_size = other.screen_layout & MASK_SCREENSIZE;
+        let device_size = device.screen_layout & MASK_SCREENSIZE;
+        if my_size != other_size && device_size != 0 {
+            let size_or_normal = |size: u8| match (size, device_size >= SCREENSIZE_NORMAL) {
+                (0, true) => SCREENSIZE_NORMAL,
+                (size, _) => size,
+            };
+            return match size_or_normal(my_size) == size_or_normal(other_size) {
+                true => my_size != 0,
+                false => size_or_normal(my_size) > size_or_normal(other_size),
+            };
+        }
+
+        if let Some(better) = specified((self.screen_layout & MASK_SCREENLONG) as u32,
+                                        (other.screen_layout & MASK_SCREENLONG) as u32,
+                                        (device.screen_layout & MASK_SCREENLONG) as u32)
+            .or_else(|| specified((self.screen_layout2 & MASK_SCREENROUND) as u32,
+                                  (other.screen_layout2 & MASK_SCREENROUND) as u32,
+                                  (device.screen_layout2 & MASK_SCREENROUND) as u32))
+            .or_else(|| specified((self.color_mode & MASK_HDR) as u32,
+                                  (other.color_mode & MASK_HDR) as u32,
+                                  (device.color_mode & MASK_HDR) as u32))
+            .or_else(|| specified((self.color_mode & MASK_WIDE_COLOR_GAMUT) as u32,
+                                  (other.color_mode & MASK_WIDE_COLOR_GAMUT) as u32,
+                                  (device.color_mode & MASK_WIDE_COLOR_GAMUT) as u32))
+            .or_else(|| specified(self.orientation as u32, other.orientation as u32, device.orientation as u32))
+            .or_else(|| specified((self.ui_mode & MASK_UI_MODE_TYPE) as u32,
+                                  (other.ui_mode & MASK_UI_MODE_TYPE) as u32,
+                                  (device.ui_mode & MASK_UI_MODE_TYPE) as u32))
+            .or_else(|| specified((self.ui_mode & MASK_UI_MODE_NIGHT) as u32,
+                                  (other.ui_mode & MASK_UI_MODE_NIGHT) as u32,
+                                  (device.ui_mode & MASK_UI_MODE_NIGHT) as u32)) {
+            return better;
+        }
+
+        if self.density != other.density {
+            return self.is_density_better_than(other, device);
+        }
+
+        if let Some(better) = specified(self.touchscreen as u32, other.touchscreen as u32, device.touchscreen as u32)
+            .or_else(|| specified((self.input_flags & MASK_KEYSHIDDEN) as u32,
+                                  (other.input_flags & MASK_KEYSHIDDEN) as u32,
+                                  (device.input_flags & MASK_KEYSHIDDEN) as u32))
+            .or_else(|| specified(self.keyboard as u32, other.keyboard as u32, device.keyboard as u32))
+            .or_else(|| specified((self.input_flags & MASK_NAVHIDDEN) as u32,
+                                  (other.input_flags & MASK_NAVHIDDEN) as u32,
+                                  (device.input_flags & MASK_NAVHIDDEN) as u32))
+            .or_else(|| specified(self.navigation as u32, other.navigation as u32, device.navigation as u32)) {
+            return better;
+        }
+
+        /* Screen sizes in pixels are "at most" as well, and only compared
+         * in the dimensions the device gives */
+        if self.screen_width != other.screen_width || self.screen_height != other.screen_height {
+            let mut my_delta = 0;
+            let mut other_delta = 0;
+            if device.screen_width != 0 {
+                my_delta += device.screen_width as i32 - self.screen_width as i32;
+                other_delta += device.screen_width as i32 - other.screen_width as i32;
+            }
+            if device.screen_height != 0 {
+                my_delta += device.screen_height as i32 - self.screen_height as i32;
+                other_delta += device.screen_height as i32 - other.screen_height as i32;
+            }
+            if my_delta != other_delta {
+                return my_delta < other_delta;
+            }
+        }
+
+        if self.sdk_version != other.sdk_version && device.sdk_version != 0 {
+            return self.sdk_version > other.sdk_version;
+        }
+        if self.minor_version != other.minor_version && device.minor_version != 0 {
+            return self.minor_version != 0;
+        }
+
+        false
+    }
+
+    /// Compare the locales of two configurations, `None` if they are equally good
+    fn is_locale_better_than(&self, other: &ResConfig, device: &ResConfig) -> Option<bool> {
+        if device.language[0] == 0 {
+            return None;
+        }
+
+        if self.language != other.language {
+            return Some(self.language[0] != 0);
+        }
+
+        if self.country != other.country && device.country[0] != 0 {
+            return Some(self.country[0] != 0);
+        }
+
+        if self.locale_script != other.locale_script && device.locale_script[0] != 0 {
+            return Some(self.locale_script == device.locale_script);
+        }
+
+        if self.locale_variant != other.locale_variant {
+            return Some(self.locale_variant == device.locale_variant);
+        }
+
+        None
+    }
+
+    /// Compare the densities of two configurations. The closest density
+    /// above the device's is preferred, as scaling down looks better than
+    /// scaling up; a lower density is only used if it is much closer.
+    fn is_density_better_than(&self, other: &ResConfig, device: &ResConfig) -> bool {
+        let density_or_medium = |density: u16| match density {
+            DENSITY_DEFAULT => DENSITY_MEDIUM,
+            density => density,
+        };
+
+        let my_density = density_or_medium(self.density);
+        let other_density = density_or_medium(other.density);
+
+        if my_density == DENSITY_ANY {
+            return true;
+        }
+        if other_density == DENSITY_ANY {
+            return false;
+        }
+
+        /* A device which accepts any density is treated as a medium
+         * density one */
+        let requested = match device.density {
+            DENSITY_DEFAULT | DENSITY_ANY => DENSITY_MEDIUM as i64,
+            density => density as i64,
+        };
+
+        let (high, low, i_am_bigger) = match my_density >= other_density {
+            true => (my_density as i64, other_density as i64, true),
+            false => (other_density as i64, my_density as i64, false),
+        };
+
+        /* Both are lower than requested: take the higher one */
+        if requested >= high {
+            return i_am_bigger;
+        }
+
+        /* Both are higher than requested: take the lower one */
+        if low >= requested {
+            return !i_am_bigger;
+        }
+
+        /* One is lower and one is higher: take the lower only if scaling
+         * it up is less costly than scaling the higher one down */
+        if (2 * low - requested) * high > requested * requested {
+            !i_am_bigger
+        } else {
+            i_am_bigger
+        }
+    }
+
+    /// Qualifier of the locale, either in the legacy form (`fr-rCA`) or in
+    /// BCP 47 form (`b+sr+Latn`) if a script or variant is specified
+    fn locale_qualifier(&self) -> Option<String> {
+        if self.language[0] == 0 {
+            return None;
+        }
+
+        let script_provided = self.locale_script[0] != 0 && !self.locale_script_was_computed;
+        let variant = c_string(&self.locale_variant);
+        let numbering = c_string(&self.locale_numbering_system);
+
+        if !script_provided && variant.is_empty() && numbering.is_empty() {
+            return match self.country[0] {
+                0 => Some(self.language()),
+                _ => Some(format!("{}-r{}", self.language(), self.region())),
+            };
+        }
+
+        let mut qualifier = format!("b+{}", self.language());
+        if script_provided {
+            qualifier.push('+');
+            qualifier.push_str(&c_string(&self.locale_script));
+        }
+        if self.country[0] != 0 {
+            qualifier.push('+');
+            qualifier.push_str(&self.region());
+        }
+        if !variant.is_empty() {
+            qualifier.push('+');
+            qualifier.push_str(&variant);
+        }
+        if !numbering.is_empty() {
+            qualifier.push_str("+u+nu+");
+            qualifier.push_str(&numbering);
+        }
+
+        Some(qualifier)
+    }
+
+    /// Qualifiers of the configuration, in the order used for resource
+    /// directory names
+    pub fn qualifiers(&self) -> Vec<String> {
+        let mut qualifiers = Vec::new();
+        let mut push = |qualifier: &str| qualifiers.push(qualifier.to_string());
+
+        if self.mcc != 0 {
+            push(&format!("mcc{}", self.mcc));
+        }
+        if self.mnc != 0 {
+            push(&format!("mnc{}", self.mnc));
+        }
+        if let Some(locale) = self.locale_qualifier() {
+            push(&locale);
+        }
+
+        match self.grammatical_inflection & MASK_GRAMMATICAL_GENDER {
+            1 => push("neuter"),
+            2 => push("feminine"),
+            3 => push("masculine"),
+            _ => { },
+        }
+
+        match self.screen_layout & MASK_LAYOUTDIR {
+            0x40 => push("ldltr"),
+            0x80 => push("ldrtl"),
+            _ => { },
+        }
+
+        if self.smallest_screen_width_dp != 0 {
+            push(&format!("sw{}dp", self.smallest_screen_width_dp));
+        }
+        if self.screen_width_dp != 0 {
+            push(&format!("w{}dp", self.screen_width_dp));
+        }
+        if self.screen_height_dp != 0 {
+            push(&format!("h{}dp", self.screen_height_dp));
+        }
+
+        match self.screen_layout & MASK_SCREENSIZE {
+            0x01 => push("small"),
+            0x02 => push("normal"),
+            0x03 => push("large"),
+            0x04 => push("xlarge"),
+            _ => { },
+        }
+
+        match self.screen_layout & MASK_SCREENLONG {
+            0x10 => push("notlong"),
+            0x20 => push("long"),
+            _ => { },
+        }
+
+        match self.screen_layout2 & MASK_SCREENROUND {
+            0x01 => push("notround"),
+            0x02 => push("round"),
+            _ => { },
+        }
+
+        match self.color_mode & MASK_WIDE_COLOR_GAMUT {
+            0x01 => push("nowidecg"),
+            0x02 => push("widecg"),
+            _ => { },
+        }
+
+        match self.color_mode & MASK_HDR {
+            0x04 => push("lowdr"),
+            0x08 => push("highdr"),
+            _ => { },
+        }
+
+        match self.orientation {
+            1 => push("port"),
+            2 => push("land"),
+            3 => push("square"),
+            _ => { },
+        }
+
+        match self.ui_mode & MASK_UI_MODE_TYPE {
+            0x02 => push("desk"),
+            0x03 => push("car"),
+            0x04 => push("television"),
+            0x05 => push("appliance"),
+            0x06 => push("watch"),
+            0x07 => push("vrheadset"),
+            _ => { },
+        }
+
+        match self.ui_mode & MASK_UI_MODE_NIGHT {
+            0x10 => push("notnight"),
+            0x20 => push("night"),
+            _ => { },
+        }
+
+        match self.density {
+            DENSITY_DEFAULT => { },
+            120 => push("ldpi"),
+            160 => push("mdpi"),
+            213 => push("tvdpi"),
+            240 => push("hdpi"),
+            320 => push("xhdpi"),
+            480 => push("xxhdpi"),
+            640 => push("xxxhdpi"),
+            DENSITY_ANY => push("anydpi"),
+            DENSITY_NONE => push("nodpi"),
+            density => push(&format!("{}dpi", density)),
+        }
+
+        match self.touchscreen {
+            1 => push("notouch"),
+            2 => push("stylus"),
+            3 => push("finger"),
+            _ => { },
+        }
+
+        match self.input_flags & MASK_KEYSHIDDEN {
+            0x01 => push("keysexposed"),
+            0x02 => push("keyshidden"),
+            0x03 => push("keyssoft"),
+            _ => { },
+        }
+
+        match self.keyboard {
+            1 => push("nokeys"),
+            2 => push("qwerty"),
+            3 => push("12key"),
+            _ => { },
+        }
+
+        match self.input_flags & MASK_NAVHIDDEN {
+            0x04 => push("navexposed"),
+            0x08 => push("navhidden"),
+            _ => { },
+        }
+
+        match self.navigation {
+            1 => push("nonav"),
+            2 => push("dpad"),
+            3 => push("trackball"),
+            4 => push("wheel"),
+            _ => { },
+        }
+
+        if self.screen_width != 0 && self.screen_height != 0 {
+            push(&format!("{}x{}", self.screen_width, self.screen_height));
+        }
+
+        if self.sdk_version != 0 {
+            push(&format!("v{}", self.sdk_version));
+        }
+
+        qualifiers
+    }
+}
+
+/* Qualifiers separated by dashes, e.g., `fr-rCA-night-v29`. The default
+ * configuration is shown as an empty string. */
+impl fmt::Display for ResConfig {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.qualifiers().join("-"))
+    }
+}
+
+/// Unpack a language (`base` = 'a') or region (`base` = '0') code. Three
+/// letter codes are packed on two bytes as three 5-bit values.
+fn unpack_language_or_region(packed: [u8; 2], base: u8) -> String {
+    if packed[0] & 0x80 != 0 {
+        let first = packed[1] & 0x1f;
+        let second = ((packed[1] & 0xe0) >> 5) + ((packed[0] & 0x03) << 3);
+        let third = (packed[0] & 0x7c) >> 2;
+
+        return [first + base, second + base, third + base].iter()
+                                                          .map(|&c| c as char)
+                                                          .collect();
+    }
+
+    c_string(&packed)
+}
+
+/// Decode a fixed-size, `\0`-padded ASCII field
+fn c_string(raw: &[u8]) -> String {
+    raw.iter()
+       .take_while(|&&c| c != 0)
+       .map(|&c| c as char)
+       .collect()
+}
@@ -0,0 +1,59 @@
+use std::fmt;
+
+/* Package ID of the application's own resources */
+const APP_PACKAGE_ID: u32 = 0x7f;
+
+/// Symbolic name of a resource, e.g., `android:style/Theme`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ResourceName {
+    /// Package the resource belongs to, e.g., `android`
+    pub package: String,
+
+    /// Type of the resource, e.g., `string`
+    pub type_name: String,
+
+    /// Name of the entry, e.g., `app_name`
+    pub entry: String,
+}
+
+impl fmt::Display for ResourceName {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}:{}/{}", self.package, self.type_name, self.entry)
+    }
+}
+
+/**
+ * Something that can give a symbolic name to a resource ID, like the
+ * resource table of an app or of the Android framework.
+ */
+pub trait ResourceResolver {
+    /// Name of the resource with the given `0xPPTTEEEE` ID, if known
+    fn resource_name(&self, id: u32) -> Option<ResourceName>;
+}
+
+/* Several resolvers can be chained, the first one that knows an ID wins */
+impl ResourceResolver for Vec<&dyn ResourceResolver> {
+    fn resource_name(&self, id: u32) -> Option<ResourceName> {
+        self.iter().find_map(|resolver| resolver.resource_name(id))
+    }
+}
+
+/// Format a reference to a resource the way aapt does. `sigil` is `@` for
+/// references and `?` for theme attributes. Without a resolver, or if the
+/// resolver does not know the ID, the ID is printed in hexadecimal
+/// (`@0x7f0a0012`). Otherwise, the symbolic name is used, without package
+/// for the application's own resources (`@string/app_name`) and with it for
+/// other packages (`@android:style/Theme`).
+pub fn format_reference(sigil: char, id: u32, resolver: Option<&dyn ResourceResolver>) -> String {
+    /* A reference to 0 is how "@null" is encoded */
+    if id == 0 && sigil == '@' {
+        return "@null".to_string();
+    }
+
+    match resolver.and_then(|resolver| resolver.resource_name(id)) {
+        Some(name) if id >> 24 == APP_PACKAGE_ID =>
+            format!("{}{}/{}", sigil, name.type_name, name.entry),
+        Some(name) => format!("{}{}", sigil, name),
+        None => format!("{}0x{:08x}", sigil, id),
+    }
+}
@@ -1,17 +1,88 @@
 #![allow(dead_code)]
 
 use crate::chunk_header::ChunkHeader;
+use crate::chunk_walker::{
+    ChunkWalker,
+    UnknownChunk,
+};
+use crate::data_value_type::DataValueType;
+use crate::res_config::ResConfig;
+use crate::res_resolver::{
+    ResourceName,
+    ResourceResolver,
+};
+use crate::res_value::ResValue;
 use crate::string_pool::StringPool;
 use crate::xml_types::XmlTypes;
 
-use std::io::{
-    Error,
-    Cursor,
-};
-use byteorder::{
-    LittleEndian,
-    ReadBytesExt
+use crate::read_ext::ReadExt;
+use crate::error::Result;
+
+use std::collections::{
+    BTreeMap,
+    HashMap,
 };
+use std::borrow::Cow;
+use std::io::Cursor;
+
+/* Marker for a missing entry in the offsets of a ResTable_type */
+const NO_ENTRY: u32 = 0xffffffff;
+const NO_ENTRY_16: u16 = 0xffff;
+
+/* Flags of a ResTable_type */
+const TYPE_FLAG_SPARSE: u8 = 0x01;
+const TYPE_FLAG_OFFSET16: u8 = 0x02;
+
+/* Flags of a ResTable_entry */
+const ENTRY_FLAG_COMPLEX: u16 = 0x0001;
+const ENTRY_FLAG_COMPACT: u16 = 0x0008;
+
+/* Types of resources whose value can be substituted for a reference */
+const SUBSTITUTABLE_TYPES: [&str; 3] = ["string", "bool", "integer"];
+
+/* Maximum number of references followed when substituting a value, to
+ * avoid looping forever on circular references */
+const MAX_REFERENCE_DEPTH: usize = 16;
+
+/* Key of the value of an `attr` bag which holds the types of values the
+ * attribute accepts. Other internal keys (type 0x00) give its bounds, etc. */
+const ATTR_TYPE: u32 = 0x01000000;
+
+/* Types of values accepted by an attribute, as in ResTable_map */
+pub const FORMAT_ANY: u32 = 0x0000ffff;
+pub const FORMAT_REFERENCE: u32 = 1 << 0;
+pub const FORMAT_STRING: u32 = 1 << 1;
+pub const FORMAT_INTEGER: u32 = 1 << 2;
+pub const FORMAT_BOOLEAN: u32 = 1 << 3;
+pub const FORMAT_COLOR: u32 = 1 << 4;
+pub const FORMAT_FLOAT: u32 = 1 << 5;
+pub const FORMAT_DIMENSION: u32 = 1 << 6;
+pub const FORMAT_FRACTION: u32 = 1 << 7;
+pub const FORMAT_ENUM: u32 = 1 << 16;
+pub const FORMAT_FLAGS: u32 = 1 << 17;
+
+/* Size of the fixed part of a ResTable_package header, without type_id_offset
+ * which was added in later versions of the format */
+const PACKAGE_HEADER_SIZE_V1: u16 = 284;
+
+/// Decode a `\0`-terminated UTF-16 buffer, as used for package names
+fn decode_utf16_name(name: &[u16]) -> String {
+    let len = name.iter().position(|&unit| unit == 0).unwrap_or(name.len());
+    String::from_utf16_lossy(&name[..len])
+}
+
+/// Make a string value of a table refer to the same string in the global
+/// pool of another table, copying the string there if it is not yet.
+/// `copied` maps the indices in `from` to the indices in `to`.
+fn remap_string(value: &mut ResValue, from: &StringPool, to: &mut StringPool<'static>, copied: &mut HashMap<u32, u32>) {
+    if value.data_type != DataValueType::TypeString {
+        return;
+    }
+
+    value.data = *copied.entry(value.data).or_insert_with(|| {
+        to.push(from.get(value.data).unwrap_or_default().to_string())
+    });
+}
 
 /**
  * Header for a resource table
@@ -25,44 +96,305 @@ use byteorder::{
  * Specific entries within a resource table can be uniquely identified
  * with a single integer as defined by the ResTable_ref structure.
  */
+#[derive(Debug)]
 pub struct ResTable {
     /* Chunk header */
     header: ChunkHeader,
 
     /* The number of ResTable_package structures */
     pub package_count: u32,
+
+    /* Global pool of string values */
+    pub strings: StringPool<'static>,
+
+    /* Packages, in the order in which they appear in the table */
+    pub packages: Vec<ResTablePackage>,
+
+    /* Chunks that could not be decoded */
+    pub unknown_chunks: Vec<UnknownChunk>,
+
+    /* IDs of the resources by type and name, see `resource_id` */
+    ids_by_name: HashMap<(String, String), u32>,
 }
 
 impl ResTable {
-    pub fn parse(axml_buff: &mut Cursor<Vec<u8>>) {
+    pub fn parse<T: AsRef<[u8]>>(axml_buff: &mut Cursor<T>) -> Result<Self> {
 
         /* Go back 2 bytes, to account from the block type */
-        let initial_offset = axml_buff.position();
-        axml_buff.set_position(initial_offset - 2);
+        let initial_offset = axml_buff.position() - 2;
+        axml_buff.set_position(initial_offset);
 
         /* Parse chunk header */
-        let header = ChunkHeader::from_buff(axml_buff, XmlTypes::ResTableType)
-                     .expect("Error: cannot get chunk header from string pool");
+        let header = ChunkHeader::from_buff(axml_buff, XmlTypes::ResTableType)?;
 
         /* Get package count */
-        let package_count = axml_buff.read_u32::<LittleEndian>().unwrap();
-
-        let mut strings = Vec::<String>::new();
-        for _ in 0..package_count {
-            let block_type = XmlTypes::parse_block_type(axml_buff)
-                            .expect("Error: cannot parse block type");
-            match block_type {
-                XmlTypes::ResStringPoolType => {
-                    StringPool::from_buff(axml_buff, &mut strings)
-                               .expect("Error: cannot parse string pool header");
+        let package_count = axml_buff.read_u32_le()?;
+
+        let mut strings = StringPool::empty();
+        let mut packages = Vec::new();
+        let mut unknown_chunks = Vec::new();
+
+        let mut walker = ChunkWalker::with_range(initial_offset + header.header_size as u64,
+                                                 initial_offset + header.size as u64);
+        while let Some(chunk) = walker.next_chunk(axml_buff)? {
+            match chunk.chunk_type {
+                Some(XmlTypes::ResStringPoolType) => {
+                    strings = StringPool::from_buff(axml_buff)?;
                 },
-                XmlTypes::ResTablePackageType => {
-                    ResTablePackage::parse(axml_buff)
-                                    .expect("Error: cannot parse table package");
+                Some(XmlTypes::ResTablePackageType) => {
+                    packages.push(ResTablePackage::parse(axml_buff)?);
                 },
-                _ => { panic!("######## Unexpected block type: {:02X}", block_type); }
+                _ => unknown_chunks.push(UnknownChunk::from_buff(axml_buff, &chunk)?),
+            };
+        }
+
+        let mut table = ResTable {
+            header,
+            package_count,
+            strings,
+            packages,
+            unknown_chunks,
+            ids_by_name: HashMap::new(),
+        };
+        table.index_names();
+
+        Ok(table)
+    }
+
+    /// Build the index of the resource IDs by type and name, keeping the
+    /// lowest ID when several resources have the same name (in different
+    /// packages)
+    fn index_names(&mut self) {
+        self.ids_by_name.clear();
+
+        for package in self.packages.iter() {
+            for res_type in package.types.iter() {
+                let type_name = match package.type_name(res_type.id) {
+                    Some(type_name) => type_name,
+                    None => continue,
+                };
+
+                for (&entry_id, entry) in res_type.entries.iter() {
+                    let entry_name = match package.key_names.get(entry.key) {
+                        Some(entry_name) => entry_name,
+                        None => continue,
+                    };
+
+                    let res_id = package.id << 24 | (res_type.id as u32) << 16 | entry_id as u32;
+                    let id = self.ids_by_name.entry((type_name.to_string(), entry_name.into_owned()))
+                                             .or_insert(res_id);
+                    *id = (*id).min(res_id);
+                }
+            }
+        }
+    }
+
+    /// Package with the given ID
+    pub fn package(&self, package_id: u8) -> Option<&ResTablePackage> {
+        self.packages.iter().find(|package| package.id == package_id as u32)
+    }
+
+    /// Every value of the resource with the given `0xPPTTEEEE` ID, one per
+    /// configuration in which it is defined
+    pub fn entries(&self, res_id: u32) -> Vec<(&ResTableType, &ResTableEntry)> {
+        match self.package((res_id >> 24) as u8) {
+            Some(package) => package.entries(res_id),
+            None => Vec::new(),
+        }
+    }
+
+    /// Value of the resource with the given `0xPPTTEEEE` ID that best
+    /// matches the configuration of a device, like Android's
+    /// `ResTable::getResource` does
+    pub fn resolve(&self, res_id: u32, device: &ResConfig) -> Option<(&ResTableType, &ResTableEntry)> {
+        let mut best: Option<(&ResTableType, &ResTableEntry)> = None;
+
+        for (res_type, entry) in self.entries(res_id) {
+            if !res_type.config.matches(device) {
+                continue;
+            }
+
+            best = match best {
+                Some((best_type, _)) if !res_type.config.is_better_than(&best_type.config, device) => best,
+                _ => Some((res_type, entry)),
             };
         }
+
+        best
+    }
+
+    /// Value of a simple resource in the default configuration or, if it is
+    /// not defined there, in the first configuration where it is
+    pub fn default_value(&self, res_id: u32) -> Option<&ResValue> {
+        let (_, entry) = self.resolve(res_id, &ResConfig::default())
+                             .or_else(|| self.entries(res_id).into_iter().next())?;
+
+        match &entry.value {
+            ResTableEntryValue::Simple(value) => Some(value),
+            ResTableEntryValue::Complex { .. } => None,
+        }
+    }
+
+    /// Actual value of a reference to a string, bool or integer resource,
+    /// formatted for display (e.g., the app name for `@string/app_name`).
+    /// References to other types of resources (layouts, drawables, etc.)
+    /// are not substituted, and `None` is returned.
+    pub fn substitute(&self, value: &ResValue) -> Option<String> {
+        if value.data_type != DataValueType::TypeReference {
+            return None;
+        }
+
+        let mut value = value;
+        for _ in 0..MAX_REFERENCE_DEPTH {
+            if value.data_type != DataValueType::TypeReference {
+                return Some(value.format(&self.strings, Some(self)));
+            }
+
+            let type_name = self.package((value.data >> 24) as u8)?
+                                .type_name((value.data >> 16) as u8)?;
+            if !SUBSTITUTABLE_TYPES.contains(&type_name.as_ref()) {
+                return None;
+            }
+
+            value = self.default_value(value.data)?;
+        }
+
+        None
+    }
+
+    /// Add the resources of another table to this one, e.g., the table of a
+    /// configuration split APK to the table of the base APK. The types of a
+    /// package which is in both tables are added to the package of this
+    /// table, as new configurations of its resources, and the strings they
+    /// refer to are copied in the string pools of this table.
+    pub fn merge(&mut self, other: ResTable) {
+        let mut value_strings = HashMap::new();
+
+        for mut other_package in other.packages {
+            /* Strings values now refer to the global pool of this table */
+            for res_type in other_package.types.iter_mut() {
+                for entry in res_type.entries.values_mut() {
+                    match &mut entry.value {
+                        ResTableEntryValue::Simple(value) => {
+                            remap_string(value, &other.strings, &mut self.strings, &mut value_strings);
+                        },
+                        ResTableEntryValue::Complex { values, .. } => {
+                            for (_, value) in values.iter_mut() {
+                                remap_string(value, &other.strings, &mut self.strings, &mut value_strings);
+                            }
+                        },
+                    }
+                }
+            }
+
+            match self.packages.iter_mut().find(|package| package.id == other_package.id) {
+                Some(package) => package.merge(other_package),
+                None => {
+                    self.packages.push(other_package);
+                    self.package_count += 1;
+                },
+            }
+        }
+
+        self.unknown_chunks.extend(other.unknown_chunks);
+        self.index_names();
+    }
+
+    /// IDs of all the resources defined in the table, in increasing order
+    pub fn resource_ids(&self) -> Vec<u32> {
+        let mut ids = Vec::new();
+        for package in self.packages.iter() {
+            for spec in package.type_specs.iter() {
+                for entry in 0..spec.entry_count {
+                    let res_id = package.id << 24 | (spec.id as u32) << 16 | entry;
+                    if !package.entries(res_id).is_empty() {
+                        ids.push(res_id);
+                    }
+                }
+            }
+        }
+
+        ids.sort_unstable();
+        ids
+    }
+
+    /// ID of the resource with the given type and name (e.g., `string` and
+    /// `app_name`), in any package of the table
+    pub fn resource_id(&self, type_name: &str, entry: &str) -> Option<u32> {
+        self.ids_by_name.get(&(type_name.to_string(), entry.to_string())).copied()
+    }
+
+    /// Format of the attribute with the given ID, read from the bag of its
+    /// `attr` resource. Returns `None` if the table does not define it.
+    pub fn attribute_format(&self, attr_id: u32) -> Option<AttributeFormat> {
+        let (_, entry) = self.entries(attr_id).into_iter().next()?;
+        let values = match &entry.value {
+            ResTableEntryValue::Complex { values, .. } => values,
+            ResTableEntryValue::Simple(_) => return None,
+        };
+
+        let mut format = AttributeFormat::any();
+        for (key, value) in values.iter() {
+            if *key == ATTR_TYPE {
+                format.types = value.data;
+            } else if key & 0x00ff0000 != 0 {
+                /* Enum and flag values are keyed by the ID of their name */
+                if let Some(name) = self.resource_name(*key) {
+                    format.values.push((name.entry, value.data));
+                }
+            }
+        }
+
+        Some(format)
+    }
+}
+
+impl ResourceResolver for ResTable {
+    fn resource_name(&self, id: u32) -> Option<ResourceName> {
+        let package = self.package((id >> 24) as u8)?;
+        let (_, entry) = package.entries(id).into_iter().next()?;
+
+        Some(ResourceName {
+            package: package.name(),
+            type_name: package.type_name((id >> 16) as u8)?.to_string(),
+            entry: package.key_names.get(entry.key)?.to_string(),
+        })
+    }
+}
+
+/**
+ * Format of an attribute: the types of values it accepts (`FORMAT_*`), and
+ * the names of its enum or flag values, if any.
+ */
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AttributeFormat {
+    /* Types of values accepted, as ResTable_map type bits */
+    pub types: u32,
+
+    /* Names and values of the enum or flag values */
+    pub values: Vec<(String, u32)>,
+}
+
+impl AttributeFormat {
+    /// Format of attributes which accept any type of value, e.g., of the
+    /// attributes whose format is not known
+    pub fn any() -> Self {
+        AttributeFormat {
+            types: FORMAT_ANY,
+            values: Vec::new(),
+        }
+    }
+
+    /// Whether the attribute accepts one of the given types of values
+    pub fn accepts(&self, types: u32) -> bool {
+        self.types & types != 0
+    }
+
+    /// Value of the enum or flag value with the given name
+    pub fn value(&self, name: &str) -> Option<u32> {
+        self.values.iter()
+                   .find(|(value_name, _)| value_name == name)
+                   .map(|&(_, value)| value)
     }
 }
 
@@ -80,7 +412,7 @@ pub struct ResTablePackage {
     /* If this is a base package, its ID.  Package IDs start
      * at 1 (corresponding to the value of the package bits in a
      * resource identifier).  0 means this is not a base package. */
-    id: u32,
+    pub id: u32,
 
     /* Actual name of this package, \0-terminated. */
     name: [u16; 128],
@@ -102,41 +434,57 @@ pub struct ResTablePackage {
     last_public_key: u32,
 
     type_id_offset: u32,
+
+    /* Names of the types (e.g., "string"), from the type string pool */
+    pub type_names: StringPool<'static>,
+
+    /* Names of the entries (e.g., "app_name"), from the key string pool */
+    pub key_names: StringPool<'static>,
+
+    /* Specification of each type of resource in the package */
+    pub type_specs: Vec<ResTableTypeSpec>,
+
+    /* Entries of each type, one chunk per configuration */
+    pub types: Vec<ResTableType>,
+
+    /* Shared libraries this package references */
+    pub libraries: Vec<ResTableLibraryEntry>,
+
+    /* Chunks that could not be decoded */
+    pub unknown_chunks: Vec<UnknownChunk>,
 }
 
 impl ResTablePackage {
-    pub fn parse(axml_buff: &mut Cursor<Vec<u8>>) -> Result<Self, Error> {
+    pub fn parse<T: AsRef<[u8]>>(axml_buff: &mut Cursor<T>) -> Result<Self> {
 
         /* Go back 2 bytes, to account from the block type */
-        let initial_offset = axml_buff.position();
-        axml_buff.set_position(initial_offset - 2);
+        let initial_offset = axml_buff.position() - 2;
+        axml_buff.set_position(initial_offset);
 
         /* Parse chunk header */
-        // let header = ResTable::from_buff(axml_buff)
-        //              .expect("Error: cannot parse resource table header from string pool");
-        let header = ChunkHeader::from_buff(axml_buff, XmlTypes::ResTablePackageType)
-                     .expect("Error: cannot get chunk header for ResTablePackage");
-        // let header = ChunkHeader { chunk_type: 0x0, header_size: 0x0, size: 0x0 };
+        let header = ChunkHeader::from_buff(axml_buff, XmlTypes::ResTablePackageType)?;
 
         /* Get other members */
-        let id = axml_buff.read_u32::<LittleEndian>().unwrap();
+        let id = axml_buff.read_u32_le()?;
 
-        // TODO: this could be simpler with an iterator
+        /* The name is always stored on 128 UTF-16 code units, even if it is
+         * shorter (in which case it is \0-terminated) */
         let mut name: [u16; 128] = [0; 128];
-        for i in 0..128 {
-            name[i] = axml_buff.read_u16::<LittleEndian>().unwrap();
-            if name[i] == 0x00 {
-                break;
-            }
+        for unit in name.iter_mut() {
+            *unit = axml_buff.read_u16_le()?;
         }
-        let type_strings = axml_buff.read_u32::<LittleEndian>().unwrap();
-        let last_public_type = axml_buff.read_u32::<LittleEndian>().unwrap();
-        let key_strings = axml_buff.read_u32::<LittleEndian>().unwrap();
-        let last_public_key = axml_buff.read_u32::<LittleEndian>().unwrap();
-        let type_id_offset = axml_buff.read_u32::<LittleEndian>().unwrap();
+        let type_strings = axml_buff.read_u32_le()?;
+        let last_public_type = axml_buff.read_u32_le()?;
+        let key_strings = axml_buff.read_u32_le()?;
+        let last_public_key = axml_buff.read_u32_le()?;
 
-        /* Build and return the object */
-        Ok(ResTablePackage {
+        /* Older tables do not have this field */
+        let type_id_offset = match header.header_size > PACKAGE_HEADER_SIZE_V1 {
+            true => axml_buff.read_u32_le()?,
+            false => 0,
+        };
+
+        let mut package = ResTablePackage {
             header,
             id,
             name,
@@ -144,7 +492,383 @@ impl ResTablePackage {
             last_public_type,
             key_strings,
             last_public_key,
-            type_id_offset
+            type_id_offset,
+            type_names: StringPool::empty(),
+            key_names: StringPool::empty(),
+            type_specs: Vec::new(),
+            types: Vec::new(),
+            libraries: Vec::new(),
+            unknown_chunks: Vec::new(),
+        };
+
+        let mut walker = ChunkWalker::with_range(initial_offset + package.header.header_size as u64,
+                                                 initial_offset + package.header.size as u64);
+        while let Some(chunk) = walker.next_chunk(axml_buff)? {
+            match chunk.chunk_type {
+                Some(XmlTypes::ResStringPoolType) => {
+                    /* The two string pools are told apart by their offsets */
+                    let relative_offset = chunk.offset - initial_offset;
+                    let strings = StringPool::from_buff(axml_buff)?;
+
+                    if relative_offset == key_strings as u64 {
+                        package.key_names = strings;
+                    } else if relative_offset == type_strings as u64 {
+                        package.type_names = strings;
+                    } else {
+                        package.unknown_chunks.push(UnknownChunk::from_buff(axml_buff, &chunk)?);
+                    }
+                },
+                Some(XmlTypes::ResTableTypeSpecType) => {
+                    package.type_specs.push(ResTableTypeSpec::parse(axml_buff)?);
+                },
+                Some(XmlTypes::ResTableTypeType) => {
+                    package.types.push(ResTableType::parse(axml_buff)?);
+                },
+                Some(XmlTypes::ResTableLibraryType) => {
+                    package.libraries.extend(ResTableLibraryEntry::parse_all(axml_buff)?);
+                },
+                _ => package.unknown_chunks.push(UnknownChunk::from_buff(axml_buff, &chunk)?),
+            }
+        }
+
+        Ok(package)
+    }
+
+    /// Name of the package, e.g., `android`
+    pub fn name(&self) -> String {
+        decode_utf16_name(&self.name)
+    }
+
+    /// Name of the type with the given ID, e.g., `string`
+    pub fn type_name(&self, type_id: u8) -> Option<Cow<'_, str>> {
+        /* Type IDs start at 1 */
+        let index = (type_id as u32).checked_sub(1 + self.type_id_offset)?;
+        self.type_names.get(index)
+    }
+
+    /// Add the types of another package with the same ID to this one, see
+    /// `ResTable::merge`
+    fn merge(&mut self, mut other: ResTablePackage) {
+        /* Types which are only in the other package */
+        for spec in std::mem::take(&mut other.type_specs) {
+            if self.type_name(spec.id).is_none() {
+                if let Some(type_name) = other.type_name(spec.id) {
+                    let index = (spec.id as u32).saturating_sub(1 + self.type_id_offset);
+                    while (self.type_names.len() as u32) < index {
+                        self.type_names.push(String::new());
+                    }
+                    self.type_names.push(type_name.to_string());
+                }
+            }
+
+            match self.type_specs.iter_mut().find(|own_spec| own_spec.id == spec.id) {
+                Some(own_spec) if own_spec.entry_count < spec.entry_count => {
+                    own_spec.flags.extend_from_slice(&spec.flags[own_spec.flags.len().min(spec.flags.len())..]);
+                    own_spec.entry_count = spec.entry_count;
+                },
+                Some(_) => { },
+                None => self.type_specs.push(spec),
+            }
+        }
+
+        /* Entry names now refer to the key pool of this package */
+        let mut key_names = HashMap::new();
+        let other_key_names = &other.key_names;
+        for mut res_type in std::mem::take(&mut other.types) {
+            for entry in res_type.entries.values_mut() {
+                entry.key = *key_names.entry(entry.key).or_insert_with(|| {
+                    let key_name = other_key_names.get(entry.key).unwrap_or_default();
+                    self.key_names.push(key_name.to_string())
+                });
+            }
+
+            self.types.push(res_type);
+        }
+
+        self.libraries.extend(other.libraries);
+        self.unknown_chunks.extend(other.unknown_chunks);
+    }
+
+    /// Every value of the resource with the given `0xPPTTEEEE` ID, one per
+    /// configuration in which it is defined
+    pub fn entries(&self, res_id: u32) -> Vec<(&ResTableType, &ResTableEntry)> {
+        let type_id = (res_id >> 16) as u8;
+        let entry_id = res_id as u16;
+
+        self.types.iter()
+                  .filter(|res_type| res_type.id == type_id)
+                  .filter_map(|res_type| res_type.entries.get(&entry_id).map(|entry| (res_type, entry)))
+                  .collect()
+    }
+}
+
+/**
+ * A specification of the resources defined by a particular type.
+ *
+ * There should be one of these chunks for each resource type.
+ *
+ * This structure is followed by an array of integers providing the set of
+ * configuration change flags (ResTable_config::CONFIG_*) that have multiple
+ * resources for that configuration.  In addition, the high bit is set if that
+ * resource has been made public.
+ */
+#[derive(Debug)]
+pub struct ResTableTypeSpec {
+    /* Chunk header */
+    header: ChunkHeader,
+
+    /* The type identifier this chunk is holding.  Type IDs start
+     * at 1 (corresponding to the value of the type bits in a
+     * resource identifier).  0 is invalid. */
+    pub id: u8,
+
+    /* Number of ResTable_type chunks with this ID (0 in older tables) */
+    pub types_count: u16,
+
+    /* Number of uint32_t entry configuration masks that follow */
+    pub entry_count: u32,
+
+    /* Configuration masks, one per entry */
+    pub flags: Vec<u32>,
+}
+
+impl ResTableTypeSpec {
+    pub fn parse<T: AsRef<[u8]>>(axml_buff: &mut Cursor<T>) -> Result<Self> {
+
+        /* Go back 2 bytes, to account from the block type */
+        let initial_offset = axml_buff.position() - 2;
+        axml_buff.set_position(initial_offset);
+
+        /* Parse chunk header */
+        let header = ChunkHeader::from_buff(axml_buff, XmlTypes::ResTableTypeSpecType)?;
+
+        let id = axml_buff.read_u8_le()?;
+        let _res0 = axml_buff.read_u8_le()?;
+        let types_count = axml_buff.read_u16_le()?;
+        let entry_count = axml_buff.read_u32_le()?;
+
+        axml_buff.set_position(initial_offset + header.header_size as u64);
+        let mut flags = Vec::new();
+        for _ in 0..entry_count {
+            flags.push(axml_buff.read_u32_le()?);
+        }
+
+        Ok(ResTableTypeSpec {
+            header,
+            id,
+            types_count,
+            entry_count,
+            flags,
+        })
+    }
+}
+
+/**
+ * A collection of resource entries for a particular resource data
+ * type, in a given configuration.
+ *
+ * The header is followed by an array of offsets to each entry (or
+ * NO_ENTRY if the entry is not defined in this configuration), and then by
+ * the entries themselves, starting at `entries_start`.
+ */
+#[derive(Debug)]
+pub struct ResTableType {
+    /* Chunk header */
+    header: ChunkHeader,
+
+    /* The type identifier this chunk is holding.  Type IDs start
+     * at 1 (corresponding to the value of the type bits in a
+     * resource identifier).  0 is invalid. */
+    pub id: u8,
+
+    /* Flags (sparse, 16-bit offsets) */
+    pub flags: u8,
+
+    /* Number of entries (or of (index, offset) pairs for sparse types) */
+    pub entry_count: u32,
+
+    /* Offset from header where ResTable_entry data starts */
+    pub entries_start: u32,
+
+    /* Configuration this type's entries are defined for */
+    pub config: ResConfig,
+
+    /* Entries defined in this configuration, by entry index */
+    pub entries: BTreeMap<u16, ResTableEntry>,
+}
+
+impl ResTableType {
+    pub fn parse<T: AsRef<[u8]>>(axml_buff: &mut Cursor<T>) -> Result<Self> {
+
+        /* Go back 2 bytes, to account from the block type */
+        let initial_offset = axml_buff.position() - 2;
+        axml_buff.set_position(initial_offset);
+
+        /* Parse chunk header */
+        let header = ChunkHeader::from_buff(axml_buff, XmlTypes::ResTableTypeType)?;
+
+        let id = axml_buff.read_u8_le()?;
+        let flags = axml_buff.read_u8_le()?;
+        let _reserved = axml_buff.read_u16_le()?;
+        let entry_count = axml_buff.read_u32_le()?;
+        let entries_start = axml_buff.read_u32_le()?;
+
+        let config = ResConfig::from_buff(axml_buff)?;
+
+        /* Get the offset of each entry */
+        axml_buff.set_position(initial_offset + header.header_size as u64);
+        let mut offsets = Vec::new();
+        for index in 0..entry_count {
+            if flags & TYPE_FLAG_SPARSE != 0 {
+                let entry_index = axml_buff.read_u16_le()?;
+                let offset = axml_buff.read_u16_le()? as u32 * 4;
+                offsets.push((entry_index, offset));
+            } else if flags & TYPE_FLAG_OFFSET16 != 0 {
+                let offset = axml_buff.read_u16_le()?;
+                if offset != NO_ENTRY_16 {
+                    offsets.push((index as u16, offset as u32 * 4));
+                }
+            } else {
+                let offset = axml_buff.read_u32_le()?;
+                if offset != NO_ENTRY {
+                    offsets.push((index as u16, offset));
+                }
+            }
+        }
+
+        /* Get the entries themselves */
+        let mut entries = BTreeMap::new();
+        for (entry_index, offset) in offsets {
+            axml_buff.set_position(initial_offset + entries_start as u64 + offset as u64);
+            entries.insert(entry_index, ResTableEntry::parse(axml_buff)?);
+        }
+
+        axml_buff.set_position(initial_offset + header.size as u64);
+
+        Ok(ResTableType {
+            header,
+            id,
+            flags,
+            entry_count,
+            entries_start,
+            config,
+            entries,
+        })
+    }
+}
+
+/// Value of an entry of a resource table
+#[derive(Debug, Clone)]
+pub enum ResTableEntryValue {
+    /* A single value, e.g., a string or a colour */
+    Simple(ResValue),
+
+    /* A bag of values (e.g., a style or an array), optionally inheriting
+     * from a parent bag. Each value is keyed by a resource ID, usually the
+     * ID of an attribute. */
+    Complex { parent: u32, values: Vec<(u32, ResValue)> },
+}
+
+/**
+ * An entry in a ResTable_type, i.e., the definition of one resource
+ * for one configuration.
+ */
+#[derive(Debug, Clone)]
+pub struct ResTableEntry {
+    /* Flags (complex, public, weak, compact) */
+    pub flags: u16,
+
+    /* Index of the name of this entry in the package key string pool */
+    pub key: u32,
+
+    /* The value of the entry */
+    pub value: ResTableEntryValue,
+}
+
+impl ResTableEntry {
+    pub fn parse<T: AsRef<[u8]>>(axml_buff: &mut Cursor<T>) -> Result<Self> {
+        let entry_offset = axml_buff.position();
+        let size = axml_buff.read_u16_le()?;
+        let flags = axml_buff.read_u16_le()?;
+        let key = axml_buff.read_u32_le()?;
+
+        /* Compact entries store the key index in the size field, the data
+         * type in the high byte of the flags, and the data in the key field */
+        if flags & ENTRY_FLAG_COMPACT != 0 {
+            let data_type = DataValueType::from_val((flags >> 8) as u8);
+
+            return Ok(ResTableEntry {
+                flags,
+                key: size as u32,
+                value: ResTableEntryValue::Simple(ResValue {
+                    size: 8,
+                    res0: 0,
+                    data_type,
+                    data: key,
+                }),
+            });
+        }
+
+        let value = if flags & ENTRY_FLAG_COMPLEX != 0 {
+            let parent = axml_buff.read_u32_le()?;
+            let count = axml_buff.read_u32_le()?;
+
+            axml_buff.set_position(entry_offset + size as u64);
+            let mut values = Vec::new();
+            for _ in 0..count {
+                let name = axml_buff.read_u32_le()?;
+                values.push((name, ResValue::from_buff(axml_buff)?));
+            }
+
+            ResTableEntryValue::Complex { parent, values }
+        } else {
+            axml_buff.set_position(entry_offset + size as u64);
+            ResTableEntryValue::Simple(ResValue::from_buff(axml_buff)?)
+        };
+
+        Ok(ResTableEntry {
+            flags,
+            key,
+            value,
         })
     }
 }
+
+/// A shared library referenced by a package, and the package ID it was
+/// assigned at build time
+#[derive(Debug, Clone)]
+pub struct ResTableLibraryEntry {
+    pub package_id: u32,
+    pub package_name: String,
+}
+
+impl ResTableLibraryEntry {
+    /// Parse all the entries of a ResTable_lib_header chunk
+    pub fn parse_all<T: AsRef<[u8]>>(axml_buff: &mut Cursor<T>) -> Result<Vec<Self>> {
+
+        /* Go back 2 bytes, to account from the block type */
+        let initial_offset = axml_buff.position() - 2;
+        axml_buff.set_position(initial_offset);
+
+        /* Parse chunk header */
+        let header = ChunkHeader::from_buff(axml_buff, XmlTypes::ResTableLibraryType)?;
+        let count = axml_buff.read_u32_le()?;
+
+        axml_buff.set_position(initial_offset + header.header_size as u64);
+        let mut libraries = Vec::new();
+        for _ in 0..count {
+            let package_id = axml_buff.read_u32_le()?;
+            let mut name: [u16; 128] = [0; 128];
+            for unit in name.iter_mut() {
+                *unit = axml_buff.read_u16_le()?;
+            }
+
+            libraries.push(ResTableLibraryEntry {
+                package_id,
+                package_name: decode_utf16_name(&name),
+            });
+        }
+
+        Ok(libraries)
+    }
+}
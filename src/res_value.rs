@@ -1,19 +1,86 @@
 #![allow(dead_code)]
 
 use crate::data_value_type::DataValueType;
-
-use std::io::{
-    Error,
-    Cursor,
-};
-use byteorder::{
-    LittleEndian,
-    ReadBytesExt
+use crate::string_pool::StringPool;
+use crate::res_resolver::{
+    self,
+    ResourceResolver,
 };
 
+use crate::read_ext::ReadExt;
+use crate::write_ext::WriteExt;
+use crate::error::Result;
+
+use std::io::Cursor;
+
+/* Structure of complex data values (TYPE_DIMENSION and TYPE_FRACTION):
+ *   - bits 0-3: unit of the value (see `DIMENSION_UNITS` and `FRACTION_UNITS`)
+ *   - bits 4-5: radix, i.e., where the decimal point is in the mantissa
+ *   - bits 8-31: signed mantissa */
+const COMPLEX_UNIT_SHIFT: u32 = 0;
+const COMPLEX_UNIT_MASK: u32 = 0xf;
+const COMPLEX_RADIX_SHIFT: u32 = 4;
+const COMPLEX_RADIX_MASK: u32 = 0x3;
+const COMPLEX_MANTISSA_SHIFT: u32 = 8;
+const COMPLEX_MANTISSA_MASK: u32 = 0xffffff;
+
+/* Multipliers for each radix value: 23p0, 16p7, 8p15 and 0p23 */
+const MANTISSA_MULT: f32 = 1.0 / (1 << COMPLEX_MANTISSA_SHIFT) as f32;
+const RADIX_MULTS: [f32; 4] = [
+    MANTISSA_MULT,
+    1.0 / (1 << 7) as f32 * MANTISSA_MULT,
+    1.0 / (1 << 15) as f32 * MANTISSA_MULT,
+    1.0 / (1 << 23) as f32 * MANTISSA_MULT,
+];
+
+/* Radix values, by position of the decimal point in the mantissa */
+const COMPLEX_RADIX_23P0: u32 = 0;
+const COMPLEX_RADIX_16P7: u32 = 1;
+const COMPLEX_RADIX_8P15: u32 = 2;
+const COMPLEX_RADIX_0P23: u32 = 3;
+
+/* Units of dimensions, indexed by the unit bits of complex data */
+const DIMENSION_UNITS: [&str; 6] = ["px", "dp", "sp", "pt", "in", "mm"];
+
+/* Units of fractions: fraction of the element itself, or of its parent */
+const FRACTION_UNITS: [&str; 2] = ["%", "%p"];
+
+/* Older spelling of the `dp` unit, still accepted by aapt */
+const DIP_UNIT: &str = "dip";
+
+/* Size of a Res_value structure */
+const RES_VALUE_SIZE: u16 = 8;
+
+/// A colour decoded from one of the TYPE_INT_COLOR_* values
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Color {
+    pub a: u8,
+    pub r: u8,
+    pub g: u8,
+    pub b: u8,
+}
+
+impl Color {
+    /// Build a colour from a packed 0xAARRGGBB integer
+    pub fn from_argb(argb: u32) -> Self {
+        Color {
+            a: (argb >> 24) as u8,
+            r: (argb >> 16) as u8,
+            g: (argb >> 8) as u8,
+            b: argb as u8,
+        }
+    }
+
+    /// Packed 0xAARRGGBB representation of the colour
+    pub fn to_argb(&self) -> u32 {
+        (self.a as u32) << 24 | (self.r as u32) << 16 | (self.g as u32) << 8 | self.b as u32
+    }
+}
+
 /* Representation of a value in a resource, supplying type
  * information.
  */
+#[derive(Debug, Clone, Copy)]
 pub struct ResValue {
     /* Number of bytes in this structure */
     pub size: u16,
@@ -26,17 +93,24 @@ pub struct ResValue {
 }
 
 impl ResValue {
-    pub fn from_buff(axml_buff: &mut Cursor<Vec<u8>>) -> Result<Self, Error> {
-        let size = axml_buff.read_u16::<LittleEndian>().unwrap();
-        let res0 = axml_buff.read_u8().unwrap();
-
-        if res0 != 0 {
-            panic!("res0 is not 0");
+    /// Value of the given type, e.g., `ResValue::new(TypeIntDec, 42)`
+    pub fn new(data_type: DataValueType, data: u32) -> Self {
+        ResValue {
+            size: RES_VALUE_SIZE,
+            res0: 0,
+            data_type,
+            data,
         }
+    }
+
+    pub fn from_buff<T: AsRef<[u8]>>(axml_buff: &mut Cursor<T>) -> Result<Self> {
+        let size = axml_buff.read_u16_le()?;
+
+        /* res0 should always be 0, but Android does not enforce it */
+        let res0 = axml_buff.read_u8_le()?;
 
-        let data_type = DataValueType::from_val(axml_buff.read_u8().unwrap())
-                        .expect("Error: cannot decode data value type");
-        let data = axml_buff.read_u32::<LittleEndian>().unwrap();
+        let data_type = DataValueType::from_val(axml_buff.read_u8_le()?);
+        let data = axml_buff.read_u32_le()?;
 
         Ok(ResValue {
             size,
@@ -45,4 +119,221 @@ impl ResValue {
             data
         })
     }
+
+    /// Append the Res_value structure to `out`
+    pub(crate) fn write(&self, out: &mut Vec<u8>) {
+        out.write_u16_le(self.size);
+        out.write_u8_le(self.res0);
+        out.write_u8_le(self.data_type.to_val());
+        out.write_u32_le(self.data);
+    }
+
+    /// Format the value the way it would appear in a text XML file.
+    /// `strings` is the string pool that TYPE_STRING values index into, and
+    /// `resolver` is used to give names to references, if available.
+    pub fn format(&self, strings: &StringPool, resolver: Option<&dyn ResourceResolver>) -> String {
+        match self.data_type {
+            /* 0 means undefined, 1 means explicitly empty */
+            DataValueType::TypeNull => match self.data {
+                1 => "@empty".to_string(),
+                _ => String::new(),
+            },
+            DataValueType::TypeReference
+            | DataValueType::TypeDynamicReference => res_resolver::format_reference('@', self.data, resolver),
+            DataValueType::TypeAttribute
+            | DataValueType::TypeDynamicAttribute => res_resolver::format_reference('?', self.data, resolver),
+            DataValueType::TypeString => strings.get(self.data).unwrap_or_default().to_string(),
+            DataValueType::TypeFloat => self.format_float(),
+            DataValueType::TypeDimension => self.format_dimension(),
+            DataValueType::TypeFraction => self.format_fraction(),
+            DataValueType::TypeIntDec => (self.data as i32).to_string(),
+            DataValueType::TypeIntHex => format!("0x{:x}", self.data),
+            DataValueType::TypeIntBoolean => match self.data {
+                0 => "false".to_string(),
+                _ => "true".to_string(),
+            },
+            DataValueType::TypeIntColorArgb8
+            | DataValueType::TypeIntColorRgb8
+            | DataValueType::TypeIntColorArgb4
+            | DataValueType::TypeIntColorRgb4 => self.format_color(),
+            DataValueType::Unknown(_) => format!("0x{:08x}", self.data),
+        }
+    }
+
+    /// Interpret the data as a single-precision float (for TYPE_FLOAT)
+    pub fn as_float(&self) -> f32 {
+        f32::from_bits(self.data)
+    }
+
+    /// Decode the numeric part of a complex value (for TYPE_DIMENSION and
+    /// TYPE_FRACTION). Fractions are returned as a ratio, i.e., 0.5 for 50%.
+    pub fn complex_to_float(&self) -> f32 {
+        let mantissa = (self.data & (COMPLEX_MANTISSA_MASK << COMPLEX_MANTISSA_SHIFT)) as i32;
+        let radix = (self.data >> COMPLEX_RADIX_SHIFT) & COMPLEX_RADIX_MASK;
+
+        mantissa as f32 * RADIX_MULTS[radix as usize]
+    }
+
+    /// Unit bits of a complex value
+    fn complex_unit(&self) -> usize {
+        ((self.data >> COMPLEX_UNIT_SHIFT) & COMPLEX_UNIT_MASK) as usize
+    }
+
+    /// Typed colour for TYPE_INT_COLOR_* values. Short forms (#rgb, #argb)
+    /// are stored expanded, and colours without alpha are fully opaque.
+    pub fn as_color(&self) -> Option<Color> {
+        match self.data_type {
+            DataValueType::TypeIntColorArgb8
+            | DataValueType::TypeIntColorArgb4 => Some(Color::from_argb(self.data)),
+            DataValueType::TypeIntColorRgb8
+            | DataValueType::TypeIntColorRgb4 => Some(Color::from_argb(0xff000000 | self.data)),
+            _ => None,
+        }
+    }
+
+    /// Format a TYPE_INT_COLOR_* value in the form it was written in,
+    /// e.g., `#80ff0000`, `#ff0000`, `#8f00` or `#f00`
+    pub fn format_color(&self) -> String {
+        /* Short forms only keep the high nibble of each channel */
+        let nibble = |shift: u32| (self.data >> shift) & 0xf;
+
+        match self.data_type {
+            DataValueType::TypeIntColorArgb8 => format!("#{:08x}", self.data),
+            DataValueType::TypeIntColorRgb8 => format!("#{:06x}", self.data & 0xffffff),
+            DataValueType::TypeIntColorArgb4 =>
+                format!("#{:x}{:x}{:x}{:x}", nibble(28), nibble(20), nibble(12), nibble(4)),
+            DataValueType::TypeIntColorRgb4 =>
+                format!("#{:x}{:x}{:x}", nibble(20), nibble(12), nibble(4)),
+            _ => format!("#{:08x}", self.data),
+        }
+    }
+
+    /// Format a TYPE_FLOAT value, e.g., `1.5`
+    pub fn format_float(&self) -> String {
+        self.as_float().to_string()
+    }
+
+    /// Format a TYPE_DIMENSION value with its unit, e.g., `16dp`
+    pub fn format_dimension(&self) -> String {
+        let value = self.complex_to_float();
+        match DIMENSION_UNITS.get(self.complex_unit()) {
+            Some(unit) => format!("{}{}", value, unit),
+            None => format!("{} (unknown unit)", value),
+        }
+    }
+
+    /// Format a TYPE_FRACTION value as a percentage, e.g., `50%` or `100%p`
+    pub fn format_fraction(&self) -> String {
+        let value = self.complex_to_float() * 100.0;
+        match FRACTION_UNITS.get(self.complex_unit()) {
+            Some(unit) => format!("{}{}", value, unit),
+            None => format!("{} (unknown unit)", value),
+        }
+    }
+
+    /// Parse a literal value the way aapt does when compiling text XML:
+    /// booleans, decimal and hexadecimal integers, colours, dimensions,
+    /// fractions and floats. Returns `None` for anything else (strings and
+    /// references), which the caller has to handle.
+    pub fn parse(text: &str) -> Option<Self> {
+        let text = text.trim();
+
+        match text {
+            "true" => return Some(ResValue::new(DataValueType::TypeIntBoolean, 0xffffffff)),
+            "false" => return Some(ResValue::new(DataValueType::TypeIntBoolean, 0)),
+            _ => { },
+        }
+
+        if let Some(color) = text.strip_prefix('#') {
+            return ResValue::parse_color(color);
+        }
+
+        if let Some(hex) = text.strip_prefix("0x").or_else(|| text.strip_prefix("0X")) {
+            return u32::from_str_radix(hex, 16).ok()
+                                               .map(|data| ResValue::new(DataValueType::TypeIntHex, data));
+        }
+
+        if let Ok(int) = text.parse::<i32>() {
+            return Some(ResValue::new(DataValueType::TypeIntDec, int as u32));
+        }
+
+        /* Everything else starts with a number, possibly followed by a unit */
+        let number_end = text.find(|c: char| !(c.is_ascii_digit() || "+-.eE".contains(c)))
+                             .unwrap_or(text.len());
+        let (number, unit) = text.split_at(number_end);
+        if !number.starts_with(|c: char| c.is_ascii_digit() || "+-.".contains(c)) {
+            return None;
+        }
+        let value = number.parse::<f32>().ok()?;
+
+        if unit.is_empty() {
+            return Some(ResValue::new(DataValueType::TypeFloat, value.to_bits()));
+        }
+
+        let unit = unit.trim();
+        if let Some(index) = FRACTION_UNITS.iter().position(|&known| known == unit) {
+            let data = float_to_complex(value / 100.0)? | index as u32;
+            return Some(ResValue::new(DataValueType::TypeFraction, data));
+        }
+
+        let unit = if unit == DIP_UNIT { "dp" } else { unit };
+        let index = DIMENSION_UNITS.iter().position(|&known| known == unit)?;
+        let data = float_to_complex(value)? | index as u32;
+        Some(ResValue::new(DataValueType::TypeDimension, data))
+    }
+
+    /// Parse the hexadecimal digits of a colour, in any of the `rgb`,
+    /// `argb`, `rrggbb` or `aarrggbb` forms
+    fn parse_color(digits: &str) -> Option<Self> {
+        if !digits.chars().all(|c| c.is_ascii_hexdigit()) {
+            return None;
+        }
+        let value = u32::from_str_radix(digits, 16).ok()?;
+
+        /* Short forms are stored expanded, e.g., #f00 as 0xffff0000 */
+        let expand = |nibbles: u32| (0..nibbles).rev().fold(0, |acc, i| {
+            let nibble = (value >> (i * 4)) & 0xf;
+            acc << 8 | nibble << 4 | nibble
+        });
+
+        match digits.len() {
+            3 => Some(ResValue::new(DataValueType::TypeIntColorRgb4, 0xff000000 | expand(3))),
+            4 => Some(ResValue::new(DataValueType::TypeIntColorArgb4, expand(4))),
+            6 => Some(ResValue::new(DataValueType::TypeIntColorRgb8, 0xff000000 | value)),
+            8 => Some(ResValue::new(DataValueType::TypeIntColorArgb8, value)),
+            _ => None,
+        }
+    }
+}
+
+/// Encode a number as the mantissa and radix of complex data (without the
+/// unit), choosing the most precise radix that can hold it, like aapt.
+/// Returns `None` if the number is too large to be encoded.
+fn float_to_complex(value: f32) -> Option<u32> {
+    let negative = value < 0.0;
+    let bits = (value.abs() as f64 * (1u64 << 23) as f64 + 0.5) as u64;
+
+    let (radix, shift) = if bits & 0x7fffff == 0 {
+        (COMPLEX_RADIX_23P0, 23)
+    } else if bits & !0x7fffff == 0 {
+        (COMPLEX_RADIX_0P23, 0)
+    } else if bits & !0x7fffffff == 0 {
+        (COMPLEX_RADIX_8P15, 8)
+    } else if bits & !0x7fffffffff == 0 {
+        (COMPLEX_RADIX_16P7, 16)
+    } else {
+        (COMPLEX_RADIX_23P0, 23)
+    };
+
+    /* The mantissa is signed, the largest values do not fit */
+    if bits >> shift > 0x7fffff {
+        return None;
+    }
+
+    let mut mantissa = (bits >> shift) as u32 & COMPLEX_MANTISSA_MASK;
+    if negative {
+        mantissa = mantissa.wrapping_neg() & COMPLEX_MANTISSA_MASK;
+    }
+
+    Some(radix << COMPLEX_RADIX_SHIFT | mantissa << COMPLEX_MANTISSA_SHIFT)
 }
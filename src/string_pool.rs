@@ -3,16 +3,182 @@
 use crate::chunk_header::ChunkHeader;
 use crate::xml_types::XmlTypes;
 
-use std::io::{
-    Read,
-    Error,
-    Cursor,
+use crate::read_ext::ReadExt;
+use crate::write_ext::{
+    self,
+    WriteExt,
 };
-use byteorder::{
-    LittleEndian,
-    ReadBytesExt
+use crate::error::{
+    AxmlError,
+    Result,
 };
 
+use std::borrow::Cow;
+use std::cell::OnceCell;
+use std::io::Cursor;
+
+/* Marker of the end of the spans of a style */
+const SPAN_END: u32 = 0xffffffff;
+
+/* Flag of the pools whose strings are encoded in UTF-8 */
+const UTF8_FLAG: u32 = 1 << 8;
+
+/* Flag of the long form of a string length: the length is encoded on two
+ * bytes (UTF-8) or two uint16_t (UTF-16) instead of one */
+const UTF8_LONG_LENGTH: u8 = 0x80;
+const UTF16_LONG_LENGTH: u16 = 0x8000;
+
+/* Largest length of a string in a UTF-8 pool: its lengths are encoded on
+ * at most two bytes, the high bit of the first one being the flag above */
+const UTF8_MAX_LENGTH: usize = 0x7fff;
+
+/* Size of a ResStringPool_header: the chunk header, then the string count,
+ * style count, flags, strings start and styles start */
+const STRING_POOL_HEADER_SIZE: u16 = 28;
+
+/**
+ * A span of styled text in a string of the pool, e.g., the part of the
+ * string in bold for `Hello <b>world</b>`.
+ */
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct StringPoolSpan {
+    /* Index (in the same pool) of the name of the tag. Attributes of the
+     * tag are appended to its name, e.g., `a;href=https://example.com` */
+    pub name: u32,
+
+    /* Range of characters (UTF-16 code units) the span applies to,
+     * both ends included */
+    pub first_char: u32,
+    pub last_char: u32,
+}
+
+/// Read the length of a UTF-8 string at `pos`: one byte, or two if the
+/// high bit of the first one is set
+fn read_utf8_length(data: &[u8], pos: &mut usize) -> Option<usize> {
+    let first = *data.get(*pos)?;
+    *pos += 1;
+    if first & UTF8_LONG_LENGTH == 0 {
+        return Some(first as usize);
+    }
+
+    let second = *data.get(*pos)?;
+    *pos += 1;
+    Some(((first & !UTF8_LONG_LENGTH) as usize) << 8 | second as usize)
+}
+
+/// Read a uint16_t at `pos`
+fn read_u16_at(data: &[u8], pos: &mut usize) -> Option<u16> {
+    let bytes = data.get(*pos..*pos + 2)?;
+    *pos += 2;
+    Some(u16::from_le_bytes([bytes[0], bytes[1]]))
+}
+
+/// Read the length of a UTF-16 string at `pos`: one uint16_t, or two if
+/// the high bit of the first one is set
+fn read_utf16_length(data: &[u8], pos: &mut usize) -> Option<usize> {
+    let first = read_u16_at(data, pos)?;
+    if first & UTF16_LONG_LENGTH == 0 {
+        return Some(first as usize);
+    }
+
+    let second = read_u16_at(data, pos)?;
+    Some(((first & !UTF16_LONG_LENGTH) as usize) << 16 | second as usize)
+}
+
+/// Decode the string located at `offset` in the string data of a pool.
+/// Valid UTF-8 strings are borrowed, without any copy. Returns `None` if
+/// the string goes past the end of the data.
+fn decode_string(data: &[u8], offset: usize, is_utf8: bool) -> Option<Cow<'_, str>> {
+    let mut pos = offset;
+
+    if is_utf8 {
+        /* There are two lengths: the number of UTF-16 code units of the
+         * decoded string, then the number of encoded bytes */
+        let _decoded_size = read_utf8_length(data, &mut pos)?;
+        let encoded_size = read_utf8_length(data, &mut pos)?;
+        let bytes = data.get(pos..pos + encoded_size)?;
+
+        /* Some tools write invalid sequences (e.g., modified UTF-8),
+         * which Android tolerates as well */
+        Some(String::from_utf8_lossy(bytes))
+    } else {
+        let str_size = read_utf16_length(data, &mut pos)?;
+        let bytes = data.get(pos..pos + str_size * 2)?;
+        let units: Vec<u16> = bytes.chunks_exact(2)
+                                   .map(|unit| u16::from_le_bytes([unit[0], unit[1]]))
+                                   .collect();
+
+        Some(Cow::Owned(String::from_utf16_lossy(&units)))
+    }
+}
+
+/// Encode a string at the end of the string data of a pool, with its
+/// length(s) before it and a null terminator after it. In a UTF-8 pool,
+/// the string must not be longer than `UTF8_MAX_LENGTH` bytes.
+fn encode_string(data: &mut Vec<u8>, string: &str, is_utf8: bool) {
+    let units = string.encode_utf16().count();
+
+    if is_utf8 {
+        let mut write_length = |len: usize| match len > 0x7f {
+            true => {
+                data.write_u8_le(UTF8_LONG_LENGTH | (len >> 8) as u8);
+                data.write_u8_le(len as u8);
+            },
+            false => data.write_u8_le(len as u8),
+        };
+        write_length(units);
+        write_length(string.len());
+        data.extend_from_slice(string.as_bytes());
+        data.write_u8_le(0);
+    } else {
+        match units > 0x7fff {
+            true => {
+                data.write_u16_le(UTF16_LONG_LENGTH | (units >> 16) as u16);
+                data.write_u16_le(units as u16);
+            },
+            false => data.write_u16_le(units as u16),
+        }
+        for unit in string.encode_utf16() {
+            data.write_u16_le(unit);
+        }
+        data.write_u16_le(0);
+    }
+}
+
+/// Serialise a complete string pool chunk holding `strings`, in this
+/// order and without styles. A UTF-8 pool is written in UTF-16 instead if
+/// one of the strings is too long for the lengths of UTF-8 pools.
+pub fn encode_string_pool<S: AsRef<str>>(strings: &[S], is_utf8: bool) -> Vec<u8> {
+    /* A UTF-8 string is never shorter than its UTF-16 length in code
+     * units, so only its length in bytes needs to be checked */
+    let is_utf8 = is_utf8 && strings.iter().all(|string| string.as_ref().len() <= UTF8_MAX_LENGTH);
+    let header_size = STRING_POOL_HEADER_SIZE as u32;
+
+    let mut offsets = Vec::new();
+    let mut string_data = Vec::new();
+    for string in strings.iter() {
+        offsets.write_u32_le(string_data.len() as u32);
+        encode_string(&mut string_data, string.as_ref(), is_utf8);
+    }
+
+    /* The chunk is padded to a multiple of 4 bytes */
+    while string_data.len() % 4 != 0 {
+        string_data.write_u8_le(0);
+    }
+
+    let mut header = Vec::new();
+    header.write_u32_le(strings.len() as u32);
+    header.write_u32_le(0);
+    header.write_u32_le(if is_utf8 { UTF8_FLAG } else { 0 });
+    header.write_u32_le(header_size + offsets.len() as u32);
+    header.write_u32_le(0);
+
+    offsets.extend_from_slice(&string_data);
+    let mut chunk = Vec::new();
+    write_ext::write_chunk(&mut chunk, XmlTypes::ResStringPoolType as u16, &header, &offsets);
+    chunk
+}
+
 /**
  * Header of a chunk representing a pool of strings
  *
@@ -29,9 +195,15 @@ use byteorder::{
  * uint32_t indices into the string table is another array of indices
  * into a style table starting at stylesStart.  Each entry in the
  * style table is an array of ResStringPool_span structures.
+ *
+ * The pool borrows the bytes of its chunk from the input: nothing is
+ * decoded upfront, a string is only decoded when it is requested, and
+ * valid UTF-8 strings are returned without any copy. Decoded strings can
+ * optionally be cached, see `with_cache`. Use `into_owned` to keep the
+ * pool beyond the lifetime of the input.
  */
 #[derive(Debug)]
-pub struct StringPool {
+pub struct StringPool<'a> {
     /* Chunk header */
     header: ChunkHeader,
 
@@ -60,90 +232,130 @@ pub struct StringPool {
     /* Index from header of the style data. */
     styles_start: u32,
 
-    strings_offsets: Vec<u32>,
-    styles_offsets: Vec<u32>,
-    strings: Vec<String>,
+    /* Raw offset table, one uint32_t per string, relative to stringsStart */
+    strings_offsets: Cow<'a, [u8]>,
+
+    /* Raw string data, from stringsStart to the end of the chunk */
+    string_data: Cow<'a, [u8]>,
+
+    /* Strings added with `push`, after the ones of the chunk */
+    pushed: Vec<String>,
+
+    /* Strings of the chunk decoded so far, if caching is enabled */
+    cache: Option<Vec<OnceCell<String>>>,
+
+    /* Style spans of the first `style_count` strings */
+    styles: Vec<Vec<StringPoolSpan>>,
 }
 
-impl StringPool {
+impl<'a> StringPool<'a> {
 
-    pub fn from_buff(axml_buff: &mut Cursor<Vec<u8>>,
-                 global_strings: &mut Vec<String>) -> Result<Self, Error> {
+    /// A pool without strings, e.g., for documents without string pool
+    pub fn empty() -> Self {
+        StringPool {
+            header: ChunkHeader {
+                chunk_type: XmlTypes::ResStringPoolType,
+                header_size: 0,
+                size: 0,
+            },
+            string_count: 0,
+            style_count: 0,
+            flags: 0,
+            is_utf8: false,
+            strings_start: 0,
+            styles_start: 0,
+            strings_offsets: Cow::Borrowed(&[]),
+            string_data: Cow::Borrowed(&[]),
+            pushed: Vec::new(),
+            cache: None,
+            styles: Vec::new(),
+        }
+    }
+
+    /// Read the string pool chunk whose type was just read from
+    /// `axml_buff`, copying its bytes so that the pool can outlive the
+    /// buffer (e.g., in a `ResTable`)
+    pub fn from_buff<T: AsRef<[u8]>>(axml_buff: &mut Cursor<T>) -> Result<StringPool<'static>> {
 
         /* Go back 2 bytes, to account from the block type */
         let initial_offset = axml_buff.position() - 2;
-        axml_buff.set_position(initial_offset);
-        let initial_offset = initial_offset as u32;
+        let pool = StringPool::parse(axml_buff.get_ref().as_ref(), initial_offset as usize)?.into_owned();
 
-        /* Parse chunk header */
-        let header = ChunkHeader::from_buff(axml_buff, XmlTypes::ResStringPoolType)
-                     .expect("Error: cannot get chunk header from string pool");
+        /* Skip any padding at the end of the chunk */
+        axml_buff.set_position(initial_offset + pool.header.size as u64);
 
-        /* Get remaining members */
-        let string_count = axml_buff.read_u32::<LittleEndian>().unwrap();
-        let style_count = axml_buff.read_u32::<LittleEndian>().unwrap();
-        let flags = axml_buff.read_u32::<LittleEndian>().unwrap();
-        let is_utf8 = (flags & (1<<8)) != 0;
-        let strings_start = axml_buff.read_u32::<LittleEndian>().unwrap();
-        let styles_start = axml_buff.read_u32::<LittleEndian>().unwrap();
-
-        /* Get strings offsets */
-        let mut strings_offsets = Vec::new();
-        for _ in 0..string_count {
-            let offset = axml_buff.read_u32::<LittleEndian>().unwrap();
-            strings_offsets.push(offset);
-        }
+        Ok(pool)
+    }
+
+    /// Read the string pool chunk at the beginning of `chunk`
+    pub fn from_bytes(chunk: &'a [u8]) -> Result<Self> {
+        StringPool::parse(chunk, 0)
+    }
 
-        /* Get styles offsets */
-        let mut styles_offsets = Vec::new();
-        for _ in 0..style_count {
-            let offset = axml_buff.read_u32::<LittleEndian>().unwrap();
-            styles_offsets.push(offset);
+    /// Read the string pool chunk located at `offset` in `data`, borrowing
+    /// its strings from `data`
+    pub fn parse(data: &'a [u8], offset: usize) -> Result<Self> {
+        let mut axml_buff = Cursor::new(data);
+        axml_buff.set_position(offset as u64);
+
+        /* Parse chunk header, which checks that the chunk fits in `data` */
+        let header = ChunkHeader::from_buff(&mut axml_buff, XmlTypes::ResStringPoolType)?;
+        if header.header_size < STRING_POOL_HEADER_SIZE {
+            return Err(AxmlError::SizeMismatch {
+                offset: offset as u64,
+                header_size: header.header_size,
+                size: header.size,
+            });
         }
 
-        /* Strings */
-        for offset in strings_offsets.iter() {
-            // let current_start = (strings_start + offset + 8) as u64;
-            let current_start = (initial_offset + strings_start + offset) as u64;
-            axml_buff.set_position(current_start);
-
-            let str_size;
-            let decoded_string;
-
-            if is_utf8 {
-                /* NOTE for resources.arsc files
-                 *
-                 * Each String entry contains Length header (2 bytes to 4 bytes) + Actual String + [0x00]
-                 * Length header sometime contain duplicate values e.g. 20 20
-                 * Actual string sometime contains 00, which need to be ignored
-                 * Ending zero might be  2 byte or 4 byte
-                 *
-                 * TODO: Consider both Length bytes and String length > 32767 characters
-                 *
-                 * Actually, there are two length if the file is in UTF-8: the encoded and decoded lengths
-                 */
-
-                let _encoded_size = axml_buff.read_u8().unwrap() as u32;
-                str_size = axml_buff.read_u8().unwrap() as u32;
-                let mut str_buff = Vec::with_capacity(str_size as usize);
-                let mut chunk = axml_buff.take(str_size.into());
-
-                chunk.read_to_end(&mut str_buff).unwrap();
-                // decoded_string = String::from_utf8(str_buff).unwrap();
-                decoded_string = String::from_utf8(str_buff)
-                                 .expect("Error: cannot decode string, using raw");
-            } else {
-                str_size = axml_buff.read_u16::<LittleEndian>().unwrap() as u32;
-                let iter = (0..str_size as usize)
-                        .map(|_| axml_buff.read_u16::<LittleEndian>().unwrap());
-                decoded_string = std::char::decode_utf16(iter).collect::<Result<String, _>>().unwrap();
-            }
+        /* Get remaining members */
+        let string_count = axml_buff.read_u32_le()?;
+        let style_count = axml_buff.read_u32_le()?;
+        let flags = axml_buff.read_u32_le()?;
+        let is_utf8 = (flags & UTF8_FLAG) != 0;
+        let strings_start = axml_buff.read_u32_le()?;
+        let styles_start = axml_buff.read_u32_le()?;
+
+        /* Everything else must be within the chunk */
+        let chunk_end = offset + header.size as usize;
+        let chunk = &data[..chunk_end];
+        let slice = |start: usize, len: Option<usize>| {
+            len.and_then(|len| chunk.get(start..start.checked_add(len)?))
+               .ok_or(AxmlError::Truncated { offset: start as u64 })
+        };
+
+        /* Offsets of the strings, then of the styles */
+        let offsets_start = offset + header.header_size as usize;
+        let strings_offsets = slice(offsets_start, (string_count as usize).checked_mul(4))?;
+        let styles_offsets = slice(offsets_start + strings_offsets.len(), (style_count as usize).checked_mul(4))?;
+
+        /* Keep the string data as is, strings are decoded on demand */
+        let data_start = offset + strings_start as usize;
+        let string_data = match string_count {
+            0 => &chunk[..0],
+            _ => chunk.get(data_start..).ok_or(AxmlError::Truncated { offset: data_start as u64 })?,
+        };
+
+        /* Styles, each one is an array of spans ended by SPAN_END */
+        let mut styles_buff = Cursor::new(chunk);
+        let mut styles = Vec::new();
+        for style_offset in styles_offsets.chunks_exact(4) {
+            let style_offset = u32::from_le_bytes([style_offset[0], style_offset[1], style_offset[2], style_offset[3]]);
+            styles_buff.set_position(offset as u64 + styles_start as u64 + style_offset as u64);
 
-            if str_size > 0 {
-                global_strings.push(decoded_string);
+            let mut spans = Vec::new();
+            loop {
+                let name = styles_buff.read_u32_le()?;
+                if name == SPAN_END {
+                    break;
+                }
+
+                let first_char = styles_buff.read_u32_le()?;
+                let last_char = styles_buff.read_u32_le()?;
+                spans.push(StringPoolSpan { name, first_char, last_char });
             }
+            styles.push(spans);
         }
-        let strings = global_strings.to_vec();
 
         /* Build and return the object */
         Ok(StringPool {
@@ -154,9 +366,165 @@ impl StringPool {
             is_utf8,
             strings_start,
             styles_start,
-            strings_offsets,
-            styles_offsets,
-            strings
+            strings_offsets: Cow::Borrowed(strings_offsets),
+            string_data: Cow::Borrowed(string_data),
+            pushed: Vec::new(),
+            cache: None,
+            styles,
         })
     }
+
+    /// Copy the bytes of the pool, so that it does not borrow the input
+    /// anymore
+    pub fn into_owned(self) -> StringPool<'static> {
+        StringPool {
+            header: self.header,
+            string_count: self.string_count,
+            style_count: self.style_count,
+            flags: self.flags,
+            is_utf8: self.is_utf8,
+            strings_start: self.strings_start,
+            styles_start: self.styles_start,
+            strings_offsets: Cow::Owned(self.strings_offsets.into_owned()),
+            string_data: Cow::Owned(self.string_data.into_owned()),
+            pushed: self.pushed,
+            cache: self.cache,
+            styles: self.styles,
+        }
+    }
+
+    /// Keep the strings once they are decoded, which is worth it for UTF-16
+    /// pools whose strings are requested several times
+    pub fn with_cache(mut self) -> Self {
+        self.cache = Some((0..self.string_count).map(|_| OnceCell::new()).collect());
+        self
+    }
+
+    /// String at `index`, as referenced by the other chunks. The string is
+    /// decoded on each call (or on the first one, with `with_cache`), valid
+    /// UTF-8 strings are borrowed from the pool. Returns `None` if the
+    /// index is out of bounds, or if the string goes past the end of the
+    /// chunk.
+    pub fn get(&self, index: u32) -> Option<Cow<'_, str>> {
+        if index >= self.string_count {
+            let pushed = self.pushed.get((index - self.string_count) as usize)?;
+            return Some(Cow::Borrowed(pushed.as_str()));
+        }
+
+        let cell = match &self.cache {
+            Some(cache) => &cache[index as usize],
+            None => return self.decode(index),
+        };
+        if cell.get().is_none() {
+            let _ = cell.set(self.decode(index)?.into_owned());
+        }
+
+        cell.get().map(|string| Cow::Borrowed(string.as_str()))
+    }
+
+    /// Decode the string of the chunk at `index`
+    fn decode(&self, index: u32) -> Option<Cow<'_, str>> {
+        let position = index as usize * 4;
+        let offset = self.strings_offsets.get(position..position + 4)?;
+        let offset = u32::from_le_bytes([offset[0], offset[1], offset[2], offset[3]]);
+
+        decode_string(&self.string_data, offset as usize, self.is_utf8)
+    }
+
+    /// Add a string at the end of the pool, returning its index. This is
+    /// used to merge the strings of several pools (e.g., of split APKs).
+    pub fn push(&mut self, string: String) -> u32 {
+        self.pushed.push(string);
+        self.len() as u32 - 1
+    }
+
+    /// Number of strings in the pool
+    pub fn len(&self) -> usize {
+        self.string_count as usize + self.pushed.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// All the strings of the pool, in order. Strings which cannot be
+    /// decoded are returned empty.
+    pub fn iter(&self) -> impl Iterator<Item = Cow<'_, str>> + '_ {
+        (0..self.len() as u32).map(move |index| self.get(index).unwrap_or_default())
+    }
+
+    /// Whether the strings are encoded in UTF-8 (instead of UTF-16)
+    pub fn is_utf8(&self) -> bool {
+        self.is_utf8
+    }
+
+    /// Style spans of the string at `index`, empty if it is not styled
+    pub fn styles(&self, index: usize) -> &[StringPoolSpan] {
+        self.styles.get(index).map(|spans| spans.as_slice()).unwrap_or(&[])
+    }
+
+    /// String at `index` with its style spans turned back into markup,
+    /// e.g., `Hello <b>world</b>`. The text itself is escaped.
+    pub fn styled_string(&self, index: usize) -> Option<String> {
+        let string = self.get(index as u32)?;
+        let spans = self.styles(index);
+        let units: Vec<u16> = string.encode_utf16().collect();
+        let len = units.len();
+
+        /* Span ends are inclusive, tags are inserted at these boundaries */
+        let span_end = |span: &StringPoolSpan| (span.last_char as usize + 1).min(len);
+        let mut boundaries = vec![0, len];
+        for span in spans.iter() {
+            boundaries.push((span.first_char as usize).min(len));
+            boundaries.push(span_end(span));
+        }
+        boundaries.sort_unstable();
+        boundaries.dedup();
+
+        let mut styled = String::new();
+        let mut open = Vec::<&StringPoolSpan>::new();
+        for (i, &position) in boundaries.iter().enumerate() {
+            /* Close the innermost spans first */
+            while open.iter().any(|span| span_end(span) <= position) {
+                if let Some(span) = open.pop() {
+                    styled.push_str(&self.span_tag(span, true));
+                }
+            }
+
+            for span in spans.iter().filter(|span| span.first_char as usize == position) {
+                styled.push_str(&self.span_tag(span, false));
+                open.push(span);
+            }
+
+            if let Some(&next) = boundaries.get(i + 1) {
+                let text = String::from_utf16_lossy(&units[position..next]);
+                styled.push_str(&quick_xml::escape::partial_escape(&text));
+            }
+        }
+
+        Some(styled)
+    }
+
+    /// Opening or closing tag of a span. The name of a span is the name of
+    /// the tag, followed by its attributes, e.g., `a;href=https://example.com`
+    fn span_tag(&self, span: &StringPoolSpan, closing: bool) -> String {
+        let name = self.get(span.name).unwrap_or_default();
+        let mut parts = name.split(';');
+        let tag = parts.next().unwrap_or("");
+
+        if closing {
+            return format!("</{}>", tag);
+        }
+
+        let mut opening = format!("<{}", tag);
+        for attribute in parts {
+            match attribute.split_once('=') {
+                Some((key, value)) => opening.push_str(&format!(" {}=\"{}\"", key, quick_xml::escape::escape(value))),
+                None => opening.push_str(&format!(" {}", attribute)),
+            }
+        }
+        opening.push('>');
+
+        opening
+    }
 }
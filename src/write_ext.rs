@@ -0,0 +1,35 @@
+/* Size of the header common to every chunk: type, header size and size */
+pub(crate) const CHUNK_HEADER_SIZE: u16 = 8;
+
+/* Little-endian integer writers, the counterpart of `ReadExt` */
+pub(crate) trait WriteExt {
+    fn write_u8_le(&mut self, value: u8);
+    fn write_u16_le(&mut self, value: u16);
+    fn write_u32_le(&mut self, value: u32);
+}
+
+impl WriteExt for Vec<u8> {
+    fn write_u8_le(&mut self, value: u8) {
+        self.push(value);
+    }
+
+    fn write_u16_le(&mut self, value: u16) {
+        self.extend_from_slice(&value.to_le_bytes());
+    }
+
+    fn write_u32_le(&mut self, value: u32) {
+        self.extend_from_slice(&value.to_le_bytes());
+    }
+}
+
+/// Append a chunk to `out`: the common header, then the rest of the chunk
+/// header (`header`), then the data of the chunk
+pub(crate) fn write_chunk(out: &mut Vec<u8>, chunk_type: u16, header: &[u8], data: &[u8]) {
+    let header_size = CHUNK_HEADER_SIZE as usize + header.len();
+
+    out.write_u16_le(chunk_type);
+    out.write_u16_le(header_size as u16);
+    out.write_u32_le((header_size + data.len()) as u32);
+    out.extend_from_slice(header);
+    out.extend_from_slice(data);
+}
@@ -1,15 +1,13 @@
 use std::fmt;
-use std::io::{
-    Error,
-    Cursor,
-};
-use byteorder::{
-    LittleEndian,
-    ReadBytesExt
+use std::io::Cursor;
+use crate::read_ext::ReadExt;
+use crate::error::{
+    AxmlError,
+    Result,
 };
 
 /* Type identifiers for chunks. Only includes the ones related to XML */
-#[derive(PartialEq, Debug)]
+#[derive(PartialEq, Debug, Clone, Copy)]
 pub enum XmlTypes {
     ResNullType                 = 0x0000,
     ResStringPoolType           = 0x0001,
@@ -39,13 +37,15 @@ pub enum XmlTypes {
 }
 
 impl XmlTypes {
-    pub fn parse_block_type(buff: &mut Cursor<Vec<u8>>) -> Result<Self, Error> {
-        let raw_block_type = buff.read_u16::<LittleEndian>();
-        let raw_block_type = match raw_block_type {
-            Ok(block) => block,
-            Err(e) => return Err(e),
-        };
+    pub fn parse_block_type<T: AsRef<[u8]>>(buff: &mut Cursor<T>) -> Result<Self> {
+        let offset = buff.position();
+        let raw_block_type = buff.read_u16_le()?;
+
+        XmlTypes::from_u16(raw_block_type)
+            .ok_or(AxmlError::UnknownChunkType { offset, chunk_type: raw_block_type })
+    }
 
+    pub fn from_u16(raw_block_type: u16) -> Option<Self> {
         let block_type = match raw_block_type {
             0x0000 => XmlTypes::ResNullType,
             0x0001 => XmlTypes::ResStringPoolType,
@@ -72,11 +72,10 @@ impl XmlTypes {
             0x0202 => XmlTypes::ResTableTypeSpecType,
             0x0203 => XmlTypes::ResTableLibraryType,
 
-            /* If we find an unknown type, we stop and panic */
-            _ => panic!("Error: unknown block type {:02X}", raw_block_type)
+            _ => return None,
         };
 
-        Ok(block_type)
+        Some(block_type)
     }
 }
 
@@ -0,0 +1,121 @@
+use axml_parser::res_table::ResTableEntryValue;
+use axml_parser::{
+    parse_app_manifest,
+    ApkBundle,
+    AxmlError,
+    ResConfig,
+    ResourceResolver,
+    SplitApk,
+};
+
+/* The same app as `.apks`, `.xapk` and `.apkm`, generated by
+ * tools/gen_test_fixtures.py: a base APK, a German configuration split,
+ * and in some of them a feature split with its own configuration split */
+const APKS: &str = "tests/fixtures/app.apks";
+const XAPK: &str = "tests/fixtures/app.xapk";
+const APKM: &str = "tests/fixtures/app.apkm";
+
+/* Bundle whose configuration split has a truncated resource table, with
+ * an entry which is not an APK */
+const BROKEN: &str = "tests/fixtures/broken.apks";
+
+fn split_names(bundle: &ApkBundle) -> Vec<Option<&str>> {
+    bundle.apks().map(|apk| apk.split.as_deref()).collect()
+}
+
+fn split<'a>(bundle: &'a ApkBundle, name: &str) -> &'a SplitApk {
+    bundle.splits.iter().find(|apk| apk.split.as_deref() == Some(name)).unwrap()
+}
+
+#[test]
+fn base_and_splits_are_found() {
+    let bundle = ApkBundle::open(APKS).unwrap();
+
+    /* The standalone APK is left out */
+    assert_eq!(bundle.base.path, "splits/base-master.apk");
+    assert_eq!(split_names(&bundle), [None, Some("config.de"), Some("feature1"), Some("feature1.config.de")]);
+    assert!(bundle.apks().all(|apk| apk.package == "com.example.app"));
+    assert!(bundle.skipped.is_empty());
+
+    let xapk = ApkBundle::open(XAPK).unwrap();
+    assert_eq!(xapk.base.path, "com.example.app.apk");
+    assert_eq!(split_names(&xapk), [None, Some("config.de")]);
+
+    /* The base APK does not come first in this one */
+    let apkm = ApkBundle::open(APKM).unwrap();
+    assert_eq!(apkm.base.path, "base.apk");
+    assert_eq!(split_names(&apkm), [None, Some("config.de"), Some("feature1")]);
+}
+
+#[test]
+fn configuration_splits_of_the_base_are_told_apart() {
+    let bundle = ApkBundle::open(APKS).unwrap();
+
+    assert!(!bundle.base.is_base_config_split());
+    assert!(split(&bundle, "config.de").is_base_config_split());
+
+    let feature = split(&bundle, "feature1");
+    assert!(feature.is_feature_split);
+    assert!(!feature.is_base_config_split());
+
+    let feature_config = split(&bundle, "feature1.config.de");
+    assert_eq!(feature_config.config_for_split.as_deref(), Some("feature1"));
+    assert!(!feature_config.is_feature_split);
+    assert!(!feature_config.is_base_config_split());
+}
+
+#[test]
+fn resources_of_configuration_splits_are_merged() {
+    for path in [APKS, XAPK, APKM] {
+        let bundle = ApkBundle::open(path).unwrap();
+        let resources = bundle.resources.as_ref().unwrap();
+        assert!(bundle.resources_errors.is_empty());
+
+        /* A new drawable, and a new translation of a string of the base */
+        let icon_hd = resources.resource_name(0x7f020001).unwrap();
+        assert_eq!((icon_hd.type_name.as_str(), icon_hd.entry.as_str()), ("drawable", "icon_hd"));
+        assert_eq!(resources.resource_name(0x7f020000).unwrap().entry, "icon");
+
+        let app_names: Vec<String> = resources.entries(0x7f030000).into_iter()
+            .map(|(_, entry)| match &entry.value {
+                ResTableEntryValue::Simple(value) => value.format(&resources.strings, None),
+                ResTableEntryValue::Complex { .. } => panic!("{}: app_name is not a string", path),
+            })
+            .collect();
+        assert_eq!(app_names, ["My App", "Mon App", "Mi App", "Meine App"], "{}", path);
+
+        let german = ResConfig { language: *b"de", ..Default::default() };
+        let (res_type, _) = resources.resolve(0x7f030000, &german).unwrap();
+        assert_eq!(res_type.config.to_string(), "de");
+    }
+}
+
+#[test]
+fn manifest_of_a_bundle_is_the_base_one() {
+    let contents = parse_app_manifest(APKM).unwrap();
+
+    assert_eq!(contents.pkg_name, "com.example.app");
+    assert_eq!(contents.activities, ["com.example.app.MainActivity"]);
+    assert_eq!(contents.requested_perms, ["android.permission.INTERNET"]);
+}
+
+#[test]
+fn broken_splits_are_skipped() {
+    let bundle = ApkBundle::open(BROKEN).unwrap();
+
+    assert_eq!(split_names(&bundle), [None, Some("config.de")]);
+    assert_eq!(bundle.skipped.len(), 1);
+    assert_eq!(bundle.skipped[0].0, "splits/garbage.apk");
+    assert!(matches!(bundle.skipped[0].1, AxmlError::Zip(_)));
+
+    /* The resources of the base APK are still there */
+    assert_eq!(bundle.resources_errors.len(), 1);
+    assert_eq!(bundle.resources_errors[0].0, "splits/base-de.apk");
+    let resources = bundle.resources.as_ref().unwrap();
+    assert_eq!(resources.resource_id("string", "app_name"), Some(0x7f030000));
+    assert!(resources.resource_name(0x7f020001).is_none());
+
+    let contents = parse_app_manifest(BROKEN).unwrap();
+    assert_eq!(contents.pkg_name, "com.example.app");
+    assert_eq!(contents.resources_errors.len(), 1);
+}
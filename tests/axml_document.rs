@@ -0,0 +1,199 @@
+use axml_parser::data_value_type::DataValueType;
+use axml_parser::raw_chunk::RawBody;
+use axml_parser::xml_types::XmlTypes;
+use axml_parser::{
+    parse_manifest_bytes,
+    verify_roundtrip,
+    AxmlDocument,
+    AxmlEvent,
+    AxmlReader,
+    Element,
+    Node,
+    RawDocument,
+    ResValue,
+    Text,
+    XmlEncoder,
+};
+
+const ANDROID_NS: &str = "http://schemas.android.com/apk/res/android";
+
+const MANIFEST: &str = r#"<?xml version="1.0" encoding="utf-8"?>
+<manifest xmlns:android="http://schemas.android.com/apk/res/android" package="com.example.app">
+    <uses-permission android:name="android.permission.INTERNET" />
+    <application android:label="Example" android:debuggable="true">
+        <activity android:name=".MainActivity" android:exported="true" />
+    </application>
+</manifest>
+"#;
+
+fn manifest() -> AxmlDocument {
+    let bytes = XmlEncoder::new().encode(MANIFEST).unwrap();
+    AxmlDocument::from_bytes(&bytes).unwrap()
+}
+
+#[test]
+fn toggle_debuggable() {
+    let mut document = manifest();
+    let debuggable = XmlEncoder::new().attribute(Some(ANDROID_NS), "debuggable", "false").unwrap();
+    document.find_mut("application").unwrap().set_attr(debuggable);
+
+    let patched = AxmlDocument::from_bytes(&document.to_bytes()).unwrap();
+    let application = patched.find("application").unwrap();
+    let attr = application.attr("android", "debuggable").unwrap();
+    assert_eq!(attr.resource_id, Some(0x0101000f));
+    assert_eq!(attr.typed_value.data, 0);
+    assert_eq!(attr.value, "false");
+    assert_eq!(application.attr_value("android", "label"), Some("Example"));
+
+    /* Removing it works as well */
+    let mut document = patched;
+    assert!(document.find_mut("application").unwrap().remove_attr("android", "debuggable").is_some());
+    let patched = AxmlDocument::from_bytes(&document.to_bytes()).unwrap();
+    assert!(patched.find("application").unwrap().attr("android", "debuggable").is_none());
+}
+
+#[test]
+fn add_uses_permission() {
+    let mut document = manifest();
+
+    let mut permission = Element::new("uses-permission");
+    permission.set_attr(XmlEncoder::new().attribute(Some(ANDROID_NS), "name", "android.permission.CAMERA").unwrap());
+    document.root.children.insert(1, permission.into());
+
+    let contents = parse_manifest_bytes(&document.to_bytes()).unwrap();
+    assert_eq!(contents.pkg_name, "com.example.app");
+    assert_eq!(contents.requested_perms, ["android.permission.INTERNET", "android.permission.CAMERA"]);
+    assert_eq!(contents.activities, [".MainActivity"]);
+}
+
+#[test]
+fn special_indices_only_name_attributes_without_namespace() {
+    let layout = r#"<View xmlns:android="http://schemas.android.com/apk/res/android"
+        android:id="@0x7f010000" android:layout_width="16dp" style="@0x01030237" class="com.example.Custom" />"#;
+    let bytes = XmlEncoder::new().encode(layout).unwrap();
+
+    let raw = RawDocument::parse(&bytes).unwrap();
+    let element = raw.root.find(XmlTypes::ResXmlStartElementType).unwrap();
+    let data = match &element.body {
+        RawBody::Data(data) => data,
+        RawBody::Chunks(_) => panic!("start element chunk without data"),
+    };
+    let index = |offset: usize| u16::from_le_bytes([data[offset], data[offset + 1]]);
+
+    /* Attributes are sorted as android:id, android:layout_width, style, class
+     * and the indices are 1-based, android:id does not count as the ID */
+    assert_eq!((index(14), index(16), index(18)), (0, 4, 3));
+}
+
+#[test]
+fn unknown_chunks_are_written_back() {
+    let bytes = XmlEncoder::new().encode(MANIFEST).unwrap();
+
+    /* Insert a chunk of an unknown type between the resource map and the
+     * elements, and update the size of the document */
+    let unknown = [0x77, 0x07, 0x08, 0x00, 0x10, 0x00, 0x00, 0x00, 1, 2, 3, 4, 5, 6, 7, 8];
+    let mut raw = RawDocument::parse(&bytes).unwrap();
+    let map_end = raw.root.find(XmlTypes::ResXmlResourceMapType).unwrap().to_bytes().len()
+                  + raw.root.find(XmlTypes::ResStringPoolType).unwrap().to_bytes().len()
+                  + 8;
+    let mut patched = raw.to_bytes();
+    patched.splice(map_end..map_end, unknown);
+    let size = patched.len() as u32;
+    patched[4..8].copy_from_slice(&size.to_le_bytes());
+    raw = RawDocument::parse(&patched).unwrap();
+    assert_eq!(raw.to_bytes(), patched);
+
+    let document = AxmlDocument::from_bytes(&patched).unwrap();
+    assert_eq!(document.unknown_chunks.len(), 1);
+
+    let written = document.to_bytes();
+    let chunks: Vec<Vec<u8>> = AxmlReader::from_bytes(&written)
+        .filter_map(|event| match event.unwrap() {
+            AxmlEvent::UnknownChunk(chunk) => Some(chunk.data),
+            _ => None,
+        })
+        .collect();
+    assert_eq!(chunks, [unknown.to_vec()]);
+    assert_eq!(AxmlDocument::from_bytes(&written).unwrap().find_all("activity").len(), 1);
+}
+
+/// Text and element children of an element, in order
+fn children(element: &Element) -> Vec<String> {
+    element.children.iter().map(|child| match child {
+        Node::Element(element) => format!("<{}>", element.name),
+        Node::Text(text) => text.text.clone(),
+    }).collect()
+}
+
+#[test]
+fn text_keeps_its_place_among_elements() {
+    let layout = r#"<resources>
+    <string name="greeting">Hello <b>you</b> and <i>them</i>!</string>
+</resources>"#;
+    let mut document = XmlEncoder::new().parse(layout).unwrap();
+    let string = document.find_mut("string").unwrap();
+    assert_eq!(children(string), ["Hello ", "<b>", " and ", "<i>", "!"]);
+
+    /* Remove an element and add text around it */
+    string.remove_children("i", |_| true);
+    string.children.insert(3, Text::new("everyone", 2).into());
+    string.children.insert(0, Element::new("u").into());
+
+    let patched = AxmlDocument::from_bytes(&document.to_bytes()).unwrap();
+    let string = patched.find("string").unwrap();
+    assert_eq!(children(string), ["<u>", "Hello ", "<b>", " and ", "everyone", "!"]);
+    assert_eq!(string.text(), "Hello  and everyone!");
+    assert_eq!(string.find("b").unwrap().text(), "you");
+}
+
+#[test]
+fn text_keeps_its_typed_value_and_line() {
+    let layout = r#"<resources>
+    <string name="greeting">Hello</string>
+</resources>"#;
+    let bytes = XmlEncoder::new().encode(layout).unwrap();
+
+    /* The typed value of the text as older tools wrote it, a string */
+    let document = AxmlDocument::from_bytes(&bytes).unwrap();
+    let text_node = match &document.find("string").unwrap().children[0] {
+        Node::Text(text) => text.clone(),
+        Node::Element(_) => panic!("no text node"),
+    };
+    assert_eq!(text_node.line, 2);
+    assert_eq!(text_node.typed_value.data_type, DataValueType::TypeNull);
+
+    let mut raw = RawDocument::parse(&bytes).unwrap();
+    match &mut raw.root.find_mut(XmlTypes::ResXmlCDataType).unwrap().body {
+        /* ResXMLTree_cdataExt: index of the text, then a Res_value */
+        RawBody::Data(data) => {
+            data[7] = DataValueType::TypeString.to_val();
+            data.copy_within(0..4, 8);
+        },
+        RawBody::Chunks(_) => panic!("CDATA chunk without data"),
+    }
+    let bytes = raw.to_bytes();
+    verify_roundtrip(&bytes).unwrap();
+
+    /* It is written back as it was, pointing to the text in the new pool */
+    let mut document = AxmlDocument::from_bytes(&bytes).unwrap();
+    document.root.children.insert(0, Text::new("Before", 1).into());
+    let written = document.to_bytes();
+    let patched = AxmlDocument::from_bytes(&written).unwrap();
+    let events: Vec<(String, ResValue, u32)> = AxmlReader::from_bytes(&written)
+        .filter_map(|event| match event.unwrap() {
+            AxmlEvent::CData { text, typed_value, line } => Some((text, typed_value, line)),
+            _ => None,
+        })
+        .collect();
+    assert_eq!(events.len(), 2);
+    assert_eq!((events[0].0.as_str(), events[0].1.data_type, events[0].2), ("Before", DataValueType::TypeNull, 1));
+    assert_eq!((events[1].0.as_str(), events[1].1.data_type, events[1].2), ("Hello", DataValueType::TypeString, 2));
+    assert_eq!(patched.find("string").unwrap().text(), "Hello");
+
+    let raw = RawDocument::parse(&written).unwrap();
+    let hello = raw.root.children().filter(|chunk| chunk.chunk_type == XmlTypes::ResXmlCDataType as u16).nth(1).unwrap();
+    match &hello.body {
+        RawBody::Data(data) => assert_eq!(data[0..4], data[8..12]),
+        RawBody::Chunks(_) => panic!("CDATA chunk without data"),
+    }
+}
@@ -0,0 +1,226 @@
+use std::fs;
+
+use axml_parser::data_value_type::DataValueType;
+use axml_parser::parser::Attribute;
+use axml_parser::res_table::ResTable;
+use axml_parser::{
+    parse_arsc_reader,
+    AxmlDocument,
+    AxmlError,
+    XmlEncoder,
+};
+
+const ANDROID_NS: &str = "http://schemas.android.com/apk/res/android";
+
+/* Resource table of an app (package 0x7f), with `@string/app_name` as
+ * 0x7f030000 and six types but no `id` type */
+const RESOURCES: &str = "tests/fixtures/resources.arsc";
+
+/* Small framework resource table (package 0x01), with the `attr` bags of a
+ * few attributes, as in android.jar:
+ *   - textColor (reference|color), gravity (flags), id (reference),
+ *     visibility (enum), layout_width (dimension|enum), text (string),
+ *     alpha (float) and requestLegacyExternalStorage (boolean, 0x01010603)
+ *   - style/Theme.NoTitleBar (0x01030006) */
+const FRAMEWORK: &str = "tests/fixtures/framework.arsc";
+
+fn table(path: &str) -> ResTable {
+    parse_arsc_reader(fs::File::open(path).unwrap()).unwrap()
+}
+
+fn android_attr(encoder: &XmlEncoder, name: &str, value: &str) -> Attribute {
+    encoder.attribute(Some(ANDROID_NS), name, value).unwrap()
+}
+
+/// Check the typed values of Android attributes, as aapt2 encodes them
+fn check(encoder: &XmlEncoder, expected: &[(&str, &str, DataValueType, u32)]) {
+    for &(name, value, data_type, data) in expected {
+        let attr = android_attr(encoder, name, value);
+        assert_eq!((attr.typed_value.data_type, attr.typed_value.data), (data_type, data),
+                   "android:{}=\"{}\"", name, value);
+    }
+}
+
+#[test]
+fn manifest_attributes_follow_their_format() {
+    check(&XmlEncoder::new(), &[
+        /* Enums are decimal, flags hexadecimal */
+        ("screenOrientation", "portrait", DataValueType::TypeIntDec, 1),
+        ("launchMode", "singleTask", DataValueType::TypeIntDec, 2),
+        ("configChanges", "orientation|screenSize", DataValueType::TypeIntHex, 0x480),
+        ("windowSoftInputMode", "stateHidden|adjustResize", DataValueType::TypeIntHex, 0x12),
+        ("protectionLevel", "signature|privileged", DataValueType::TypeIntHex, 0x12),
+        ("debuggable", "true", DataValueType::TypeIntBoolean, 0xffffffff),
+        ("exported", "false", DataValueType::TypeIntBoolean, 0),
+        ("versionCode", "42", DataValueType::TypeIntDec, 42),
+        ("minSdkVersion", "21", DataValueType::TypeIntDec, 21),
+    ]);
+
+    /* Strings are kept as such, even when they look like numbers */
+    let encoder = XmlEncoder::new();
+    for (name, value) in [("versionName", "1.0"), ("name", "true"), ("minSdkVersion", "S"), ("label", "42")] {
+        let attr = android_attr(&encoder, name, value);
+        assert_eq!(attr.typed_value.data_type, DataValueType::TypeString, "android:{}", name);
+        assert_eq!(attr.raw_value.as_deref(), Some(value));
+    }
+}
+
+#[test]
+fn values_not_matching_the_format_are_rejected() {
+    let encoder = XmlEncoder::new();
+
+    for (name, value) in [("versionCode", "1.5"), ("debuggable", "yes"), ("launchMode", "sometimes")] {
+        assert!(matches!(encoder.attribute(Some(ANDROID_NS), name, value),
+                         Err(AxmlError::MalformedDocument { .. })), "android:{}=\"{}\"", name, value);
+    }
+}
+
+#[test]
+fn numbers_of_unknown_formats_are_typed() {
+    /* The built-in table does not know the format of these attributes */
+    check(&XmlEncoder::new(), &[
+        ("layout_width", "16dp", DataValueType::TypeDimension, 0x00001001),
+        ("layout_width", "16dip", DataValueType::TypeDimension, 0x00001001),
+        ("layout_width", "1.5dp", DataValueType::TypeDimension, 0x00c00021),
+        ("layout_width", "-2px", DataValueType::TypeDimension, 0xfffffe00),
+        ("textSize", "12sp", DataValueType::TypeDimension, 0x00000c02),
+        ("layout_width", "50%", DataValueType::TypeFraction, 0x40000030),
+        ("layout_width", "50%p", DataValueType::TypeFraction, 0x40000031),
+        ("textColor", "#f00", DataValueType::TypeIntColorRgb4, 0xffff0000),
+        ("textColor", "#8f00", DataValueType::TypeIntColorArgb4, 0x88ff0000),
+        ("textColor", "#123456", DataValueType::TypeIntColorRgb8, 0xff123456),
+        ("textColor", "#80ff0000", DataValueType::TypeIntColorArgb8, 0x80ff0000),
+        ("alpha", "0.5", DataValueType::TypeFloat, 0x3f000000),
+    ]);
+}
+
+#[test]
+fn references_are_resolved() {
+    let resources = table(RESOURCES);
+    let encoder = XmlEncoder::new().with_resources(&resources);
+
+    check(&encoder, &[
+        ("label", "@string/app_name", DataValueType::TypeReference, 0x7f030000),
+        ("theme", "@0x01030237", DataValueType::TypeReference, 0x01030237),
+        ("icon", "@null", DataValueType::TypeReference, 0),
+        ("label", "@empty", DataValueType::TypeNull, 1),
+        ("textAppearance", "?android:attr/textAppearanceMedium", DataValueType::TypeAttribute, 0x01010041),
+        ("textAppearance", "?android:textAppearanceMedium", DataValueType::TypeAttribute, 0x01010041),
+        /* References are accepted whatever the format */
+        ("debuggable", "@bool/is_tablet", DataValueType::TypeReference, 0x7f050000),
+    ]);
+
+    /* Escaped references are strings */
+    let attr = android_attr(&encoder, "label", "\\@string/app_name");
+    assert_eq!(attr.typed_value.data_type, DataValueType::TypeString);
+    assert_eq!(attr.raw_value.as_deref(), Some("@string/app_name"));
+
+    assert!(matches!(encoder.attribute(Some(ANDROID_NS), "label", "@string/missing"),
+                     Err(AxmlError::UnresolvedReference { .. })));
+}
+
+#[test]
+fn framework_table_gives_ids_and_formats() {
+    /* Only the most common styles are in the built-in table */
+    check(&XmlEncoder::new(), &[
+        ("theme", "@android:style/Theme.NoTitleBar", DataValueType::TypeReference, 0x01030006),
+    ]);
+    assert!(matches!(XmlEncoder::new().attribute(Some(ANDROID_NS), "theme", "@android:style/Theme.Material.Light.DarkActionBar"),
+                     Err(AxmlError::UnresolvedReference { .. })));
+
+    let framework = table(FRAMEWORK);
+    let encoder = XmlEncoder::new().with_framework(&framework);
+
+    check(&encoder, &[
+        ("layout_width", "match_parent", DataValueType::TypeIntDec, 0xffffffff),
+        ("layout_width", "wrap_content", DataValueType::TypeIntDec, 0xfffffffe),
+        ("layout_width", "16dp", DataValueType::TypeDimension, 0x00001001),
+        ("visibility", "gone", DataValueType::TypeIntDec, 2),
+        ("gravity", "center_vertical|left", DataValueType::TypeIntHex, 0x13),
+        ("textColor", "#fff", DataValueType::TypeIntColorRgb4, 0xffffffff),
+        /* Like aapt2, integers are floats for float attributes */
+        ("alpha", "1", DataValueType::TypeFloat, 0x3f800000),
+        ("requestLegacyExternalStorage", "true", DataValueType::TypeIntBoolean, 0xffffffff),
+        ("id", "@android:style/Theme.NoTitleBar", DataValueType::TypeReference, 0x01030006),
+    ]);
+    assert_eq!(android_attr(&encoder, "requestLegacyExternalStorage", "true").resource_id, Some(0x01010603));
+
+    let text = android_attr(&encoder, "text", "16dp");
+    assert_eq!(text.typed_value.data_type, DataValueType::TypeString);
+
+    /* A dimension attribute does not take a bare number, nor a colour an integer */
+    for (name, value) in [("layout_width", "0"), ("textColor", "12"), ("visibility", "hidden")] {
+        assert!(matches!(encoder.attribute(Some(ANDROID_NS), name, value),
+                         Err(AxmlError::MalformedDocument { .. })), "android:{}=\"{}\"", name, value);
+    }
+}
+
+#[test]
+fn new_ids_without_resource_table() {
+    let encoder = XmlEncoder::new();
+
+    check(&encoder, &[
+        ("id", "@+id/title", DataValueType::TypeReference, 0x7f010000),
+        ("id", "@+id/body", DataValueType::TypeReference, 0x7f010001),
+        ("labelFor", "@id/title", DataValueType::TypeReference, 0x7f010000),
+        ("id", "@+id/title", DataValueType::TypeReference, 0x7f010000),
+    ]);
+
+    /* IDs must be declared before they are used */
+    assert!(matches!(encoder.attribute(Some(ANDROID_NS), "labelFor", "@id/footer"),
+                     Err(AxmlError::UnresolvedReference { .. })));
+}
+
+#[test]
+fn new_ids_follow_the_app_types() {
+    let resources = table(RESOURCES);
+    let encoder = XmlEncoder::new().with_resources(&resources);
+
+    check(&encoder, &[
+        ("id", "@+id/title", DataValueType::TypeReference, 0x7f070000),
+        ("id", "@+id/body", DataValueType::TypeReference, 0x7f070001),
+    ]);
+}
+
+#[test]
+fn encoded_layout_decodes_back() {
+    let framework = table(FRAMEWORK);
+    let layout = r#"<?xml version="1.0" encoding="utf-8"?>
+<LinearLayout xmlns:android="http://schemas.android.com/apk/res/android"
+    android:layout_width="match_parent" android:gravity="center">
+    <TextView android:id="@+id/title" android:text="Hello" android:alpha="0.5" />
+    <TextView android:id="@+id/body" android:visibility="gone" />
+</LinearLayout>
+"#;
+    let bytes = XmlEncoder::new().with_framework(&framework).encode(layout).unwrap();
+    let document = AxmlDocument::from_bytes(&bytes).unwrap();
+
+    let root = &document.root;
+    assert_eq!(root.attr(ANDROID_NS, "layout_width").unwrap().typed_value.data, 0xffffffff);
+    assert_eq!(root.attr(ANDROID_NS, "gravity").unwrap().typed_value.data, 0x11);
+
+    let ids: Vec<u32> = root.elements()
+                            .map(|child| child.attr(ANDROID_NS, "id").unwrap().typed_value.data)
+                            .collect();
+    assert_eq!(ids, [0x7f010000, 0x7f010001]);
+    assert_eq!(root.elements().next().unwrap().attr_value(ANDROID_NS, "text"), Some("Hello"));
+}
+
+#[test]
+fn unknown_android_attributes_are_rejected() {
+    let encoder = XmlEncoder::new();
+    assert_eq!(android_attr(&encoder, "requestLegacyExternalStorage", "true").resource_id, Some(0x01010603));
+
+    /* Not in the built-in table: without an ID, Android would ignore it */
+    assert!(matches!(encoder.attribute(Some(ANDROID_NS), "dataExtractionRules", "@0x7f140000"),
+                     Err(AxmlError::UnknownAttribute { name, .. }) if name == "android:dataExtractionRules"));
+
+    let manifest = r#"<manifest xmlns:android="http://schemas.android.com/apk/res/android" package="com.example.app">
+    <application android:notAnAttribute="true" />
+</manifest>"#;
+    assert!(matches!(encoder.encode(manifest), Err(AxmlError::UnknownAttribute { line: 2, .. })));
+
+    /* Attributes of other namespaces are kept as strings */
+    let attr = encoder.attribute(Some("http://schemas.android.com/tools"), "ignore", "All").unwrap();
+    assert_eq!((attr.resource_id, attr.typed_value.data_type), (None, DataValueType::TypeString));
+}
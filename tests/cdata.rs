@@ -0,0 +1,46 @@
+use std::fs;
+use std::process::Command;
+
+use axml_parser::data_value_type::DataValueType;
+use axml_parser::{
+    AxmlEvent,
+    AxmlReader,
+};
+
+/* Values file generated by tools/gen_test_fixtures.py, whose strings hold
+ * `&`, `<` and `>`, the second one typed as a string */
+const CDATA: &str = "tests/fixtures/cdata.xml";
+
+#[test]
+fn text_is_decoded_unescaped() {
+    let bytes = fs::read(CDATA).unwrap();
+    let reader = AxmlReader::from_bytes(&bytes);
+    let texts: Vec<_> = reader.filter_map(|event| match event.unwrap() {
+                                  AxmlEvent::CData { text, typed_value, line } => Some((text, typed_value, line)),
+                                  _ => None,
+                              })
+                              .collect();
+
+    assert_eq!(texts.len(), 2);
+    assert_eq!((texts[0].0.as_str(), texts[0].2), ("Terms & <conditions> apply", 2));
+    assert_eq!(texts[0].1.data_type, DataValueType::TypeNull);
+
+    /* As older tools typed it */
+    assert_eq!((texts[1].0.as_str(), texts[1].2), ("a -> b && c", 3));
+    assert_eq!(texts[1].1.data_type, DataValueType::TypeString);
+}
+
+#[test]
+fn text_is_escaped_in_text_xml() {
+    let output = Command::new(env!("CARGO_BIN_EXE_axml_parser")).args(["-x", CDATA])
+                                                                .output()
+                                                                .unwrap();
+    assert!(output.status.success());
+
+    let expected = r#"<resources>
+  <string name="terms">Terms &amp; &lt;conditions&gt; apply</string>
+  <string name="arrow">a -&gt; b &amp;&amp; c</string>
+</resources>
+"#;
+    assert_eq!(String::from_utf8(output.stdout).unwrap(), expected);
+}
@@ -0,0 +1,95 @@
+use std::fs;
+use std::path::PathBuf;
+use std::process::{
+    Command,
+    Output,
+};
+
+/* APK generated by tools/gen_test_fixtures.py, with a binary layout, a text
+ * XML file, a truncated layout and a file which is not XML under `res/` */
+const APK: &str = "tests/fixtures/app.apk";
+
+/// Empty directory for the files of one test
+fn temp_dir(test: &str) -> PathBuf {
+    let dir = std::env::temp_dir().join(format!("axml_parser_{}_{}", test, std::process::id()));
+    let _ = fs::remove_dir_all(&dir);
+    fs::create_dir_all(&dir).unwrap();
+    dir
+}
+
+fn run(args: &[&str]) -> Output {
+    Command::new(env!("CARGO_BIN_EXE_axml_parser")).args(args).output().unwrap()
+}
+
+fn stdout(output: &Output) -> String {
+    String::from_utf8(output.stdout.clone()).unwrap()
+}
+
+#[test]
+fn file_types_are_detected_from_contents() {
+    let dir = temp_dir("detection");
+    let text_xml = "<?xml version=\"1.0\" encoding=\"utf-8\"?>\n<config />\n";
+    fs::write(dir.join("config"), text_xml).unwrap();
+
+    /* Names which tell nothing of the type, the contents do */
+    let cases = [
+        ("tests/fixtures/resources.arsc", "package 0x7f com.example.app\n"),
+        ("tests/fixtures/references.xml", "<LinearLayout xmlns:android="),
+        ("tests/fixtures/layout.xml", "<LinearLayout xmlns:android="),
+        (APK, "<manifest xmlns:android="),
+        ("tests/fixtures/app.apks", "<manifest xmlns:android="),
+    ];
+    for (fixture, start) in cases {
+        let path = dir.join("input.bin");
+        fs::copy(fixture, &path).unwrap();
+
+        let output = run(&["-f", path.to_str().unwrap()]);
+        assert!(output.status.success(), "{}", fixture);
+        assert!(stdout(&output).starts_with(start), "{}: {}", fixture, stdout(&output));
+    }
+
+    let output = run(&["-f", dir.join("config").to_str().unwrap()]);
+    assert_eq!(stdout(&output), format!("{}\n", text_xml));
+
+    fs::write(dir.join("input.bin"), b"\x7fELF").unwrap();
+    let output = run(&["-f", dir.join("input.bin").to_str().unwrap()]);
+    assert!(!output.status.success());
+    assert!(String::from_utf8_lossy(&output.stderr).contains("cannot infer file type"));
+}
+
+#[test]
+fn references_of_detected_files_are_resolved() {
+    let output = run(&["-f", APK]);
+    assert!(stdout(&output).contains(r#"<application android:label="My App" android:icon="@drawable/icon">"#));
+
+    let output = run(&["-f", APK, "--raw-ids"]);
+    assert!(stdout(&output).contains(r#"<application android:label="@0x7f030000" android:icon="@0x7f020000">"#));
+}
+
+#[test]
+fn every_xml_file_of_an_apk_is_decoded() {
+    let dir = temp_dir("all_xml");
+    let output = run(&["-a", APK, "--all-xml", "-o", dir.to_str().unwrap()]);
+    assert!(output.status.success());
+
+    for path in ["AndroidManifest.xml", "res/layout/main.xml", "res/xml/config.xml"] {
+        assert!(dir.join(path).is_file(), "{}", path);
+    }
+    for path in ["res/layout/broken.xml", "res/raw/notes.xml", "res/drawable/icon.png", "resources.arsc"] {
+        assert!(!dir.join(path).exists(), "{}", path);
+    }
+
+    /* References are resolved with the APK's resources */
+    let layout = fs::read_to_string(dir.join("res/layout/main.xml")).unwrap();
+    assert!(layout.starts_with("<LinearLayout xmlns:android="));
+    assert!(layout.contains(r#"android:background="@drawable/icon" android:text="My App""#));
+
+    /* Text XML is copied as is */
+    assert_eq!(fs::read_to_string(dir.join("res/xml/config.xml")).unwrap(),
+               "<?xml version=\"1.0\" encoding=\"utf-8\"?>\n<config enabled=\"true\" />\n");
+
+    /* The files which cannot be decoded are reported */
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(stderr.contains("cannot decode res/layout/broken.xml"));
+    assert!(stderr.contains("skipping res/raw/notes.xml"));
+}
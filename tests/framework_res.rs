@@ -0,0 +1,61 @@
+use axml_parser::res_resolver::ResourceResolver;
+use axml_parser::{
+    framework_attribute,
+    framework_id,
+    framework_name,
+    FrameworkResources,
+};
+
+/* ResTable_map types of the values accepted by an attribute */
+const TYPE_STRING: u32 = 1 << 1;
+const TYPE_BOOLEAN: u32 = 1 << 3;
+const TYPE_ENUM: u32 = 1 << 16;
+const TYPE_FLAGS: u32 = 1 << 17;
+
+#[test]
+fn names_and_ids_match() {
+    assert_eq!(framework_id("attr", "debuggable"), Some(0x0101000f));
+    assert_eq!(framework_name(0x0101000f), Some(("attr", "debuggable", 1)));
+    assert_eq!(framework_id("attr", "notAnAttribute"), None);
+}
+
+#[test]
+fn attribute_formats_are_known() {
+    let debuggable = framework_id("attr", "debuggable").unwrap();
+    assert_eq!(framework_attribute(debuggable), Some((TYPE_BOOLEAN, &[][..])));
+
+    let version_name = framework_id("attr", "versionName").unwrap();
+    assert_eq!(framework_attribute(version_name).map(|(types, _)| types), Some(TYPE_STRING));
+
+    let orientation = framework_id("attr", "screenOrientation").unwrap();
+    let (types, values) = framework_attribute(orientation).unwrap();
+    assert_eq!(types, TYPE_ENUM);
+    assert!(values.contains(&("portrait", 1)));
+
+    let config_changes = framework_id("attr", "configChanges").unwrap();
+    assert_eq!(framework_attribute(config_changes).map(|(types, _)| types), Some(TYPE_FLAGS));
+}
+
+#[test]
+fn attributes_after_api_25_are_known() {
+    assert_eq!(framework_name(0x01010603), Some(("attr", "requestLegacyExternalStorage", 29)));
+    assert_eq!(framework_id("attr", "requestLegacyExternalStorage"), Some(0x01010603));
+    assert_eq!(framework_attribute(0x01010603), Some((TYPE_BOOLEAN, &[][..])));
+    assert_eq!(framework_id("attr", "appComponentFactory"), Some(0x0101057a));
+}
+
+#[test]
+fn other_types_of_resources_are_known() {
+    assert_eq!(framework_name(0x01030006), Some(("style", "Theme.NoTitleBar", 1)));
+    assert_eq!(framework_id("style", "Theme.Material.Light"), Some(0x01030237));
+    assert_eq!(framework_id("id", "content"), Some(0x01020002));
+    assert_eq!(framework_id("color", "transparent"), Some(0x0106000d));
+
+    /* Names are looked up within their type */
+    assert_eq!(framework_id("attr", "Theme.NoTitleBar"), None);
+    assert_eq!(framework_attribute(0x01030006), None);
+
+    let name = FrameworkResources.resource_name(0x01080093).unwrap();
+    assert_eq!((name.package.as_str(), name.type_name.as_str(), name.entry.as_str()),
+               ("android", "drawable", "sym_def_app_icon"));
+}
@@ -0,0 +1,117 @@
+use std::fs;
+use std::io::{
+    Cursor,
+    Write,
+};
+
+use axml_parser::{
+    parse_apk_reader,
+    parse_apk_reader_with,
+    parse_app_manifest,
+    parse_arsc_reader,
+    parse_manifest_bytes,
+    AxmlError,
+    ManifestOptions,
+    XmlEncoder,
+};
+
+const MANIFEST: &str = r#"<?xml version="1.0" encoding="utf-8"?>
+<manifest xmlns:android="http://schemas.android.com/apk/res/android" package="com.example.app">
+    <application android:label="@string/app_name">
+        <activity android:name="@string/app_name" />
+        <service android:name=".Sync" />
+    </application>
+</manifest>
+"#;
+
+const RESOURCES: &str = "tests/fixtures/resources.arsc";
+
+/* The same manifest in the protobuf format of Android App Bundles, whose
+ * references to `@string/app_name` are not named */
+const PROTO_MANIFEST: &str = "tests/fixtures/proto_manifest.xml";
+
+/// APK holding `MANIFEST` and the resource table it refers to
+fn apk() -> Vec<u8> {
+    apk_with_table(fs::read(RESOURCES).unwrap())
+}
+
+/// APK holding `MANIFEST`, compiled with the resource table of
+/// `RESOURCES`, and `raw_table` as its resource table
+fn apk_with_table(raw_table: Vec<u8>) -> Vec<u8> {
+    let table = parse_arsc_reader(fs::read(RESOURCES).unwrap().as_slice()).unwrap();
+    let manifest = XmlEncoder::new().with_resources(&table).encode(MANIFEST).unwrap();
+    zip_apk(manifest, raw_table)
+}
+
+fn zip_apk(manifest: Vec<u8>, raw_table: Vec<u8>) -> Vec<u8> {
+    let mut apk = zip::ZipWriter::new(Cursor::new(Vec::new()));
+    for (name, contents) in [("AndroidManifest.xml", manifest), ("resources.arsc", raw_table)] {
+        apk.start_file(name, Default::default()).unwrap();
+        apk.write_all(&contents).unwrap();
+    }
+    apk.finish().unwrap().into_inner()
+}
+
+#[test]
+fn references_are_resolved_by_default() {
+    let contents = parse_apk_reader(Cursor::new(apk())).unwrap();
+
+    assert_eq!(contents.pkg_name, "com.example.app");
+    assert_eq!(contents.activities, ["My App"]);
+    assert_eq!(contents.services, [".Sync"]);
+}
+
+#[test]
+fn raw_ids_are_kept_on_request() {
+    let options = ManifestOptions { raw_ids: true };
+    let contents = parse_apk_reader_with(Cursor::new(apk()), &options).unwrap();
+
+    assert_eq!(contents.activities, ["@0x7f030000"]);
+    assert_eq!(contents.services, [".Sync"]);
+}
+
+#[test]
+fn references_of_proto_manifests_are_resolved() {
+    let apk = zip_apk(fs::read(PROTO_MANIFEST).unwrap(), fs::read(RESOURCES).unwrap());
+    let contents = parse_apk_reader(Cursor::new(apk.clone())).unwrap();
+
+    assert_eq!(contents.pkg_name, "com.example.app");
+    assert_eq!(contents.activities, ["My App"]);
+    assert_eq!(contents.services, [".Sync"]);
+
+    let options = ManifestOptions { raw_ids: true };
+    let contents = parse_apk_reader_with(Cursor::new(apk), &options).unwrap();
+    assert_eq!(contents.activities, ["@0x7f030000"]);
+}
+
+#[test]
+fn proto_manifests_are_detected() {
+    let contents = parse_app_manifest(PROTO_MANIFEST).unwrap();
+
+    assert_eq!(contents.pkg_name, "com.example.app");
+    assert_eq!(contents.activities, ["@0x7f030000"]);
+}
+
+#[test]
+fn broken_resource_tables_leave_references_unresolved() {
+    let mut raw_table = fs::read(RESOURCES).unwrap();
+    raw_table.truncate(raw_table.len() / 2);
+    let contents = parse_apk_reader(Cursor::new(apk_with_table(raw_table))).unwrap();
+
+    assert_eq!(contents.pkg_name, "com.example.app");
+    assert_eq!(contents.activities, ["@0x7f030000"]);
+    assert_eq!(contents.resources_errors.len(), 1);
+    assert_eq!(contents.resources_errors[0].0, "resources.arsc");
+
+    /* Nothing to report for a valid table */
+    assert!(parse_apk_reader(Cursor::new(apk())).unwrap().resources_errors.is_empty());
+}
+
+#[test]
+fn resource_tables_are_not_manifests() {
+    assert!(matches!(parse_app_manifest(RESOURCES), Err(AxmlError::NotAManifest(_))));
+
+    let raw_table = fs::read(RESOURCES).unwrap();
+    assert!(matches!(parse_manifest_bytes(&raw_table),
+                     Err(AxmlError::UnexpectedChunkType { offset: 0, expected: 0x0003, found: 0x0002 })));
+}
@@ -0,0 +1,120 @@
+use std::fs;
+use std::process::Command;
+
+use axml_parser::parser;
+use axml_parser::{
+    AxmlEvent,
+    AxmlReader,
+};
+
+/* Layout generated by tools/gen_test_fixtures.py, with namespaces declared
+ * on inner elements, a shadowed prefix, and undeclared namespaces:
+ *
+ *   <LinearLayout xmlns:android="...">
+ *     <Inner xmlns:app="res-auto" app:custom tools:ignore>
+ *       <widgets:widget widgets:color /> (twice)
+ *       <Shadowed xmlns:app="res/com.example.lib" app:flag />
+ *       <After app:custom />
+ *     </Inner>
+ *     <w:item xmlns:w="widgets" w:k />
+ *   </LinearLayout>
+ */
+const NAMESPACES: &str = "tests/fixtures/namespaces.xml";
+
+/* An element, as far as namespaces are concerned */
+struct Element {
+    qualified_name: String,
+    namespaces: Vec<(String, String)>,
+    attrs: Vec<String>,
+}
+
+/// Every element, with the namespaces declared on it and the qualified
+/// names of its attributes
+fn elements() -> Vec<Element> {
+    let bytes = fs::read(NAMESPACES).unwrap();
+    AxmlReader::from_bytes(&bytes).filter_map(|event| match event.unwrap() {
+                                      AxmlEvent::StartElement { name, prefix, namespaces, attrs, .. } => Some(Element {
+                                          qualified_name: parser::qualified_name(prefix.as_deref(), &name),
+                                          namespaces,
+                                          attrs: attrs.iter().map(|attr| attr.qualified_name()).collect(),
+                                      }),
+                                      _ => None,
+                                  })
+                                  .collect()
+}
+
+fn declaration(prefix: &str, uri: &str) -> (String, String) {
+    (prefix.to_string(), uri.to_string())
+}
+
+#[test]
+fn declarations_are_scoped_on_their_element() {
+    let elements = elements();
+
+    assert_eq!(elements[0].namespaces, [declaration("android", "http://schemas.android.com/apk/res/android")]);
+    assert_eq!(elements[0].attrs, ["android:layout_width"]);
+
+    /* Declared on the element after them, not on the root */
+    let inner = &elements[1];
+    assert_eq!(inner.namespaces[0], declaration("app", "http://schemas.android.com/apk/res-auto"));
+    assert_eq!(inner.attrs, ["app:custom", "tools:ignore"]);
+
+    let item = &elements[6];
+    assert_eq!(item.qualified_name, "w:item");
+    assert_eq!(item.namespaces, [declaration("w", "http://example.com/widgets")]);
+    assert_eq!(item.attrs, ["w:k"]);
+}
+
+#[test]
+fn inner_declarations_shadow_outer_ones() {
+    let elements = elements();
+
+    let shadowed = &elements[4];
+    assert_eq!(shadowed.namespaces, [declaration("app", "http://schemas.android.com/apk/res/com.example.lib")]);
+    assert_eq!(shadowed.attrs, ["app:flag"]);
+
+    /* The outer binding is back once the inner one ends */
+    let after = &elements[5];
+    assert!(after.namespaces.is_empty());
+    assert_eq!(after.attrs, ["app:custom"]);
+}
+
+#[test]
+fn undeclared_namespaces_get_a_prefix() {
+    let elements = elements();
+
+    /* Well-known namespaces get their usual prefix */
+    assert!(elements[1].namespaces.contains(&declaration("tools", "http://schemas.android.com/tools")));
+
+    /* Others a made-up one, declared on each element which needs it */
+    for widget in &elements[2..4] {
+        assert_eq!(widget.qualified_name, "ns0:widget");
+        assert_eq!(widget.namespaces, [declaration("ns0", "http://example.com/widgets")]);
+        assert_eq!(widget.attrs, ["ns0:color"]);
+    }
+}
+
+#[test]
+fn declarations_are_written_on_their_element() {
+    let output = Command::new(env!("CARGO_BIN_EXE_axml_parser")).args(["-x", NAMESPACES])
+                                                                .output()
+                                                                .unwrap();
+    assert!(output.status.success());
+
+    let expected = r#"<LinearLayout xmlns:android="http://schemas.android.com/apk/res/android" android:layout_width="-1">
+  <Inner xmlns:app="http://schemas.android.com/apk/res-auto" xmlns:tools="http://schemas.android.com/tools" app:custom="v" tools:ignore="All">
+    <ns0:widget xmlns:ns0="http://example.com/widgets" ns0:color="red">
+    </ns0:widget>
+    <ns0:widget xmlns:ns0="http://example.com/widgets" ns0:color="blue">
+    </ns0:widget>
+    <Shadowed xmlns:app="http://schemas.android.com/apk/res/com.example.lib" app:flag="x">
+    </Shadowed>
+    <After app:custom="w">
+    </After>
+  </Inner>
+  <w:item xmlns:w="http://example.com/widgets" w:k="v">
+  </w:item>
+</LinearLayout>
+"#;
+    assert_eq!(String::from_utf8(output.stdout).unwrap(), expected);
+}
@@ -0,0 +1,109 @@
+use std::fs;
+
+use axml_parser::data_value_type::DataValueType;
+use axml_parser::parser::Attribute;
+use axml_parser::{
+    AxmlEvent,
+    FrameworkResources,
+    ProtoXmlReader,
+};
+
+/* Layout of a bundle, generated by tools/gen_test_fixtures.py as the protobuf
+ * XmlNode aapt2 compiles it to. The textAppearance and style references are
+ * stored by ID only, without the name aapt2 usually records:
+ *
+ *   <LinearLayout xmlns:android="..." xmlns:app="http://schemas.android.com/apk/res-auto"
+ *                 android:orientation="1" android:layout_width="16dp"
+ *                 android:background="#ff00ff00" app:visible="true"
+ *                 android:text="@string/app_name"
+ *                 android:textAppearance="?0x01010041" style="@0x01030237">
+ *       Hello &amp; &lt;world&gt;
+ *       <TextView android:text="raw text" app:tag="plain" />
+ *   </LinearLayout>
+ */
+const LAYOUT: &str = "tests/fixtures/layout.xml";
+
+const ANDROID_NS: &str = "http://schemas.android.com/apk/res/android";
+const APP_NS: &str = "http://schemas.android.com/apk/res-auto";
+
+fn events(reader: ProtoXmlReader) -> Vec<AxmlEvent> {
+    reader.collect::<Result<_, _>>().unwrap()
+}
+
+fn attrs(event: &AxmlEvent) -> &[Attribute] {
+    match event {
+        AxmlEvent::StartElement { attrs, .. } => attrs,
+        other => panic!("expected an element, found {:?}", other),
+    }
+}
+
+fn attr<'a>(attrs: &'a [Attribute], name: &str) -> &'a Attribute {
+    attrs.iter().find(|attr| attr.name == name).unwrap()
+}
+
+#[test]
+fn namespaces_are_declared_and_closed() {
+    let bytes = fs::read(LAYOUT).unwrap();
+    let events = events(ProtoXmlReader::from_bytes(&bytes));
+
+    assert!(matches!(&events[0], AxmlEvent::StartNamespace { prefix, uri, .. } if prefix == "android" && uri == ANDROID_NS));
+    assert!(matches!(&events[1], AxmlEvent::StartNamespace { prefix, uri, .. } if prefix == "app" && uri == APP_NS));
+    assert!(matches!(&events[events.len() - 2], AxmlEvent::EndNamespace { prefix, .. } if prefix == "app"));
+    assert!(matches!(&events[events.len() - 1], AxmlEvent::EndNamespace { prefix, .. } if prefix == "android"));
+
+    let layout = attrs(&events[2]);
+    assert_eq!(attr(layout, "visible").qualified_name(), "app:visible");
+    assert_eq!(attr(layout, "orientation").qualified_name(), "android:orientation");
+    assert_eq!(attr(layout, "style").qualified_name(), "style");
+}
+
+#[test]
+fn compiled_items_are_typed() {
+    let bytes = fs::read(LAYOUT).unwrap();
+    let events = events(ProtoXmlReader::from_bytes(&bytes));
+    let layout = attrs(&events[2]);
+
+    let expected = [
+        ("orientation", DataValueType::TypeIntDec, 1, "1"),
+        ("layout_width", DataValueType::TypeDimension, 0x1001, "16dp"),
+        ("background", DataValueType::TypeIntColorArgb8, 0xff00ff00, "#ff00ff00"),
+        ("visible", DataValueType::TypeIntBoolean, 0xffffffff, "true"),
+        ("text", DataValueType::TypeReference, 0x7f030000, "@string/app_name"),
+        ("textAppearance", DataValueType::TypeAttribute, 0x01010041, "?0x01010041"),
+        ("style", DataValueType::TypeReference, 0x01030237, "@0x01030237"),
+    ];
+    for (name, data_type, data, value) in expected {
+        let attr = attr(layout, name);
+        assert_eq!(attr.typed_value.data_type, data_type, "{}", name);
+        assert_eq!(attr.typed_value.data, data, "{}", name);
+        assert_eq!(attr.value, value, "{}", name);
+    }
+    assert_eq!(attr(layout, "text").resource_id, Some(0x0101014f));
+}
+
+#[test]
+fn unnamed_references_use_the_resolver() {
+    let bytes = fs::read(LAYOUT).unwrap();
+    let events = events(ProtoXmlReader::from_bytes(&bytes).with_resolver(&FrameworkResources));
+    let layout = attrs(&events[2]);
+
+    assert_eq!(attr(layout, "textAppearance").value, "?android:attr/textAppearanceMedium");
+    /* Names recorded by aapt2 are kept */
+    assert_eq!(attr(layout, "text").value, "@string/app_name");
+}
+
+#[test]
+fn text_nodes_and_children_follow_their_parent() {
+    let bytes = fs::read(LAYOUT).unwrap();
+    let events = events(ProtoXmlReader::from_bytes(&bytes));
+
+    assert!(matches!(&events[3], AxmlEvent::CData { text, line: 2, .. } if text == "Hello & <world>"));
+
+    let text_view = attrs(&events[4]);
+    assert_eq!(attr(text_view, "text").value, "raw text");
+    assert_eq!(attr(text_view, "text").raw_value.as_deref(), Some("raw text"));
+    assert_eq!(attr(text_view, "tag").qualified_name(), "app:tag");
+
+    assert!(matches!(&events[5], AxmlEvent::EndElement { name, line: 3, .. } if name == "TextView"));
+    assert!(matches!(&events[6], AxmlEvent::EndElement { name, .. } if name == "LinearLayout"));
+}
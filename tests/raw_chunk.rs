@@ -0,0 +1,178 @@
+use std::fs;
+
+use axml_parser::raw_chunk::{
+    RawBody,
+    RawChunk,
+    RawPart,
+};
+use axml_parser::string_pool::encode_string_pool;
+use axml_parser::xml_types::XmlTypes;
+use axml_parser::{
+    parse_arsc_reader,
+    verify_roundtrip,
+    AxmlDocument,
+    AxmlError,
+    RawDocument,
+    XmlEncoder,
+};
+
+const LAYOUT: &str = r#"<?xml version="1.0" encoding="utf-8"?>
+<LinearLayout xmlns:android="http://schemas.android.com/apk/res/android" android:orientation="vertical">
+    <TextView android:id="@+id/title" android:text="Hello" />
+</LinearLayout>
+"#;
+
+/* Fixtures generated by tools/gen_test_fixtures.py: a values file with
+ * text around and between elements, a layout with namespaces which are
+ * never declared, and resources.arsc with padding and an unknown chunk in
+ * its package */
+const TEXT: &str = "tests/fixtures/text.xml";
+const NAMESPACES: &str = "tests/fixtures/namespaces.xml";
+const PADDED_TABLE: &str = "tests/fixtures/padded.arsc";
+
+/* Chunk of an unknown type, with an 8-byte header and 8 bytes of data */
+const UNKNOWN_CHUNK: [u8; 16] = [0x77, 0x07, 0x08, 0x00, 0x10, 0x00, 0x00, 0x00, 1, 2, 3, 4, 5, 6, 7, 8];
+
+fn layout() -> Vec<u8> {
+    XmlEncoder::new().encode(LAYOUT).unwrap()
+}
+
+/// Offsets and types of the chunks of a document without padding
+fn chunk_offsets(bytes: &[u8]) -> Vec<(usize, u16)> {
+    let document = RawDocument::parse(bytes).unwrap();
+    let mut offset = 8;
+    document.root.children().map(|chunk| {
+        let chunk_offset = offset;
+        offset += chunk.to_bytes().len();
+        (chunk_offset, chunk.chunk_type)
+    }).collect()
+}
+
+/// Insert `inserted` at `offset` in the document, and update its size
+fn insert(bytes: &[u8], offset: usize, inserted: &[u8]) -> Vec<u8> {
+    let mut patched = bytes.to_vec();
+    patched.splice(offset..offset, inserted.iter().copied());
+    let size = patched.len() as u32;
+    patched[4..8].copy_from_slice(&size.to_le_bytes());
+    patched
+}
+
+#[test]
+fn padding_between_chunks_is_kept() {
+    let bytes = layout();
+    let (first_node, _) = chunk_offsets(&bytes)[2];
+    let padded = insert(&bytes, first_node, &[0; 4]);
+
+    let document = RawDocument::parse(&padded).unwrap();
+    let parts = match &document.root.body {
+        RawBody::Chunks(parts) => parts,
+        RawBody::Data(_) => panic!("document without chunks"),
+    };
+    assert_eq!(parts[2], RawPart::Bytes(vec![0; 4]));
+    assert_eq!(document.root.children().count(), chunk_offsets(&bytes).len());
+
+    assert_eq!(document.to_bytes(), padded);
+    verify_roundtrip(&padded).unwrap();
+}
+
+#[test]
+fn trailing_bytes_are_kept() {
+    let mut bytes = layout();
+    bytes.extend_from_slice(b"trailing");
+
+    let document = RawDocument::parse(&bytes).unwrap();
+    assert_eq!(document.trailing, b"trailing");
+    assert_eq!(document.to_bytes(), bytes);
+    verify_roundtrip(&bytes).unwrap();
+}
+
+#[test]
+fn unknown_chunks_are_kept_in_place() {
+    let bytes = layout();
+    /* Between the namespace and the first element */
+    let (start_element, _) = chunk_offsets(&bytes)[3];
+    let patched = insert(&bytes, start_element, &UNKNOWN_CHUNK);
+
+    let document = RawDocument::parse(&patched).unwrap();
+    let chunk = document.root.children().nth(3).unwrap();
+    assert_eq!(chunk, &RawChunk {
+        chunk_type: 0x0777,
+        header: Vec::new(),
+        body: RawBody::Data(vec![1, 2, 3, 4, 5, 6, 7, 8]),
+    });
+    assert_eq!(document.to_bytes(), patched);
+
+    /* Decoding moves it after the resource map, which is not a difference */
+    verify_roundtrip(&patched).unwrap();
+}
+
+#[test]
+fn chunks_changed_by_reencoding_are_reported() {
+    let bytes = layout();
+
+    /* A string pool with an unused string */
+    let mut document = RawDocument::parse(&bytes).unwrap();
+    let mut strings: Vec<String> = document.root.find(XmlTypes::ResStringPoolType).unwrap()
+                                           .string_pool().unwrap()
+                                           .iter().map(|string| string.into_owned()).collect();
+    strings.push("unused".to_string());
+    let pool = RawDocument::parse(&encode_string_pool(&strings, false)).unwrap().root;
+    *document.root.find_mut(XmlTypes::ResStringPoolType).unwrap() = pool;
+    assert!(matches!(verify_roundtrip(&document.to_bytes()),
+                     Err(AxmlError::ChunkMismatch { offset: 8, chunk_type: 0x0001 })));
+
+    /* android:id given as the id attribute of an element, which only
+     * applies to `id` without namespace */
+    let (text_view, chunk_type) = chunk_offsets(&bytes)[4];
+    assert_eq!(chunk_type, XmlTypes::ResXmlStartElementType as u16);
+    let mut patched = bytes.clone();
+    patched[text_view + 16 + 14] = 1;
+    assert!(matches!(verify_roundtrip(&patched),
+                     Err(AxmlError::ChunkMismatch { offset, chunk_type: 0x0102 }) if offset == text_view as u64));
+
+    verify_roundtrip(&bytes).unwrap();
+}
+
+#[test]
+fn documents_with_text_round_trip() {
+    let bytes = fs::read(TEXT).unwrap();
+    verify_roundtrip(&bytes).unwrap();
+
+    /* Text is re-encoded in place, with its line, even once edited */
+    let mut document = AxmlDocument::from_bytes(&bytes).unwrap();
+    document.find_mut("i").unwrap().set_attr(XmlEncoder::new().attribute(None, "lang", "en").unwrap());
+    let edited = document.to_bytes();
+    verify_roundtrip(&edited).unwrap();
+    assert_eq!(AxmlDocument::from_bytes(&edited).unwrap().find("string").unwrap().text(),
+               "Hello  reader,\nwelcome !");
+}
+
+#[test]
+fn documents_with_undeclared_namespaces_round_trip() {
+    /* The prefixes made up for them are not written back */
+    let bytes = fs::read(NAMESPACES).unwrap();
+    verify_roundtrip(&bytes).unwrap();
+}
+
+#[test]
+fn resource_tables_keep_padding_and_unknown_chunks() {
+    let bytes = fs::read(PADDED_TABLE).unwrap();
+    let document = RawDocument::parse(&bytes).unwrap();
+    assert_eq!(document.to_bytes(), bytes);
+    verify_roundtrip(&bytes).unwrap();
+
+    let package = document.root.find(XmlTypes::ResTablePackageType).unwrap();
+    let parts = match &package.body {
+        RawBody::Chunks(parts) => parts,
+        RawBody::Data(_) => panic!("package without chunks"),
+    };
+    assert!(parts.contains(&RawPart::Bytes(vec![0; 4])));
+    let unknown = package.children().find(|chunk| chunk.chunk_type == 0x0777).unwrap();
+    assert_eq!(unknown.body, RawBody::Data(vec![1, 2, 3, 4, 5, 6, 7, 8]));
+
+    /* Decoding skips both */
+    let table = parse_arsc_reader(bytes.as_slice()).unwrap();
+    assert_eq!(table.packages[0].unknown_chunks.len(), 1);
+    assert_eq!(table.resource_id("string", "app_name"), Some(0x7f030000));
+    assert_eq!(table.resource_id("integer", "max_items"), Some(0x7f060000));
+}
@@ -0,0 +1,67 @@
+use std::io::Cursor;
+
+use axml_parser::data_value_type::DataValueType;
+use axml_parser::raw_chunk::RawBody;
+use axml_parser::xml_types::XmlTypes;
+use axml_parser::{
+    encode_xml,
+    parse_arsc_reader,
+    parse_axml_reader,
+    AxmlError,
+    RawDocument,
+};
+
+const MANIFEST: &str = r#"<?xml version="1.0" encoding="utf-8"?>
+<manifest xmlns:android="http://schemas.android.com/apk/res/android" package="com.example.app">
+    <application android:label="Example" />
+</manifest>
+"#;
+
+#[test]
+fn axml_from_reader() {
+    let axml_bytes = encode_xml(MANIFEST).unwrap();
+    let document = parse_axml_reader(Cursor::new(axml_bytes)).unwrap();
+
+    assert_eq!(document.root.attr_value("", "package"), Some("com.example.app"));
+    assert_eq!(document.find("application").and_then(|application| application.attr_value("android", "label")),
+               Some("Example"));
+}
+
+#[test]
+fn arsc_from_reader_checks_the_chunk_type() {
+    let axml_bytes = encode_xml(MANIFEST).unwrap();
+
+    assert!(matches!(parse_arsc_reader(axml_bytes.as_slice()),
+                     Err(AxmlError::UnexpectedChunkType { .. })));
+}
+
+#[test]
+fn unknown_value_types_are_kept() {
+    let axml_bytes = encode_xml(r#"<manifest package="com.example.app" versionCode="42" />"#).unwrap();
+
+    /* Give the second attribute (versionCode) a type Android does not
+     * know (yet), which its parser accepts, and no raw value */
+    let mut raw = RawDocument::parse(&axml_bytes).unwrap();
+    match &mut raw.root.find_mut(XmlTypes::ResXmlStartElementType).unwrap().body {
+        RawBody::Data(data) => {
+            let raw_value = 20 + 20 + 8;
+            data[raw_value..raw_value + 4].copy_from_slice(&[0xff; 4]);
+            let typed_value = raw_value + 4;
+            data[typed_value + 3] = 0x09;
+            data[typed_value + 4..typed_value + 8].copy_from_slice(&0xcafe_u32.to_le_bytes());
+        },
+        RawBody::Chunks(_) => panic!("start element chunk without data"),
+    }
+    let patched = raw.to_bytes();
+
+    let document = parse_axml_reader(Cursor::new(&patched)).unwrap();
+    let attr = document.root.attr("", "versionCode").unwrap();
+    assert_eq!(attr.typed_value.data_type, DataValueType::Unknown(0x09));
+    assert_eq!(attr.value, "0x0000cafe");
+    assert_eq!(document.root.attr_value("", "package"), Some("com.example.app"));
+
+    /* And written back as they were */
+    let written = parse_axml_reader(Cursor::new(document.to_bytes())).unwrap();
+    let typed_value = written.root.attr("", "versionCode").unwrap().typed_value;
+    assert_eq!((typed_value.data_type.to_val(), typed_value.data), (0x09, 0xcafe));
+}
@@ -0,0 +1,161 @@
+use std::fs;
+use std::io::Cursor;
+
+use axml_parser::parse_arsc_reader;
+use axml_parser::res_table::{
+    ResTable,
+    ResTableEntryValue,
+};
+use axml_parser::ResConfig;
+
+const DENSITY_ANY: u16 = 0xfffe;
+const UI_MODE_NIGHT_YES: u8 = 0x20;
+
+fn density(density: u16) -> ResConfig {
+    ResConfig { density, ..ResConfig::default() }
+}
+
+/* Table of `com.example.app`, whose `string/app_name` is defined by
+ * default, in `fr` and in `es-rUS-night-xxhdpi-v29`, and `bool/is_tablet`
+ * by default and in `xhdpi` */
+fn fixture() -> ResTable {
+    parse_arsc_reader(fs::File::open("tests/fixtures/resources.arsc").unwrap()).unwrap()
+}
+
+fn resolved(table: &ResTable, res_id: u32, device: &ResConfig) -> String {
+    let (_, entry) = table.resolve(res_id, device).unwrap();
+    match &entry.value {
+        ResTableEntryValue::Simple(value) => value.format(&table.strings, None),
+        ResTableEntryValue::Complex { .. } => panic!("{:#010x} is not a simple value", res_id),
+    }
+}
+
+#[test]
+fn closest_density_is_better() {
+    let device = density(320);
+    assert!(density(320).is_better_than(&density(240), &device));
+    assert!(density(320).is_better_than(&density(480), &device));
+
+    /* Scaling down is preferred, unless the lower density is much closer */
+    let device = density(400);
+    assert!(density(480).is_better_than(&density(320), &device));
+    let device = density(340);
+    assert!(density(320).is_better_than(&density(480), &device));
+}
+
+#[test]
+fn any_density_is_always_better() {
+    let device = density(480);
+    assert!(density(DENSITY_ANY).is_better_than(&density(480), &device));
+    assert!(!density(480).is_better_than(&density(DENSITY_ANY), &device));
+}
+
+#[test]
+fn device_with_any_density_is_medium() {
+    /* Like an unspecified density, the device is treated as mdpi */
+    for device in [density(DENSITY_ANY), density(0)] {
+        assert!(density(160).is_better_than(&density(120), &device));
+        assert!(density(240).is_better_than(&density(120), &device));
+        assert!(density(160).is_better_than(&density(240), &device));
+    }
+}
+
+#[test]
+fn resolve_by_locale() {
+    let table = fixture();
+    let app_name = table.resource_id("string", "app_name").unwrap();
+
+    assert_eq!(resolved(&table, app_name, &ResConfig::default()), "My App");
+
+    let device = ResConfig { language: *b"fr", country: *b"CA", ..ResConfig::default() };
+    assert_eq!(resolved(&table, app_name, &device), "Mon App");
+
+    let device = ResConfig { language: *b"de", ..ResConfig::default() };
+    assert_eq!(resolved(&table, app_name, &device), "My App");
+}
+
+#[test]
+fn resolve_needs_every_qualifier_to_match() {
+    let table = fixture();
+    let app_name = table.resource_id("string", "app_name").unwrap();
+
+    let mut device = ResConfig {
+        language: *b"es",
+        country: *b"US",
+        ui_mode: UI_MODE_NIGHT_YES,
+        density: 480,
+        sdk_version: 30,
+        ..ResConfig::default()
+    };
+    assert_eq!(resolved(&table, app_name, &device), "Mi App");
+
+    /* The platform version is "at least" */
+    device.sdk_version = 28;
+    assert_eq!(resolved(&table, app_name, &device), "My App");
+}
+
+#[test]
+fn resolve_by_density() {
+    let table = fixture();
+    let is_tablet = table.resource_id("bool", "is_tablet").unwrap();
+
+    assert_eq!(resolved(&table, is_tablet, &density(320)), "true");
+    assert_eq!(resolved(&table, is_tablet, &density(480)), "true");
+    assert_eq!(resolved(&table, is_tablet, &density(160)), "false");
+    assert_eq!(resolved(&table, is_tablet, &density(DENSITY_ANY)), "false");
+}
+
+/// ResTable_config of 64 bytes (as written by aapt2) with the given bytes set
+fn raw_config(fields: &[(usize, u8)]) -> ResConfig {
+    let mut raw = vec![0; 64];
+    raw[0] = 64;
+    for &(index, value) in fields {
+        raw[index] = value;
+    }
+    ResConfig::from_buff(&mut Cursor::new(raw)).unwrap()
+}
+
+#[test]
+fn grammatical_gender_is_parsed() {
+    /* grammaticalInflection is the last byte of the `input` union, after
+     * keyboard, navigation and inputFlags */
+    let config = raw_config(&[(8, b'f'), (9, b'r'), (19, 2)]);
+    assert_eq!(config.grammatical_inflection, 2);
+    assert_eq!(config.to_string(), "fr-feminine");
+
+    /* Byte 50 is padding */
+    let config = raw_config(&[(50, 3)]);
+    assert_eq!(config.grammatical_inflection, 0);
+    assert!(config.is_default());
+
+    let device = ResConfig { grammatical_inflection: 2, ..ResConfig::default() };
+    assert!(raw_config(&[(19, 2)]).matches(&device));
+    assert!(!raw_config(&[(19, 3)]).matches(&device));
+    assert!(raw_config(&[(19, 2)]).is_better_than(&ResConfig::default(), &device));
+}
+
+#[test]
+fn unrequested_qualifiers_do_not_make_a_config_better() {
+    /* Neither the version nor the screen size in pixels is more specific
+     * for a device which does not give them */
+    let device = ResConfig::default();
+    let v29 = ResConfig { sdk_version: 29, ..ResConfig::default() };
+    assert!(!v29.is_better_than(&ResConfig::default(), &device));
+    assert!(!ResConfig::default().is_better_than(&v29, &device));
+
+    let device = ResConfig { sdk_version: 30, ..ResConfig::default() };
+    assert!(v29.is_better_than(&ResConfig::default(), &device));
+
+    let sized = ResConfig { screen_width: 800, screen_height: 480, ..ResConfig::default() };
+    assert!(!sized.is_better_than(&ResConfig::default(), &ResConfig::default()));
+    let device = ResConfig { screen_width: 1280, screen_height: 800, ..ResConfig::default() };
+    assert!(sized.is_better_than(&ResConfig::default(), &device));
+
+    /* For a large screen, an unspecified size is a normal one */
+    const SCREENSIZE_NORMAL: u8 = 0x02;
+    const SCREENSIZE_LARGE: u8 = 0x03;
+    let device = ResConfig { screen_layout: SCREENSIZE_LARGE, ..ResConfig::default() };
+    let normal = ResConfig { screen_layout: SCREENSIZE_NORMAL, ..ResConfig::default() };
+    assert!(normal.is_better_than(&ResConfig::default(), &device));
+    assert!(!ResConfig::default().is_better_than(&normal, &device));
+}
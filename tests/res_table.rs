@@ -0,0 +1,52 @@
+use std::fs;
+
+use axml_parser::parse_arsc_reader;
+use axml_parser::res_table::ResTable;
+use axml_parser::ResourceResolver;
+
+/* Table of `com.example.app` with a drawable, a string translated in
+ * several configurations, a style, a bool and an integer */
+fn fixture() -> ResTable {
+    parse_arsc_reader(fs::File::open("tests/fixtures/resources.arsc").unwrap()).unwrap()
+}
+
+#[test]
+fn resource_ids_by_name() {
+    let table = fixture();
+
+    assert_eq!(table.resource_id("string", "app_name"), Some(0x7f030000));
+    assert_eq!(table.resource_id("style", "AppTheme"), Some(0x7f040000));
+    assert_eq!(table.resource_id("bool", "is_tablet"), Some(0x7f050000));
+    assert_eq!(table.resource_id("string", "is_tablet"), None);
+    assert_eq!(table.resource_id("string", "missing"), None);
+}
+
+#[test]
+fn names_and_ids_agree() {
+    let table = fixture();
+
+    for id in table.resource_ids() {
+        let name = table.resource_name(id).unwrap();
+        assert_eq!(table.resource_id(&name.type_name, &name.entry), Some(id));
+    }
+}
+
+#[test]
+fn merged_resources_are_indexed() {
+    let mut table = fixture();
+
+    /* Another package, whose string is renamed */
+    let mut other = fixture();
+    let package = &mut other.packages[0];
+    package.id = 0x80;
+    let key = package.key_names.push("greeting".to_string());
+    for res_type in package.types.iter_mut().filter(|res_type| res_type.id == 0x03) {
+        res_type.entries.get_mut(&0).unwrap().key = key;
+    }
+
+    table.merge(other);
+    assert_eq!(table.resource_id("string", "greeting"), Some(0x80030000));
+
+    /* The lowest ID wins when two packages define the same name */
+    assert_eq!(table.resource_id("bool", "is_tablet"), Some(0x7f050000));
+}
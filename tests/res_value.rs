@@ -0,0 +1,173 @@
+use std::fs;
+
+use axml_parser::data_value_type::DataValueType;
+use axml_parser::parser::Attribute;
+use axml_parser::res_resolver::ResourceResolver;
+use axml_parser::res_value::Color;
+use axml_parser::{
+    parse_arsc_reader,
+    AxmlEvent,
+    AxmlReader,
+    FrameworkResources,
+};
+
+/* Layouts generated by tools/gen_test_fixtures.py, whose values are encoded
+ * the way aapt encodes them (see `complex_data` there) */
+const DIMENSIONS: &str = "tests/fixtures/dimensions.xml";
+const COLORS: &str = "tests/fixtures/colors.xml";
+
+/* Refers to resources of tests/fixtures/resources.arsc, and of the framework */
+const REFERENCES: &str = "tests/fixtures/references.xml";
+const RESOURCES: &str = "tests/fixtures/resources.arsc";
+
+/// Attributes of every element of a binary XML file, in document order
+fn elements_attrs(path: &str) -> Vec<Vec<Attribute>> {
+    resolved_elements_attrs(path, None)
+}
+
+/// Attributes of every element, whose references are named by `resolver`
+fn resolved_elements_attrs(path: &str, resolver: Option<&dyn ResourceResolver>) -> Vec<Vec<Attribute>> {
+    let bytes = fs::read(path).unwrap();
+    let mut reader = AxmlReader::from_bytes(&bytes);
+    if let Some(resolver) = resolver {
+        reader = reader.with_resolver(resolver);
+    }
+    reader.filter_map(|event| match event.unwrap() {
+              AxmlEvent::StartElement { attrs, .. } => Some(attrs),
+              _ => None,
+          })
+          .collect()
+}
+
+fn values(attrs: &[Attribute]) -> Vec<(&str, &str)> {
+    attrs.iter().map(|attr| (attr.name.as_str(), attr.value.as_str())).collect()
+}
+
+#[test]
+fn dimensions_and_floats_are_formatted_with_their_unit() {
+    let elements = elements_attrs(DIMENSIONS);
+
+    assert_eq!(values(&elements[0]), [
+        ("layout_width", "16dp"),
+        ("layout_height", "48.5dp"),
+        ("padding", "-8px"),
+        ("textSize", "14.5sp"),
+        ("elevation", "0.5dp"),
+        ("minWidth", "72pt"),
+        ("minHeight", "1in"),
+        ("maxWidth", "2.25mm"),
+        ("drawablePadding", "1000.25px"),
+        ("lineSpacingExtra", "100000px"),
+        ("alpha", "0.25"),
+        ("layout_weight", "-1.5"),
+    ]);
+}
+
+#[test]
+fn every_radix_is_decoded() {
+    let elements = elements_attrs(DIMENSIONS);
+    let attr = |name: &str| elements[0].iter().find(|attr| attr.name == name).unwrap().typed_value;
+
+    /* 23p0, 8p15, 0p23 and 16p7, in the radix bits */
+    for (name, radix, value) in [("layout_width", 0, 16.0), ("layout_height", 2, 48.5),
+                                 ("elevation", 3, 0.5), ("drawablePadding", 1, 1000.25)] {
+        let typed_value = attr(name);
+        assert_eq!(typed_value.data_type, DataValueType::TypeDimension, "{}", name);
+        assert_eq!((typed_value.data >> 4) & 0x3, radix, "{}", name);
+        assert_eq!(typed_value.complex_to_float(), value, "{}", name);
+    }
+
+    /* The mantissa is signed */
+    assert_eq!(attr("padding").complex_to_float(), -8.0);
+    assert_eq!(attr("layout_weight").as_float(), -1.5);
+}
+
+#[test]
+fn fractions_are_formatted_as_percentages() {
+    let elements = elements_attrs(DIMENSIONS);
+
+    assert_eq!(values(&elements[1]), [("pivotX", "50%"), ("pivotY", "100%p")]);
+    assert_eq!(values(&elements[2]), [("pivotX", "12.5%"), ("pivotY", "-25%p")]);
+
+    /* Stored as a ratio */
+    let pivot_x = elements[1][0].typed_value;
+    assert_eq!(pivot_x.data_type, DataValueType::TypeFraction);
+    assert_eq!(pivot_x.complex_to_float(), 0.5);
+}
+
+#[test]
+fn colors_keep_the_form_they_were_written_in() {
+    let elements = elements_attrs(COLORS);
+
+    assert_eq!(values(&elements[0]), [
+        ("textColor", "#80ff0000"),
+        ("textColorHighlight", "#00000000"),
+        ("background", "#336699"),
+        ("textColorHint", "#8f00"),
+        ("shadowColor", "#0f0"),
+        ("textColorLink", "#a0c"),
+    ]);
+}
+
+#[test]
+fn colors_are_typed() {
+    let elements = elements_attrs(COLORS);
+    let colors: Vec<Color> = elements[0].iter().map(|attr| attr.typed_value.as_color().unwrap()).collect();
+
+    assert_eq!(colors, [
+        Color { a: 0x80, r: 0xff, g: 0x00, b: 0x00 },
+        Color { a: 0x00, r: 0x00, g: 0x00, b: 0x00 },
+        Color { a: 0xff, r: 0x33, g: 0x66, b: 0x99 },
+        Color { a: 0x88, r: 0xff, g: 0x00, b: 0x00 },
+        Color { a: 0xff, r: 0x00, g: 0xff, b: 0x00 },
+        Color { a: 0xff, r: 0xaa, g: 0x00, b: 0xcc },
+    ]);
+    assert_eq!(colors[2].to_argb(), 0xff336699);
+
+    /* Only colours have one */
+    assert_eq!(elements_attrs(DIMENSIONS)[0][0].typed_value.as_color(), None);
+}
+
+#[test]
+fn references_are_hex_ids_by_default() {
+    let elements = elements_attrs(REFERENCES);
+
+    assert_eq!(values(&elements[0]), [
+        ("background", "@0x7f020000"),
+        ("text", "@0x7f030000"),
+        ("theme", "@0x01030006"),
+        ("textAppearance", "?0x01010041"),
+        ("textColor", "@null"),
+        ("id", "@0x7f0a0000"),
+        ("icon", "@0x7f020000"),
+        ("textColorHint", "?0x7f010000"),
+    ]);
+}
+
+#[test]
+fn references_are_named_by_the_resolvers() {
+    let table = parse_arsc_reader(fs::File::open(RESOURCES).unwrap()).unwrap();
+    let resolvers: Vec<&dyn ResourceResolver> = vec![&table, &FrameworkResources];
+    let elements = resolved_elements_attrs(REFERENCES, Some(&resolvers));
+
+    /* Resources of the app have no package, the others do, and unknown
+     * ones are left as IDs */
+    assert_eq!(values(&elements[0]), [
+        ("background", "@drawable/icon"),
+        ("text", "@string/app_name"),
+        ("theme", "@android:style/Theme.NoTitleBar"),
+        ("textAppearance", "?android:attr/textAppearanceMedium"),
+        ("textColor", "@null"),
+        ("id", "@0x7f0a0000"),
+        ("icon", "@drawable/icon"),
+        ("textColorHint", "?0x7f010000"),
+    ]);
+
+    /* The framework alone does not know the app's resources */
+    let elements = resolved_elements_attrs(REFERENCES, Some(&FrameworkResources));
+    assert_eq!(&values(&elements[0])[..3], [
+        ("background", "@0x7f020000"),
+        ("text", "@0x7f030000"),
+        ("theme", "@android:style/Theme.NoTitleBar"),
+    ]);
+}
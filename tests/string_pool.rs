@@ -0,0 +1,222 @@
+use std::borrow::Cow;
+use std::fs;
+
+use axml_parser::string_pool::{
+    encode_string_pool,
+    StringPool,
+    StringPoolSpan,
+};
+use axml_parser::res_table::ResTableEntryValue;
+use axml_parser::{
+    parse_arsc_reader,
+    AxmlError,
+    AxmlEvent,
+    AxmlReader,
+    ResourceResolver,
+};
+
+/* Flag of the pools whose strings are encoded in UTF-8 */
+const UTF8_FLAG: u32 = 1 << 8;
+
+/* Tables generated by tools/gen_test_fixtures.py: string resources in a
+ * UTF-8 pool with styles, and a binary XML file with long text in its
+ * UTF-16 pool */
+const STRINGS: &str = "tests/fixtures/strings.arsc";
+const LONG_TEXT: &str = "tests/fixtures/long_text.xml";
+
+/* A table and a manifest with empty strings between the others in their
+ * pools, as obfuscators leave them */
+const EMPTY_STRINGS_TABLE: &str = "tests/fixtures/empty_strings.arsc";
+const EMPTY_STRINGS_XML: &str = "tests/fixtures/empty_strings.xml";
+
+/* Offset of the string pool in a binary XML file, after its header */
+const XML_POOL_OFFSET: usize = 8;
+
+fn flags(chunk: &[u8]) -> u32 {
+    u32::from_le_bytes([chunk[16], chunk[17], chunk[18], chunk[19]])
+}
+
+#[test]
+fn utf8_strings_are_borrowed() {
+    let chunk = encode_string_pool(&["manifest", "package", "été"], true);
+    let pool = StringPool::from_bytes(&chunk).unwrap();
+
+    assert!(pool.is_utf8());
+    assert_eq!(pool.len(), 3);
+    assert!(matches!(pool.get(0), Some(Cow::Borrowed("manifest"))));
+    assert!(matches!(pool.get(2), Some(Cow::Borrowed("été"))));
+    assert_eq!(pool.get(3), None);
+}
+
+#[test]
+fn utf16_strings_are_decoded() {
+    let chunk = encode_string_pool(&["", "activity", "日本語"], false);
+    let pool = StringPool::from_bytes(&chunk).unwrap();
+
+    assert!(!pool.is_utf8());
+    let strings: Vec<Cow<str>> = pool.iter().collect();
+    assert_eq!(strings, ["", "activity", "日本語"]);
+}
+
+#[test]
+fn owned_pool_outlives_its_input() {
+    let pool = {
+        let chunk = encode_string_pool(&["a", "b"], true);
+        StringPool::from_bytes(&chunk).unwrap().into_owned()
+    };
+
+    assert_eq!(pool.get(1).as_deref(), Some("b"));
+}
+
+#[test]
+fn pushed_strings_follow_the_pool() {
+    let chunk = encode_string_pool(&["a"], false);
+    let mut pool = StringPool::from_bytes(&chunk).unwrap();
+
+    assert_eq!(pool.push("b".to_string()), 1);
+    assert_eq!(pool.len(), 2);
+    assert_eq!(pool.get(1).as_deref(), Some("b"));
+}
+
+#[test]
+fn short_header_is_rejected() {
+    let mut chunk = encode_string_pool(&["a"], false);
+    chunk[2..4].copy_from_slice(&20u16.to_le_bytes());
+
+    assert!(matches!(StringPool::from_bytes(&chunk),
+                     Err(AxmlError::SizeMismatch { header_size: 20, .. })));
+}
+
+#[test]
+fn offsets_past_the_chunk_are_rejected() {
+    let mut chunk = encode_string_pool(&["a"], false);
+    chunk[8..12].copy_from_slice(&1000u32.to_le_bytes());
+
+    assert!(matches!(StringPool::from_bytes(&chunk), Err(AxmlError::Truncated { .. })));
+}
+
+#[test]
+fn long_strings_fall_back_to_utf16() {
+    let long = "x".repeat(0x8000);
+    let chunk = encode_string_pool(&["short", long.as_str()], true);
+    assert_eq!(flags(&chunk) & UTF8_FLAG, 0);
+
+    let pool = StringPool::from_bytes(&chunk).unwrap();
+    assert_eq!(pool.get(1).as_deref(), Some(long.as_str()));
+
+    /* Strings which fit are still written in UTF-8 */
+    let fitting = "x".repeat(0x7fff);
+    let chunk = encode_string_pool(&[fitting.as_str()], true);
+    assert_eq!(flags(&chunk) & UTF8_FLAG, UTF8_FLAG);
+    assert_eq!(StringPool::from_bytes(&chunk).unwrap().get(0).as_deref(), Some(fitting.as_str()));
+}
+
+#[test]
+fn cached_strings_are_decoded_once() {
+    let chunk = encode_string_pool(&["service", "receiver"], false);
+    let pool = StringPool::from_bytes(&chunk).unwrap().with_cache();
+
+    assert!(matches!(pool.get(1), Some(Cow::Borrowed("receiver"))));
+    assert!(matches!(pool.get(1), Some(Cow::Borrowed("receiver"))));
+    assert_eq!(pool.get(2), None);
+}
+
+#[test]
+fn style_spans_are_read() {
+    let table = parse_arsc_reader(fs::File::open(STRINGS).unwrap()).unwrap();
+    let pool = &table.strings;
+
+    assert!(pool.is_utf8());
+    assert_eq!(pool.styles(0), [
+        StringPoolSpan { name: 4, first_char: 6, last_char: 10 },
+        StringPoolSpan { name: 5, first_char: 13, last_char: 19 },
+    ]);
+    assert_eq!(pool.get(0).as_deref(), Some("Hello world, welcome!"));
+    assert!(pool.styles(7).is_empty());
+}
+
+#[test]
+fn styled_strings_are_marked_up() {
+    let table = parse_arsc_reader(fs::File::open(STRINGS).unwrap()).unwrap();
+    let pool = &table.strings;
+
+    assert_eq!(pool.styled_string(0).unwrap(), "Hello <b>world</b>, <i>welcome</i>!");
+    assert_eq!(pool.styled_string(1).unwrap(), "<b>Bold and <i>italic</i></b>");
+
+    /* Spans count UTF-16 code units, two for the emoji */
+    assert_eq!(pool.styled_string(2).unwrap(), "\u{1f600} <b>smile</b>");
+
+    /* Attributes follow the name of the tag */
+    assert_eq!(pool.styled_string(3).unwrap(),
+               r#"Read the <a href="https://example.com/terms?a=1&amp;b=2">terms</a> &amp; conditions"#);
+}
+
+#[test]
+fn long_lengths_are_read() {
+    let table = parse_arsc_reader(fs::File::open(STRINGS).unwrap()).unwrap();
+    assert_eq!(table.strings.get(7).as_deref(), Some("\u{e9}".repeat(200).as_str()));
+
+    let bytes = fs::read(LONG_TEXT).unwrap();
+    let pool = StringPool::parse(&bytes, XML_POOL_OFFSET).unwrap();
+    assert!(!pool.is_utf8());
+    assert_eq!(pool.get(2).as_deref(), Some("0123456789".repeat(3500).as_str()));
+}
+
+#[test]
+fn invalid_strings_are_decoded_lossily() {
+    let table = parse_arsc_reader(fs::File::open(STRINGS).unwrap()).unwrap();
+    assert_eq!(table.strings.get(8).as_deref(), Some("na\u{ef}ve \u{fffd}"));
+
+    /* An unpaired surrogate in UTF-16 */
+    let bytes = fs::read(LONG_TEXT).unwrap();
+    let pool = StringPool::parse(&bytes, XML_POOL_OFFSET).unwrap();
+    assert_eq!(pool.get(3).as_deref(), Some("broken \u{fffd} pair"));
+}
+
+#[test]
+fn empty_strings_keep_their_index_in_tables() {
+    let table = parse_arsc_reader(fs::File::open(EMPTY_STRINGS_TABLE).unwrap()).unwrap();
+    let package = &table.packages[0];
+
+    /* Each pool has its own strings */
+    assert_eq!(table.strings.iter().collect::<Vec<_>>(), ["", "First", "", "Second"]);
+    assert_eq!(package.type_names.iter().collect::<Vec<_>>(), ["", "string", "", "bool"]);
+    assert_eq!(package.key_names.iter().collect::<Vec<_>>(), ["", "first", "", "second", "enabled"]);
+
+    let names: Vec<String> = table.resource_ids().into_iter()
+                                  .map(|id| table.resource_name(id).unwrap().to_string())
+                                  .collect();
+    assert_eq!(names, ["com.example.app:string/first", "com.example.app:string/second",
+                       "com.example.app:string/", "com.example.app:bool/enabled"]);
+
+    let values: Vec<String> = (0..3).map(|entry| match &table.entries(0x7f020000 | entry)[0].1.value {
+                                        ResTableEntryValue::Simple(value) => value.format(&table.strings, None),
+                                        ResTableEntryValue::Complex { .. } => panic!("not a string"),
+                                    })
+                                    .collect();
+    assert_eq!(values, ["First", "Second", ""]);
+}
+
+#[test]
+fn empty_strings_keep_their_index_in_binary_xml() {
+    let bytes = fs::read(EMPTY_STRINGS_XML).unwrap();
+    let pool = StringPool::parse(&bytes, XML_POOL_OFFSET).unwrap();
+    assert_eq!(pool.iter().take(4).collect::<Vec<_>>(), ["", "", "", "android"]);
+
+    let attrs: Vec<(String, String)> = AxmlReader::from_bytes(&bytes)
+        .filter_map(|event| match event.unwrap() {
+            AxmlEvent::StartElement { attrs, .. } => Some(attrs),
+            _ => None,
+        })
+        .flatten()
+        .map(|attr| (attr.qualified_name(), attr.value))
+        .collect();
+
+    /* Emptied names are given back by their resource ID */
+    assert_eq!(attrs, [
+        ("android:versionCode".to_string(), "42".to_string()),
+        ("android:versionName".to_string(), "".to_string()),
+        ("package".to_string(), "com.example.app".to_string()),
+        ("android:label".to_string(), "@0x7f030000".to_string()),
+    ]);
+}
@@ -0,0 +1,164 @@
+#!/usr/bin/env python3
+"""
+Generate src/framework_res_table.rs, the table of the public resources of the
+Android framework (package 0x01), and of the formats of its attributes.
+
+The IDs come from the platform's public.xml files, the API level in which
+each resource was introduced from api-versions.xml, and the formats of the
+attributes (with the names of their enum and flag values) from attrs.xml and
+attrs_manifest.xml. All can be found in the Android SDK:
+
+    $ANDROID_HOME/platforms/android-XX/data/res/values/public*.xml
+    $ANDROID_HOME/platforms/android-XX/data/res/values/attrs*.xml
+    $ANDROID_HOME/platform-tools/api/api-versions.xml
+
+Usage:
+    tools/gen_framework_res.py --attrs attrs.xml --attrs attrs_manifest.xml \\
+        api-versions.xml public.xml [public-*.xml...] > src/framework_res_table.rs
+"""
+
+import argparse
+import sys
+import xml.etree.ElementTree as ET
+
+HEADER = """\
+/* Generated by tools/gen_framework_res.py from the platform's public.xml,
+ * attrs.xml, attrs_manifest.xml and api-versions.xml, do not edit by hand. */
+
+/* Public resources of the Android framework, sorted by ID:
+ * (ID, type, name, API level in which the resource was introduced) */
+pub(crate) static FRAMEWORK_RESOURCES: &[(u32, &str, &str, u32)] = &[
+"""
+
+ATTRIBUTES_HEADER = """\
+];
+
+/* Names and values of the enum or flag values of an attribute */
+pub(crate) type AttributeValues = &'static [(&'static str, u32)];
+
+/* Formats of the public attributes of the Android framework, sorted by ID:
+ * (ID, types of values accepted as ResTable_map type bits, enum or flag
+ * values) */
+pub(crate) static FRAMEWORK_ATTRIBUTES: &[(u32, u32, AttributeValues)] = &[
+"""
+
+FOOTER = """\
+];
+"""
+
+# Types of values accepted by an attribute, as in ResTable_map
+FORMAT_TYPES = {
+    'reference': 1 << 0,
+    'string': 1 << 1,
+    'integer': 1 << 2,
+    'boolean': 1 << 3,
+    'color': 1 << 4,
+    'float': 1 << 5,
+    'dimension': 1 << 6,
+    'fraction': 1 << 7,
+    'enum': 1 << 16,
+    'flags': 1 << 17,
+}
+TYPE_ANY = 0xffff
+
+
+def r_field_name(name):
+    """Name of the field of android.R for a resource, e.g., Theme_NoTitleBar"""
+    return name.replace('.', '_')
+
+
+def load_api_levels(path):
+    """Map (type, R field name) to the API level it was introduced in"""
+    levels = {}
+    for cls in ET.parse(path).getroot().iter('class'):
+        cls_name = cls.get('name')
+        if not cls_name.startswith('android/R$'):
+            continue
+
+        res_type = cls_name[len('android/R$'):]
+        cls_since = int(cls.get('since', '1'))
+        for field in cls.iter('field'):
+            levels[(res_type, field.get('name'))] = int(field.get('since', cls_since))
+
+    return levels
+
+
+def load_public(paths):
+    """Every `<public type=".." name=".." id=".."/>`, including the ones in
+    `<public-group>` elements which only give the first ID of a sequence"""
+    resources = {}
+    for path in paths:
+        for node in ET.parse(path).getroot():
+            if node.tag == 'public' and node.get('id'):
+                resources[int(node.get('id'), 16)] = (node.get('type'), node.get('name'))
+            elif node.tag == 'public-group':
+                res_type = node.get('type')
+                res_id = int(node.get('first-id'), 16)
+                for child in node.iter('public'):
+                    resources[res_id] = (res_type, child.get('name'))
+                    res_id += 1
+
+    return resources
+
+
+def load_formats(paths):
+    """Map the name of each attribute to its format: the types of values it
+    accepts, and its enum or flag values. Attributes are defined either at
+    the top level or in a `<declare-styleable>`, where they can also just be
+    referenced by name (without format)."""
+    formats = {}
+    for path in paths:
+        for attr in ET.parse(path).getroot().iter('attr'):
+            types = 0
+            for format_name in filter(None, (attr.get('format') or '').split('|')):
+                types |= FORMAT_TYPES[format_name.strip()]
+
+            symbols = []
+            for child in attr:
+                if child.tag in ('enum', 'flag'):
+                    types |= FORMAT_TYPES['enum' if child.tag == 'enum' else 'flags']
+                    symbols.append((child.get('name'), int(child.get('value'), 0) & 0xffffffff))
+
+            if types == 0:
+                if attr.get('name') in formats:
+                    continue
+                types = TYPE_ANY
+
+            formats[attr.get('name')] = (types, symbols)
+
+    return formats
+
+
+def main():
+    parser = argparse.ArgumentParser(usage=__doc__)
+    parser.add_argument('--attrs', action='append', default=[])
+    parser.add_argument('api_versions')
+    parser.add_argument('public', nargs='+')
+    args = parser.parse_args()
+
+    levels = load_api_levels(args.api_versions)
+    resources = load_public(args.public)
+    formats = load_formats(args.attrs)
+
+    out = [HEADER]
+    for res_id in sorted(resources):
+        res_type, name = resources[res_id]
+        api = levels.get((res_type, r_field_name(name)), 1)
+        out.append('    (0x%08x, "%s", "%s", %d),\n' % (res_id, res_type, name, api))
+
+    out.append(ATTRIBUTES_HEADER)
+    for res_id in sorted(resources):
+        res_type, name = resources[res_id]
+        if res_type != 'attr' or name not in formats:
+            continue
+
+        types, symbols = formats[name]
+        values = ', '.join('("%s", 0x%x)' % symbol for symbol in symbols)
+        out.append('    (0x%08x, 0x%x, &[%s]),\n' % (res_id, types, values))
+    out.append(FOOTER)
+
+    sys.stdout.write(''.join(out))
+
+
+if __name__ == '__main__':
+    main()
@@ -0,0 +1,883 @@
+#!/usr/bin/env python3
+"""Generate the binary fixtures of tests/fixtures.
+
+The fixtures are written byte by byte from the structures of AOSP's
+ResourceTypes.h, with the same layout as aapt2's output (UTF-16 global
+string pool, UTF-8 key pools, 64-byte configurations, 8-byte entries,
+attributes with a resource ID first in the XML string pool), without
+going through the crate, so that the tests do not only check that the
+crate agrees with itself.
+
+Usage: tools/gen_test_fixtures.py [OUTPUT_DIR]  (default: tests/fixtures)
+"""
+
+import io
+import os
+import struct
+import sys
+import zipfile
+
+ANDROID = 'http://schemas.android.com/apk/res/android'
+NO_INDEX = 0xffffffff
+
+# Res_value types
+TYPE_REFERENCE = 0x01
+TYPE_ATTRIBUTE = 0x02
+TYPE_STRING = 0x03
+TYPE_FLOAT = 0x04
+TYPE_DIMENSION = 0x05
+TYPE_FRACTION = 0x06
+TYPE_DYNAMIC_REFERENCE = 0x07
+TYPE_DYNAMIC_ATTRIBUTE = 0x08
+TYPE_INT_DEC = 0x10
+TYPE_INT_BOOLEAN = 0x12
+TYPE_INT_COLOR_ARGB8 = 0x1c
+TYPE_INT_COLOR_RGB8 = 0x1d
+TYPE_INT_COLOR_ARGB4 = 0x1e
+TYPE_INT_COLOR_RGB4 = 0x1f
+
+
+def chunk(chunk_type, header_extra, body, header_size=None):
+    """ResChunk_header, the rest of the header and the body of a chunk"""
+    header_size = header_size or 8 + len(header_extra)
+    return struct.pack('<HHI', chunk_type, header_size, header_size + len(body)) + header_extra + body
+
+
+def string_pool(strings, utf8=False, styles=()):
+    """ResStringPool. Strings are `str`, or `bytes` to write invalid UTF-8
+    as is. `styles` holds the spans of the first strings, as (name index,
+    first char, last char) tuples."""
+    offsets = []
+    data = b''
+    for string in strings:
+        offsets.append(len(data))
+        if isinstance(string, bytes):
+            data += utf8_length(len(string)) + utf8_length(len(string)) + string + b'\0'
+        elif utf8:
+            encoded = string.encode('utf-8')
+            data += utf8_length(utf16_units(string)) + utf8_length(len(encoded)) + encoded + b'\0'
+        else:
+            encoded = string.encode('utf-16-le', 'surrogatepass')
+            data += utf16_length(len(encoded) // 2) + encoded + b'\0\0'
+    while len(data) % 4:
+        data += b'\0'
+
+    # Each style is its spans, then an end marker, and the styles end with
+    # two more end markers
+    style_offsets = []
+    style_data = b''
+    for spans in styles:
+        style_offsets.append(len(style_data))
+        style_data += b''.join(struct.pack('<III', *span) for span in spans) + struct.pack('<I', NO_INDEX)
+    if styles:
+        style_data += struct.pack('<II', NO_INDEX, NO_INDEX)
+
+    header_size = 28
+    strings_start = header_size + 4 * (len(strings) + len(styles))
+    styles_start = strings_start + len(data) if styles else 0
+    flags = 1 << 8 if utf8 else 0
+    body = struct.pack('<IIIII', len(strings), len(styles), flags, strings_start, styles_start)
+    body += b''.join(struct.pack('<I', offset) for offset in offsets + style_offsets) + data + style_data
+    return struct.pack('<HHI', 0x0001, header_size, 8 + len(body)) + body
+
+
+def utf16_units(string):
+    return len(string.encode('utf-16-le', 'surrogatepass')) // 2
+
+
+def utf8_length(length):
+    if length < 0x80:
+        return bytes([length])
+    return bytes([0x80 | (length >> 8), length & 0xff])
+
+
+def utf16_length(length):
+    if length < 0x8000:
+        return struct.pack('<H', length)
+    return struct.pack('<HH', 0x8000 | (length >> 16), length & 0xffff)
+
+
+class XmlBuilder:
+    """Binary XML document, built from events like aapt2's XmlFlattener"""
+
+    def __init__(self):
+        self.strings = []
+        self.events = []
+
+    def index(self, string):
+        if string is None:
+            return NO_INDEX
+        if string not in self.strings:
+            self.strings.append(string)
+        return self.strings.index(string)
+
+    def namespace(self, prefix, uri, end=False, line=1):
+        self.events.append(('namespace', line, end, prefix, uri))
+
+    def start(self, name, attrs, ns=None, line=1):
+        """`attrs` holds (namespace, name, raw value, type, data) tuples"""
+        self.events.append(('start', line, name, attrs, ns))
+
+    def end(self, name, ns=None, line=1):
+        self.events.append(('end', line, name, ns))
+
+    def text(self, text, line=1, string_typed=False):
+        """Text node, typed as TYPE_NULL like aapt2 does or, with
+        `string_typed`, as a string like older tools did"""
+        self.events.append(('text', line, text, string_typed))
+
+    def build(self, resource_ids=None, utf8=False, extra=b'', rename=None):
+        """`extra` is inserted after the resource map, and `rename` replaces
+        some strings of the pool (as obfuscators do)"""
+        resource_ids = resource_ids or {}
+        rename = rename or {}
+
+        # Names of attributes with an ID come first, in the order of the map
+        for event in self.events:
+            if event[0] == 'start':
+                for attr in event[3]:
+                    if attr[1] in resource_ids and attr[1] not in self.strings:
+                        self.strings.append(attr[1])
+        self.strings = [s for s in self.strings if s in resource_ids] + [s for s in self.strings if s not in resource_ids]
+
+        nodes = b''
+        for event in self.events:
+            if event[0] == 'namespace':
+                _, line, end, prefix, uri = event
+                nodes += struct.pack('<HHIIIII', 0x0101 if end else 0x0100, 16, 24, line, NO_INDEX,
+                                     self.index(prefix), self.index(uri))
+            elif event[0] == 'start':
+                _, line, name, attrs, ns = event
+                raw_attrs = b''
+                for (attr_ns, attr_name, raw_value, data_type, data) in attrs:
+                    raw_index = self.index(raw_value) if raw_value is not None else NO_INDEX
+                    if data_type == TYPE_STRING:
+                        data = self.index(raw_value)
+                    raw_attrs += struct.pack('<IIIHBBI', self.index(attr_ns), self.index(attr_name), raw_index,
+                                             8, 0, data_type, data)
+                nodes += struct.pack('<HHIIIIIHHHHHH', 0x0102, 16, 36 + len(raw_attrs), line, NO_INDEX,
+                                     self.index(ns), self.index(name), 20, 20, len(attrs), 0, 0, 0) + raw_attrs
+            elif event[0] == 'end':
+                _, line, name, ns = event
+                nodes += struct.pack('<HHIIIII', 0x0103, 16, 24, line, NO_INDEX, self.index(ns), self.index(name))
+            elif event[0] == 'text':
+                _, line, text, string_typed = event
+                index = self.index(text)
+                data_type, data = (TYPE_STRING, index) if string_typed else (0, 0)
+                nodes += struct.pack('<HHIIIIHBBI', 0x0104, 16, 28, line, NO_INDEX, index, 8, 0, data_type, data)
+
+        pool = string_pool([rename.get(s, s) for s in self.strings], utf8)
+        ids = [resource_ids[s] for s in self.strings if s in resource_ids]
+        resource_map = b''
+        if ids:
+            resource_map = struct.pack('<HHI', 0x0180, 8, 8 + 4 * len(ids)) + b''.join(struct.pack('<I', i) for i in ids)
+        body = pool + resource_map + extra + nodes
+        return struct.pack('<HHI', 0x0003, 8, 8 + len(body)) + body
+
+
+def config(lang=b'\0\0', country=b'\0\0', density=0, sdk=0, night=0, orientation=0, size=64):
+    """ResTable_config"""
+    raw = bytearray(size)
+    struct.pack_into('<I', raw, 0, size)
+    raw[8:10] = lang
+    raw[10:12] = country
+    raw[12] = orientation
+    struct.pack_into('<H', raw, 14, density)
+    struct.pack_into('<H', raw, 24, sdk)
+    raw[29] = night
+    return bytes(raw)
+
+
+def type_spec(type_id, flags):
+    """ResTable_typeSpec, with the flags of each entry"""
+    return chunk(0x0202, struct.pack('<BBHI', type_id, 0, 0, len(flags)),
+                 b''.join(struct.pack('<I', f) for f in flags))
+
+
+def res_type(type_id, res_config, entries, count):
+    """ResTable_type, `entries` maps entry indices to their bytes"""
+    offsets = b''
+    data = b''
+    for i in range(count):
+        if i in entries:
+            offsets += struct.pack('<I', len(data))
+            data += entries[i]
+        else:
+            offsets += struct.pack('<I', NO_INDEX)
+    header = struct.pack('<BBHII', type_id, 0, 0, count, 8 + 12 + len(res_config) + len(offsets)) + res_config
+    return chunk(0x0201, header, offsets + data)
+
+
+def simple_entry(key, data_type, data):
+    return struct.pack('<HHI', 8, 0, key) + struct.pack('<HBBI', 8, 0, data_type, data)
+
+
+def map_entry(key, parent, items):
+    raw = struct.pack('<HHIII', 16, 1, key, parent, len(items))
+    for name, data_type, data in items:
+        raw += struct.pack('<I', name) + struct.pack('<HBBI', 8, 0, data_type, data)
+    return raw
+
+
+def package(package_id, name, types, keys, body):
+    """ResTable_package with its type and key pools"""
+    header_size = 288
+    type_pool = string_pool(types)
+    key_pool = string_pool(keys, utf8=True)
+    header = struct.pack('<I', package_id) + name.encode('utf-16-le').ljust(256, b'\0')
+    header += struct.pack('<IIIII', header_size, len(types), header_size + len(type_pool), len(keys), 0)
+    return chunk(0x0200, header, type_pool + key_pool + body, header_size)
+
+
+def table(strings, packages, utf8=False, styles=()):
+    return chunk(0x0002, struct.pack('<I', len(packages)), string_pool(strings, utf8, styles) + b''.join(packages))
+
+
+def float_bits(value):
+    return struct.unpack('<I', struct.pack('<f', value))[0]
+
+
+def resources_arsc():
+    """tests/fixtures/resources.arsc: `com.example.app` with a drawable, a
+    string translated in several configurations, a style, a bool and an
+    integer"""
+    body = type_spec(2, [0])
+    body += res_type(2, config(), {0: simple_entry(1, TYPE_STRING, 2)}, 1)
+    body += type_spec(3, [4])
+    body += res_type(3, config(), {0: simple_entry(0, TYPE_STRING, 0)}, 1)
+    body += res_type(3, config(lang=b'fr'), {0: simple_entry(0, TYPE_STRING, 1)}, 1)
+    body += res_type(3, config(lang=b'es', country=b'US', night=0x20, sdk=29, density=480),
+                     {0: simple_entry(0, TYPE_STRING, 3)}, 1)
+    body += type_spec(4, [0])
+    body += res_type(4, config(), {0: map_entry(2, 0x01030237, [(0x01010098, TYPE_INT_COLOR_ARGB8, 0xff336699)])}, 1)
+    body += type_spec(5, [0])
+    body += res_type(5, config(), {0: simple_entry(3, TYPE_INT_BOOLEAN, 0)}, 1)
+    body += res_type(5, config(density=320, size=32), {0: simple_entry(3, TYPE_INT_BOOLEAN, NO_INDEX)}, 1)
+    body += type_spec(6, [0])
+    body += res_type(6, config(), {0: simple_entry(4, TYPE_INT_DEC, 10)}, 1)
+
+    app = package(0x7f, 'com.example.app', ['attr', 'drawable', 'string', 'style', 'bool', 'integer'],
+                  ['app_name', 'icon', 'AppTheme', 'is_tablet', 'max_items'], body)
+    return table(['My App', 'Mon App', 'res/drawable/icon.png', 'Mi App'], [app])
+
+
+MANIFEST_IDS = {
+    'versionCode': 0x0101021b, 'versionName': 0x0101021c, 'minSdkVersion': 0x0101020c,
+    'targetSdkVersion': 0x01010270, 'label': 0x01010001, 'icon': 0x01010002, 'name': 0x01010003,
+    'debuggable': 0x0101000f, 'exported': 0x01010010, 'hasCode': 0x0101000c, 'isFeatureSplit': 0x0101055b,
+}
+
+
+def base_manifest():
+    """Manifest of the base APK, referring to resources.arsc"""
+    xml = XmlBuilder()
+    xml.namespace('android', ANDROID)
+    xml.start('manifest', [(ANDROID, 'versionCode', None, TYPE_INT_DEC, 42),
+                           (ANDROID, 'versionName', '1.0', TYPE_STRING, 0),
+                           (None, 'package', 'com.example.app', TYPE_STRING, 0)])
+    xml.start('uses-permission', [(ANDROID, 'name', 'android.permission.INTERNET', TYPE_STRING, 0)])
+    xml.end('uses-permission')
+    xml.start('application', [(ANDROID, 'label', None, TYPE_REFERENCE, 0x7f030000),
+                              (ANDROID, 'icon', None, TYPE_REFERENCE, 0x7f020000)])
+    xml.start('activity', [(ANDROID, 'name', 'com.example.app.MainActivity', TYPE_STRING, 0),
+                           (ANDROID, 'exported', None, TYPE_INT_BOOLEAN, NO_INDEX)])
+    xml.end('activity')
+    xml.end('application')
+    xml.end('manifest')
+    xml.namespace('android', ANDROID, end=True)
+    return xml.build(MANIFEST_IDS)
+
+
+def split_manifest(split, feature=False, config_for=None):
+    """Manifest of a split, as written by bundletool"""
+    attrs = [(None, 'package', 'com.example.app', TYPE_STRING, 0), (None, 'split', split, TYPE_STRING, 0)]
+    if feature:
+        attrs.append((ANDROID, 'isFeatureSplit', None, TYPE_INT_BOOLEAN, NO_INDEX))
+    if config_for is not None:
+        attrs.append((None, 'configForSplit', config_for, TYPE_STRING, 0))
+
+    xml = XmlBuilder()
+    xml.namespace('android', ANDROID)
+    xml.start('manifest', attrs)
+    xml.start('application', [(ANDROID, 'hasCode', None, TYPE_INT_BOOLEAN, 0)])
+    xml.end('application')
+    xml.end('manifest')
+    xml.namespace('android', ANDROID, end=True)
+    return xml.build(MANIFEST_IDS)
+
+
+def split_resources_arsc(app_name, icon_path):
+    """Resource table of a configuration split: a German `string/app_name`
+    and a new xxhdpi drawable, `drawable/icon_hd` (0x7f020001)"""
+    body = type_spec(2, [0, 0])
+    body += res_type(2, config(density=480), {1: simple_entry(2, TYPE_STRING, 2)}, 2)
+    body += type_spec(3, [4])
+    body += res_type(3, config(lang=b'de'), {0: simple_entry(1, TYPE_STRING, 1)}, 1)
+    app = package(0x7f, 'com.example.app', ['attr', 'drawable', 'string'], ['unused', 'app_name', 'icon_hd'], body)
+    return table(['unused', app_name, icon_path], [app])
+
+
+def zip_file(files):
+    raw = io.BytesIO()
+    with zipfile.ZipFile(raw, 'w') as archive:
+        for name, contents in files.items():
+            # A fixed date, so that the fixtures do not change when generated again
+            archive.writestr(zipfile.ZipInfo(name, date_time=(2024, 1, 1, 0, 0, 0)), contents)
+    return raw.getvalue()
+
+
+def apk():
+    """tests/fixtures/app.apk: the base APK with XML resources: a binary
+    layout, a text XML file, a truncated layout, a file named like XML which
+    is not, and an image"""
+    return {
+        'AndroidManifest.xml': base_manifest(),
+        'resources.arsc': resources_arsc(),
+        'res/layout/main.xml': references_xml(),
+        'res/layout/broken.xml': references_xml()[:40],
+        'res/xml/config.xml': b'<?xml version="1.0" encoding="utf-8"?>\n<config enabled="true" />\n',
+        'res/raw/notes.xml': b'not XML at all',
+        'res/drawable/icon.png': b'\x89PNG\r\n\x1a\n',
+    }
+
+
+def bundles():
+    """The same app as .apks (bundletool), .xapk and .apkm: a base APK, a
+    German configuration split, a feature split and the feature split's own
+    configuration split. The last one holds another translation of
+    `string/app_name`, which must not be merged with the base resources.
+    broken.apks has a configuration split with a truncated table, and an
+    entry which is not an APK."""
+    base = zip_file({'AndroidManifest.xml': base_manifest(), 'resources.arsc': resources_arsc()})
+    config_de = zip_file({'AndroidManifest.xml': split_manifest('config.de'),
+                          'resources.arsc': split_resources_arsc('Meine App', 'res/drawable-xxhdpi/icon_hd.png')})
+    feature = zip_file({'AndroidManifest.xml': split_manifest('feature1', feature=True)})
+    feature_de = zip_file({'AndroidManifest.xml': split_manifest('feature1.config.de', config_for='feature1'),
+                           'resources.arsc': split_resources_arsc('Feature', 'res/drawable-xxhdpi/feature.png')})
+    broken_table = split_resources_arsc('Kaputt', 'res/drawable-xxhdpi/icon_hd.png')
+    broken_de = zip_file({'AndroidManifest.xml': split_manifest('config.de'), 'resources.arsc': broken_table[:100]})
+
+    return {
+        'app.apks': {
+            'toc.pb': b'',
+            'splits/base-master.apk': base,
+            'splits/base-de.apk': config_de,
+            'splits/feature1-master.apk': feature,
+            'splits/feature1-de.apk': feature_de,
+            'standalones/standalone-hdpi.apk': base,
+        },
+        'app.xapk': {
+            'manifest.json': b'{}',
+            'com.example.app.apk': base,
+            'config.de.apk': config_de,
+            'icon.png': b'',
+        },
+        'app.apkm': {
+            'info.json': b'{}',
+            'split_config.de.apk': config_de,
+            'base.apk': base,
+            'split_feature1.apk': feature,
+        },
+        'broken.apks': {
+            'splits/base-master.apk': base,
+            'splits/base-de.apk': broken_de,
+            'splits/garbage.apk': b'not a zip',
+        },
+    }
+
+
+def text_xml():
+    """tests/fixtures/text.xml: a values file with mixed content, text
+    before, between and after child elements, on several lines, and a text
+    node typed as a string"""
+    xml = XmlBuilder()
+    xml.start('resources', [], line=1)
+    xml.start('string', [(None, 'name', 'greeting', TYPE_STRING, 0)], line=2)
+    xml.text('Hello ', line=2)
+    xml.start('b', [], line=2)
+    xml.text('dear', line=2)
+    xml.end('b', line=2)
+    xml.text(' reader,\nwelcome ', line=2)
+    xml.start('i', [], line=3)
+    xml.end('i', line=3)
+    xml.text('!', line=3, string_typed=True)
+    xml.end('string', line=3)
+    xml.end('resources', line=4)
+    return xml.build()
+
+
+def cdata_xml():
+    """tests/fixtures/cdata.xml: a values file whose text holds characters
+    to escape in text XML, one text node typed as a string"""
+    xml = XmlBuilder()
+    xml.start('resources', [], line=1)
+    xml.start('string', [(None, 'name', 'terms', TYPE_STRING, 0)], line=2)
+    xml.text('Terms & <conditions> apply', line=2)
+    xml.end('string', line=2)
+    xml.start('string', [(None, 'name', 'arrow', TYPE_STRING, 0)], line=3)
+    xml.text('a -> b && c', line=3, string_typed=True)
+    xml.end('string', line=3)
+    xml.end('resources', line=4)
+    return xml.build()
+
+
+def strings_arsc():
+    """tests/fixtures/strings.arsc: string resources in a UTF-8 pool, as
+    aapt2 writes them: styled strings first (nested spans, spans after a
+    character outside the BMP, a link), then the names of their tags, a
+    string whose lengths take two bytes, and invalid UTF-8"""
+    strings = ['Hello world, welcome!', 'Bold and italic', '\U0001f600 smile', 'Read the terms & conditions',
+               'b', 'i', 'a;href=https://example.com/terms?a=1&b=2', '\u00e9' * 200, b'na\xc3\xafve \xff']
+    styles = [
+        [(4, 6, 10), (5, 13, 19)],
+        [(4, 0, 14), (5, 9, 14)],
+        [(4, 3, 7)],
+        [(6, 9, 13)],
+    ]
+    keys = ['welcome', 'nested', 'smile', 'terms', 'long', 'invalid']
+    values = [0, 1, 2, 3, 7, 8]
+    body = type_spec(1, [0] * len(keys))
+    body += res_type(1, config(), {i: simple_entry(i, TYPE_STRING, value) for i, value in enumerate(values)},
+                     len(keys))
+    app = package(0x7f, 'com.example.app', ['string'], keys, body)
+    return table(strings, [app], utf8=True, styles=styles)
+
+
+def empty_strings_arsc():
+    """tests/fixtures/empty_strings.arsc: a table whose global, type and
+    key pools hold empty strings between the others (as left by resource
+    obfuscators), so that an index is only right if they are counted"""
+    body = type_spec(2, [0, 0, 0])
+    body += res_type(2, config(), {0: simple_entry(1, TYPE_STRING, 1), 1: simple_entry(3, TYPE_STRING, 3),
+                                   2: simple_entry(2, TYPE_STRING, 0)}, 3)
+    body += type_spec(4, [0])
+    body += res_type(4, config(), {0: simple_entry(4, TYPE_INT_BOOLEAN, NO_INDEX)}, 1)
+    app = package(0x7f, 'com.example.app', ['', 'string', '', 'bool'], ['', 'first', '', 'second', 'enabled'], body)
+    return table(['', 'First', '', 'Second'], [app])
+
+
+def empty_strings_xml():
+    """tests/fixtures/empty_strings.xml: a manifest whose attribute names
+    with a resource ID are emptied in the string pool (as obfuscators do),
+    followed by the other strings"""
+    xml = XmlBuilder()
+    xml.namespace('android', ANDROID)
+    xml.start('manifest', [(ANDROID, 'versionCode', None, TYPE_INT_DEC, 42),
+                           (ANDROID, 'versionName', '', TYPE_STRING, 0),
+                           (None, 'package', 'com.example.app', TYPE_STRING, 0)])
+    xml.start('application', [(ANDROID, 'label', None, TYPE_REFERENCE, 0x7f030000)])
+    xml.end('application')
+    xml.end('manifest')
+    xml.namespace('android', ANDROID, end=True)
+    return xml.build(MANIFEST_IDS, rename={'versionCode': '', 'versionName': '', 'label': ''})
+
+
+def long_text_xml():
+    """tests/fixtures/long_text.xml: a document whose UTF-16 pool holds a
+    text longer than 0x7fff characters, whose length takes two uint16_t, and
+    a text with an unpaired surrogate"""
+    xml = XmlBuilder()
+    xml.start('resources', [], line=1)
+    xml.start('string', [], line=2)
+    xml.text('0123456789' * 3500, line=2)
+    xml.end('string', line=2)
+    xml.start('string', [], line=3)
+    xml.text('broken \ud800 pair', line=3)
+    xml.end('string', line=3)
+    xml.end('resources', line=4)
+    return xml.build()
+
+
+def padded_resources_arsc():
+    """tests/fixtures/padded.arsc: resources.arsc with 4 bytes of padding
+    after the key pool of the package and a chunk of an unknown type
+    (0x0777) between two type chunks"""
+    raw = resources_arsc()
+    global_pool = struct.unpack_from('<H', raw, 2)[0]
+    package_start = global_pool + struct.unpack_from('<I', raw, global_pool + 4)[0]
+    type_pool = package_start + 288
+    key_pool = type_pool + struct.unpack_from('<I', raw, type_pool + 4)[0]
+    first_spec = key_pool + struct.unpack_from('<I', raw, key_pool + 4)[0]
+    first_type = first_spec + struct.unpack_from('<I', raw, first_spec + 4)[0]
+    second_spec = first_type + struct.unpack_from('<I', raw, first_type + 4)[0]
+
+    unknown = chunk(0x0777, b'', b'\1\2\3\4\5\6\7\x08')
+    padded = raw[:first_spec] + bytes(4) + raw[first_spec:second_spec] + unknown + raw[second_spec:]
+    added = len(padded) - len(raw)
+    padded = bytearray(padded)
+    struct.pack_into('<I', padded, 4, len(padded))
+    package_size = struct.unpack_from('<I', padded, package_start + 4)[0]
+    struct.pack_into('<I', padded, package_start + 4, package_size + added)
+    return bytes(padded)
+
+
+# Formats of attributes, in the ResTable_map of their `attr` resource
+FORMAT_REFERENCE = 1 << 0
+FORMAT_STRING = 1 << 1
+FORMAT_BOOLEAN = 1 << 3
+FORMAT_COLOR = 1 << 4
+FORMAT_FLOAT = 1 << 5
+FORMAT_DIMENSION = 1 << 6
+FORMAT_ENUM = 1 << 16
+FORMAT_FLAGS = 1 << 17
+
+
+def framework_arsc():
+    """tests/fixtures/framework.arsc: a few resources of the `android`
+    package, with their real IDs: attributes with their formats and enum or
+    flag values, the IDs naming those values, and a style"""
+    attrs = {
+        0x0098: ('textColor', FORMAT_REFERENCE | FORMAT_COLOR, []),
+        0x00af: ('gravity', FORMAT_FLAGS, [('top', 0x30), ('bottom', 0x50), ('left', 0x03), ('right', 0x05),
+                                           ('center_vertical', 0x10), ('center_horizontal', 0x01),
+                                           ('center', 0x11)]),
+        0x00d0: ('id', FORMAT_REFERENCE, []),
+        0x00dc: ('visibility', FORMAT_ENUM, [('visible', 0), ('invisible', 1), ('gone', 2)]),
+        0x00f4: ('layout_width', FORMAT_DIMENSION | FORMAT_ENUM, [('fill_parent', NO_INDEX),
+                                                                  ('match_parent', NO_INDEX),
+                                                                  ('wrap_content', 0xfffffffe)]),
+        0x014f: ('text', FORMAT_STRING, []),
+        0x031f: ('alpha', FORMAT_FLOAT, []),
+        0x0603: ('requestLegacyExternalStorage', FORMAT_BOOLEAN, []),
+    }
+    ids = []
+    for _, (_, _, symbols) in sorted(attrs.items()):
+        for name, _ in symbols:
+            if name not in ids:
+                ids.append(name)
+    styles = {0x0006: 'Theme.NoTitleBar'}
+    keys = [name for _, (name, _, _) in sorted(attrs.items())] + ids + list(styles.values())
+
+    # The format is the ATTR_TYPE (0x01000000) item, then each symbol
+    attr_entries = {}
+    for index, (name, formats, symbols) in attrs.items():
+        items = [(0x01000000, TYPE_INT_DEC, formats)]
+        items += [(0x01020000 | ids.index(symbol), TYPE_INT_DEC, value) for symbol, value in symbols]
+        attr_entries[index] = map_entry(keys.index(name), 0, items)
+    id_entries = {i: simple_entry(keys.index(name), TYPE_INT_BOOLEAN, 0) for i, name in enumerate(ids)}
+    style_entries = {i: map_entry(keys.index(name), 0, []) for i, name in styles.items()}
+
+    body = b''
+    for type_id, entries in [(1, attr_entries), (2, id_entries), (3, style_entries)]:
+        count = max(entries) + 1
+        body += type_spec(type_id, [0] * count)
+        body += res_type(type_id, config(), entries, count)
+    android = package(0x01, 'android', ['attr', 'id', 'style'], keys, body)
+    return table([], [android])
+
+
+def complex_data(value, unit):
+    """Data of a dimension or fraction, as aapt's floatToComplex encodes
+    it (ResourceTypes.cpp): the most precise radix which holds the value,
+    and a signed 24-bit mantissa"""
+    negative = value < 0
+    bits = int(abs(value) * (1 << 23) + 0.5)
+    if bits & 0x7fffff == 0:
+        radix, shift = 0, 23
+    elif bits & ~0x7fffff == 0:
+        radix, shift = 3, 0
+    elif bits & ~0x7fffffff == 0:
+        radix, shift = 2, 8
+    elif bits & ~0x7fffffffff == 0:
+        radix, shift = 1, 16
+    else:
+        radix, shift = 0, 23
+    mantissa = (bits >> shift) & 0xffffff
+    if negative:
+        mantissa = -mantissa & 0xffffff
+    return mantissa << 8 | radix << 4 | unit
+
+
+def dimension(value, unit):
+    units = ['px', 'dp', 'sp', 'pt', 'in', 'mm']
+    return TYPE_DIMENSION, complex_data(value, units.index(unit))
+
+
+def fraction(percent, unit):
+    """Fractions are stored as a ratio, 50% as 0.5"""
+    return TYPE_FRACTION, complex_data(percent / 100, ['%', '%p'].index(unit))
+
+
+LAYOUT_IDS = {
+    'layout_width': 0x010100f4, 'layout_height': 0x010100f5, 'padding': 0x010100d5, 'textSize': 0x01010095,
+    'elevation': 0x01010440, 'minWidth': 0x0101013f, 'minHeight': 0x01010140, 'maxWidth': 0x0101011f,
+    'drawablePadding': 0x01010171, 'lineSpacingExtra': 0x01010217, 'alpha': 0x0101031f,
+    'layout_weight': 0x01010181, 'pivotX': 0x010101b5, 'pivotY': 0x010101b6, 'textColor': 0x01010098,
+    'textColorHighlight': 0x01010099, 'textColorHint': 0x0101009a, 'textColorLink': 0x0101009b,
+    'background': 0x010100d4, 'shadowColor': 0x01010161, 'theme': 0x01010000, 'icon': 0x01010002,
+    'textAppearance': 0x01010034, 'text': 0x0101014f, 'id': 0x010100d0,
+}
+
+
+def android_attrs(values):
+    """Attributes of the android namespace, from (name, (type, data))
+    pairs, without raw values like aapt2 writes typed values"""
+    return [(ANDROID, name, None, data_type, data) for name, (data_type, data) in values]
+
+
+def dimensions_xml():
+    """tests/fixtures/dimensions.xml: a layout with dimensions in every
+    unit and radix (integers, 0.5, 48.5, 1000.25, negative values), floats
+    and fractions of the element and of its parent"""
+    xml = XmlBuilder()
+    xml.namespace('android', ANDROID)
+    xml.start('LinearLayout', android_attrs([
+        ('layout_width', dimension(16, 'dp')),
+        ('layout_height', dimension(48.5, 'dp')),
+        ('padding', dimension(-8, 'px')),
+        ('textSize', dimension(14.5, 'sp')),
+        ('elevation', dimension(0.5, 'dp')),
+        ('minWidth', dimension(72, 'pt')),
+        ('minHeight', dimension(1, 'in')),
+        ('maxWidth', dimension(2.25, 'mm')),
+        ('drawablePadding', dimension(1000.25, 'px')),
+        ('lineSpacingExtra', dimension(100000, 'px')),
+        ('alpha', (TYPE_FLOAT, float_bits(0.25))),
+        ('layout_weight', (TYPE_FLOAT, float_bits(-1.5))),
+    ]))
+    xml.start('ImageView', android_attrs([('pivotX', fraction(50, '%')), ('pivotY', fraction(100, '%p'))]), line=2)
+    xml.end('ImageView', line=2)
+    xml.start('ImageView', android_attrs([('pivotX', fraction(12.5, '%')), ('pivotY', fraction(-25, '%p'))]), line=3)
+    xml.end('ImageView', line=3)
+    xml.end('LinearLayout', line=4)
+    xml.namespace('android', ANDROID, end=True, line=4)
+    return xml.build(LAYOUT_IDS)
+
+
+def color(digits):
+    """Colour as aapt stores it: the type tells the form it was written
+    in, and short forms are expanded (#f00 as 0xffff0000), opaque without
+    an alpha channel"""
+    value = int(digits, 16)
+    expanded = 0
+    for nibble in digits:
+        expanded = expanded << 8 | int(nibble, 16) * 0x11
+    return {
+        3: (TYPE_INT_COLOR_RGB4, 0xff000000 | expanded),
+        4: (TYPE_INT_COLOR_ARGB4, expanded),
+        6: (TYPE_INT_COLOR_RGB8, 0xff000000 | value),
+        8: (TYPE_INT_COLOR_ARGB8, value),
+    }[len(digits)]
+
+
+def colors_xml():
+    """tests/fixtures/colors.xml: a layout with colours in each of the
+    #aarrggbb, #rrggbb, #argb and #rgb forms"""
+    xml = XmlBuilder()
+    xml.namespace('android', ANDROID)
+    xml.start('TextView', android_attrs([
+        ('textColor', color('80ff0000')),
+        ('textColorHighlight', color('00000000')),
+        ('background', color('336699')),
+        ('textColorHint', color('8f00')),
+        ('shadowColor', color('0f0')),
+        ('textColorLink', color('A0C')),
+    ]))
+    xml.end('TextView')
+    xml.namespace('android', ANDROID, end=True)
+    return xml.build(LAYOUT_IDS)
+
+
+def references_xml():
+    """tests/fixtures/references.xml: a layout referring to resources of
+    resources.arsc and of the framework, to theme attributes, to `@null`, to
+    an unknown resource, and with the dynamic references aapt2 writes for
+    shared libraries"""
+    xml = XmlBuilder()
+    xml.namespace('android', ANDROID)
+    xml.start('LinearLayout', android_attrs([
+        ('background', (TYPE_REFERENCE, 0x7f020000)),
+        ('text', (TYPE_REFERENCE, 0x7f030000)),
+        ('theme', (TYPE_REFERENCE, 0x01030006)),
+        ('textAppearance', (TYPE_ATTRIBUTE, 0x01010041)),
+        ('textColor', (TYPE_REFERENCE, 0)),
+        ('id', (TYPE_REFERENCE, 0x7f0a0000)),
+        ('icon', (TYPE_DYNAMIC_REFERENCE, 0x7f020000)),
+        ('textColorHint', (TYPE_DYNAMIC_ATTRIBUTE, 0x7f010000)),
+    ]))
+    xml.end('LinearLayout')
+    xml.namespace('android', ANDROID, end=True)
+    return xml.build(LAYOUT_IDS)
+
+
+def namespaces_xml():
+    """tests/fixtures/namespaces.xml: a layout whose namespaces are
+    declared on inner elements, one of them shadowing an outer prefix, and
+    which uses the tools namespace and a custom one without declaring them
+    (as obfuscators leave documents)"""
+    app = 'http://schemas.android.com/apk/res-auto'
+    lib = 'http://schemas.android.com/apk/res/com.example.lib'
+    tools = 'http://schemas.android.com/tools'
+    widgets = 'http://example.com/widgets'
+
+    xml = XmlBuilder()
+    xml.namespace('android', ANDROID)
+    xml.start('LinearLayout', [(ANDROID, 'layout_width', None, TYPE_INT_DEC, NO_INDEX)])
+    xml.namespace('app', app, line=2)
+    xml.start('Inner', [(app, 'custom', 'v', TYPE_STRING, 0), (tools, 'ignore', 'All', TYPE_STRING, 0)], line=2)
+    xml.start('widget', [(widgets, 'color', 'red', TYPE_STRING, 0)], ns=widgets, line=3)
+    xml.end('widget', ns=widgets, line=3)
+    xml.start('widget', [(widgets, 'color', 'blue', TYPE_STRING, 0)], ns=widgets, line=4)
+    xml.end('widget', ns=widgets, line=4)
+    xml.namespace('app', lib, line=5)
+    xml.start('Shadowed', [(lib, 'flag', 'x', TYPE_STRING, 0)], line=5)
+    xml.end('Shadowed', line=5)
+    xml.namespace('app', lib, end=True, line=5)
+    xml.start('After', [(app, 'custom', 'w', TYPE_STRING, 0)], line=6)
+    xml.end('After', line=6)
+    xml.end('Inner', line=7)
+    xml.namespace('app', app, end=True, line=7)
+    xml.namespace('w', widgets, line=8)
+    xml.start('item', [(widgets, 'k', 'v', TYPE_STRING, 0)], ns=widgets, line=8)
+    xml.end('item', ns=widgets, line=8)
+    xml.namespace('w', widgets, end=True, line=8)
+    xml.end('LinearLayout', line=9)
+    xml.namespace('android', ANDROID, end=True, line=9)
+    return xml.build(LAYOUT_IDS)
+
+
+def varint(value):
+    value &= (1 << 64) - 1
+    raw = b''
+    while True:
+        byte = value & 0x7f
+        value >>= 7
+        if not value:
+            return raw + bytes([byte])
+        raw += bytes([byte | 0x80])
+
+
+def proto_varint(field, value):
+    return varint(field << 3) + varint(value)
+
+
+def proto_bytes(field, data):
+    if isinstance(data, str):
+        data = data.encode('utf-8')
+    return varint(field << 3 | 2) + varint(len(data)) + data
+
+
+def proto_attribute(name, value='', uri=ANDROID, resource_id=0, item=None, line=None):
+    """XmlAttribute of aapt2's Resources.proto"""
+    raw = proto_bytes(1, uri) if uri else b''
+    raw += proto_bytes(2, name)
+    if value:
+        raw += proto_bytes(3, value)
+    if line is not None:
+        raw += proto_bytes(4, proto_varint(1, line))
+    if resource_id:
+        raw += proto_varint(5, resource_id)
+    if item is not None:
+        raw += proto_bytes(6, item)
+    return proto_bytes(4, raw)
+
+
+def proto_reference(resource_id, name='', attribute=False):
+    """Item holding a Reference, named or not"""
+    raw = proto_varint(1, 1) if attribute else b''
+    raw += proto_varint(2, resource_id)
+    if name:
+        raw += proto_bytes(3, name)
+    return proto_bytes(1, raw)
+
+
+def proto_primitive(field, value):
+    """Item holding a Primitive, `field` being its type (6 for a decimal
+    integer, 8 a boolean, 9 an ARGB colour, 13 a dimension)"""
+    return proto_bytes(7, proto_varint(field, value))
+
+
+def proto_string(string):
+    return proto_bytes(2, proto_bytes(1, string))
+
+
+def proto_source(line, column=None):
+    """SourcePosition of a node"""
+    return proto_varint(1, line) + (proto_varint(2, column) if column is not None else b'')
+
+
+def proto_element(name, attrs=b'', children=(), namespaces=b'', line=1, column=None):
+    """XmlNode holding an XmlElement, with its source line"""
+    raw = namespaces + proto_bytes(3, name) + attrs + b''.join(proto_bytes(5, child) for child in children)
+    return proto_bytes(1, raw) + proto_bytes(3, proto_source(line, column))
+
+
+def proto_text(text, line, column=None):
+    """XmlNode holding text"""
+    return proto_bytes(2, text) + proto_bytes(3, proto_source(line, column))
+
+
+def proto_namespace(prefix, uri):
+    return proto_bytes(1, proto_bytes(1, prefix) + proto_bytes(2, uri))
+
+
+def proto_manifest_xml():
+    """tests/fixtures/proto_manifest.xml: a manifest as stored in an Android
+    App Bundle (or a proto APK), whose label and activity name refer to
+    `string/app_name` of resources.arsc by ID only, without the name aapt2
+    usually records"""
+    app_name = proto_reference(0x7f030000)
+    return proto_element('manifest',
+        proto_attribute('package', 'com.example.app', uri=None),
+        [proto_element('application', proto_attribute('label', resource_id=0x01010001, item=app_name),
+            [proto_element('activity', proto_attribute('name', resource_id=0x01010003, item=app_name), line=4),
+             proto_element('service', proto_attribute('name', '.Sync', resource_id=0x01010003,
+                                                      item=proto_string('.Sync')), line=5)],
+            line=3)],
+        namespaces=proto_namespace('android', ANDROID))
+
+
+def proto_layout_xml():
+    """tests/fixtures/layout.xml: a layout as stored in an Android App
+    Bundle, with typed items, references by ID only (the textAppearance and
+    the style), text, and an attribute of the app's namespace"""
+    app = 'http://schemas.android.com/apk/res-auto'
+    attrs = proto_attribute('orientation', resource_id=0x010100c4, item=proto_primitive(6, 1), line=1)
+    attrs += proto_attribute('layout_width', resource_id=0x010100f4, item=proto_primitive(13, 0x1001), line=1)
+    attrs += proto_attribute('background', resource_id=0x010100d4, item=proto_primitive(9, 0xff00ff00), line=1)
+    attrs += proto_attribute('visible', uri=app, item=proto_primitive(8, 1), line=1)
+    attrs += proto_attribute('text', resource_id=0x0101014f, item=proto_reference(0x7f030000, 'string/app_name'),
+                             line=1)
+    attrs += proto_attribute('textAppearance', resource_id=0x01010034,
+                             item=proto_reference(0x01010041, attribute=True), line=1)
+    attrs += proto_attribute('style', uri=None, item=proto_reference(0x01030237), line=1)
+    text_view = proto_element('TextView',
+        proto_attribute('text', 'raw text', resource_id=0x0101014f, item=proto_string('raw text'), line=1)
+        + proto_attribute('tag', 'plain', uri=app, line=1),
+        line=3, column=4)
+    return proto_element('LinearLayout', attrs, [proto_text('Hello & <world>', 2, 4), text_view],
+                         namespaces=proto_namespace('android', ANDROID) + proto_namespace('app', app),
+                         line=1, column=4)
+
+
+def main():
+    output_dir = sys.argv[1] if len(sys.argv) > 1 else os.path.join(os.path.dirname(__file__), '..', 'tests', 'fixtures')
+
+    fixtures = {
+        'resources.arsc': resources_arsc(),
+        'framework.arsc': framework_arsc(),
+        'padded.arsc': padded_resources_arsc(),
+        'strings.arsc': strings_arsc(),
+        'empty_strings.arsc': empty_strings_arsc(),
+        'text.xml': text_xml(),
+        'cdata.xml': cdata_xml(),
+        'long_text.xml': long_text_xml(),
+        'empty_strings.xml': empty_strings_xml(),
+        'dimensions.xml': dimensions_xml(),
+        'colors.xml': colors_xml(),
+        'references.xml': references_xml(),
+        'namespaces.xml': namespaces_xml(),
+        'proto_manifest.xml': proto_manifest_xml(),
+        'layout.xml': proto_layout_xml(),
+    }
+    fixtures['app.apk'] = zip_file(apk())
+    for name, files in bundles().items():
+        fixtures[name] = zip_file(files)
+
+    for name, contents in fixtures.items():
+        with open(os.path.join(output_dir, name), 'wb') as fixture:
+            fixture.write(contents)
+
+
+if __name__ == '__main__':
+    main()